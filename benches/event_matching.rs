@@ -0,0 +1,46 @@
+// Scan latency of cross-venue event matching as the market count grows
+
+use chrono::{Duration, Utc};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use polymarket_kalshi_arbitrage_bot::{Event, EventMatcher, Platform};
+
+const ASSETS: &[&str] = &["BTC", "ETH", "SOL", "XRP", "DOGE", "ADA", "AVAX", "LINK"];
+
+/// `count` price-threshold markets on `platform`, phrased the way each venue does,
+/// resolving across a month so blocking has buckets to skip
+fn events(platform: Platform, count: usize) -> Vec<Event> {
+    let now = Utc::now();
+    (0..count)
+        .map(|i| {
+            let asset = ASSETS[i % ASSETS.len()];
+            let threshold = 1_000 + (i / ASSETS.len()) * 250;
+            let title = match platform {
+                Platform::Kalshi => format!("{} above ${} on close?", asset, threshold),
+                _ => format!("Will {} be above ${}?", asset, threshold),
+            };
+            Event::new(platform, format!("{}-{}", platform, i), title, String::new())
+                .with_category("Crypto".to_string())
+                .with_resolution_date(now + Duration::hours(1 + (i % 720) as i64))
+        })
+        .collect()
+}
+
+fn find_matches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_matches");
+    group.sample_size(10);
+    for count in [100, 250, 500] {
+        let pm = events(Platform::Polymarket, count);
+        let kalshi = events(Platform::Kalshi, count);
+        for blocking in [false, true] {
+            let matcher = EventMatcher::new(0.7).with_blocking(blocking);
+            let name = if blocking { "blocked" } else { "all_pairs" };
+            group.bench_with_input(BenchmarkId::new(name, count), &count, |b, _| {
+                b.iter(|| matcher.find_matches_with_confidence(&pm, &kalshi))
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, find_matches);
+criterion_main!(benches);
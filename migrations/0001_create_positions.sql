@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS positions (
+    id          TEXT PRIMARY KEY NOT NULL,
+    arb_id      TEXT,
+    platform    TEXT NOT NULL,
+    event_id    TEXT NOT NULL,
+    event_title TEXT NOT NULL,
+    outcome     TEXT NOT NULL,
+    amount      REAL NOT NULL,
+    cost        REAL NOT NULL,
+    price       REAL NOT NULL,
+    fees_paid   REAL NOT NULL DEFAULT 0,
+    order_id    TEXT,
+    status      TEXT NOT NULL,
+    created_at  TEXT NOT NULL,
+    settled_at  TEXT,
+    payout      REAL,
+    profit      REAL
+);
+
+CREATE INDEX IF NOT EXISTS idx_positions_status ON positions (status);
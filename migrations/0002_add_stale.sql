@@ -0,0 +1 @@
+ALTER TABLE positions ADD COLUMN stale INTEGER NOT NULL DEFAULT 0;
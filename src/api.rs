@@ -0,0 +1,180 @@
+// HTTP API over the running bot's state (enabled with the `api` feature)
+
+use crate::approval::{ApprovalQueue, PendingOpportunity};
+use crate::position_tracker::{Position, PositionStatistics, PositionTracker};
+use crate::settlement_checker::SettlementChecker;
+use crate::trade_executor::{TradeExecutor, TradeResult};
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Path, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+#[derive(Clone)]
+struct ApiState {
+    position_tracker: Arc<Mutex<PositionTracker>>,
+    settlement_checker: Arc<SettlementChecker>,
+    approval_queue: ApprovalQueue,
+    trade_executor: Arc<TradeExecutor>,
+    auth_token: Option<Arc<str>>, // Bearer token for routes that trade; none disables them
+}
+
+#[derive(Debug, Serialize)]
+struct Balances {
+    polymarket: f64,
+    kalshi: f64,
+    total: f64,
+}
+
+/// Routes: `GET /positions`, `POST /positions/close-all`, `GET /statistics`,
+/// `GET /balances`, and `GET /approvals` with `POST /approvals/:id/approve` / `.../reject`.
+/// The `POST` routes need `Authorization: Bearer <auth_token>`, and are
+/// refused outright without an `auth_token`.
+pub fn router(
+    position_tracker: Arc<Mutex<PositionTracker>>,
+    settlement_checker: Arc<SettlementChecker>,
+    approval_queue: ApprovalQueue,
+    trade_executor: Arc<TradeExecutor>,
+    auth_token: Option<String>,
+) -> Router {
+    let state = ApiState {
+        position_tracker,
+        settlement_checker,
+        approval_queue,
+        trade_executor,
+        auth_token: auth_token.filter(|token| !token.is_empty()).map(Arc::from),
+    };
+
+    let authenticated = Router::new()
+        .route("/positions/close-all", post(close_all))
+        .route("/approvals/:id/approve", post(approve))
+        .route("/approvals/:id/reject", post(reject))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    Router::new()
+        .route("/positions", get(positions))
+        .route("/statistics", get(statistics))
+        .route("/balances", get(balances))
+        .route("/approvals", get(approvals))
+        .merge(authenticated)
+        .with_state(state)
+}
+
+/// Serve the API on localhost:`port` until `shutdown` fires
+pub async fn serve(
+    position_tracker: Arc<Mutex<PositionTracker>>,
+    settlement_checker: Arc<SettlementChecker>,
+    approval_queue: ApprovalQueue,
+    trade_executor: Arc<TradeExecutor>,
+    auth_token: Option<String>,
+    port: u16,
+    shutdown: CancellationToken,
+) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Failed to bind API port {}", port))?;
+
+    info!("🌐 API available at http://127.0.0.1:{}", port);
+    let app = router(position_tracker, settlement_checker, approval_queue, trade_executor, auth_token);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown.cancelled_owned())
+        .await
+        .context("API server failed")
+}
+
+/// Reject requests without the configured bearer token
+async fn require_token(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let Some(expected) = &state.auth_token else {
+        return (StatusCode::FORBIDDEN, "Set API_TOKEN to enable this route").into_response();
+    };
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match provided {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => next.run(request).await,
+        _ => StatusCode::UNAUTHORIZED.into_response(),
+    }
+}
+
+/// Compare without short-circuiting, so response timing doesn't leak the token
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn positions(State(state): State<ApiState>) -> Json<Vec<Position>> {
+    let tracker = state.position_tracker.lock().await;
+    let mut positions: Vec<Position> = tracker.get_all_positions().into_iter().cloned().collect();
+    positions.sort_by_key(|p| p.created_at);
+    Json(positions)
+}
+
+/// Sell every open position. Per-position failures are reported in the
+/// results (as `error`) rather than failing the request.
+async fn close_all(State(state): State<ApiState>) -> Json<Vec<TradeResult>> {
+    let results = state
+        .trade_executor
+        .close_all_open(&state.position_tracker)
+        .await
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or_else(|e| TradeResult {
+                success: false,
+                skipped: false,
+                polymarket_order_id: None,
+                kalshi_order_id: None,
+                error: Some(e.to_string()),
+            })
+        })
+        .collect();
+    Json(results)
+}
+
+async fn statistics(State(state): State<ApiState>) -> Json<PositionStatistics> {
+    Json(state.position_tracker.lock().await.get_statistics())
+}
+
+async fn balances(State(state): State<ApiState>) -> Result<Json<Balances>, (StatusCode, String)> {
+    let (polymarket, kalshi) = state
+        .settlement_checker
+        .check_balances()
+        .await
+        .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+
+    Ok(Json(Balances {
+        polymarket,
+        kalshi,
+        total: polymarket + kalshi,
+    }))
+}
+
+async fn approvals(State(state): State<ApiState>) -> Json<Vec<PendingOpportunity>> {
+    Json(state.approval_queue.pending().await)
+}
+
+async fn approve(State(state): State<ApiState>, Path(id): Path<String>) -> StatusCode {
+    if state.approval_queue.approve(&id).await {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+async fn reject(State(state): State<ApiState>, Path(id): Path<String>) -> StatusCode {
+    if state.approval_queue.reject(&id).await {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
@@ -0,0 +1,160 @@
+// Human sign-off for matches the bot isn't confident enough to trade alone
+
+use crate::arbitrage_detector::ArbitrageOpportunity;
+use crate::event::Event;
+use crate::event_matcher::MatchConfidence;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::info;
+
+/// Which opportunities may execute without a human approving them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalPolicy {
+    /// Trade everything above the similarity threshold (default)
+    #[default]
+    AutoExecute,
+    /// Trade high-confidence matches; queue medium-confidence ones for approval
+    ApproveMediumConfidence,
+}
+
+impl ApprovalPolicy {
+    /// Whether a match scored `confidence` has to wait for approval
+    pub fn requires_approval(&self, confidence: &MatchConfidence) -> bool {
+        match self {
+            ApprovalPolicy::AutoExecute => false,
+            ApprovalPolicy::ApproveMediumConfidence => !confidence.is_high_confidence(),
+        }
+    }
+}
+
+/// An opportunity waiting for a human decision
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingOpportunity {
+    pub id: String,
+    pub pm_event: Event,
+    pub kalshi_event: Event,
+    pub opportunity: ArbitrageOpportunity,
+    pub trade_size: f64,
+    pub confidence: MatchConfidence,
+    pub queued_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    pending: Vec<PendingOpportunity>,
+    approved: Vec<PendingOpportunity>,
+}
+
+/// Shared queue of opportunities awaiting approval. Clones share the same
+/// queue, so the bot, the scan loop and the API can each hold one.
+#[derive(Clone)]
+pub struct ApprovalQueue {
+    ttl: Duration,
+    state: Arc<Mutex<QueueState>>,
+}
+
+impl Default for ApprovalQueue {
+    fn default() -> Self {
+        Self::new(Duration::minutes(5))
+    }
+}
+
+impl ApprovalQueue {
+    /// Entries older than `ttl` are dropped - their prices are no longer tradable
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            state: Arc::new(Mutex::new(QueueState::default())),
+        }
+    }
+
+    /// Queue an opportunity, replacing any pending entry for the same pair
+    /// (so each scan refreshes prices rather than piling up duplicates)
+    pub async fn push(
+        &self,
+        pm_event: Event,
+        kalshi_event: Event,
+        opportunity: ArbitrageOpportunity,
+        trade_size: f64,
+        confidence: MatchConfidence,
+    ) -> String {
+        let mut state = self.state.lock().await;
+        let same_pair = |p: &PendingOpportunity| {
+            p.pm_event.event_id == pm_event.event_id && p.kalshi_event.event_id == kalshi_event.event_id
+        };
+
+        let id = state
+            .pending
+            .iter()
+            .find(|p| same_pair(p))
+            .map(|p| p.id.clone())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        state.pending.retain(|p| !same_pair(p));
+
+        info!(
+            approval_id = %id,
+            pm_event_id = %pm_event.event_id,
+            kalshi_event_id = %kalshi_event.event_id,
+            score = confidence.overall_score,
+            "🙋 Opportunity awaiting approval: {}",
+            pm_event.title
+        );
+        state.pending.push(PendingOpportunity {
+            id: id.clone(),
+            pm_event,
+            kalshi_event,
+            opportunity,
+            trade_size,
+            confidence,
+            queued_at: Utc::now(),
+        });
+        id
+    }
+
+    /// Opportunities still waiting for a decision, oldest first
+    pub async fn pending(&self) -> Vec<PendingOpportunity> {
+        let mut state = self.state.lock().await;
+        self.expire(&mut state);
+        state.pending.clone()
+    }
+
+    /// Approve a pending opportunity for execution on the next scan.
+    /// Returns false if `id` is unknown or has expired.
+    pub async fn approve(&self, id: &str) -> bool {
+        let mut state = self.state.lock().await;
+        self.expire(&mut state);
+        let Some(index) = state.pending.iter().position(|p| p.id == id) else {
+            return false;
+        };
+
+        let approved = state.pending.remove(index);
+        info!("👍 Approved opportunity {}", approved.id);
+        state.approved.push(approved);
+        true
+    }
+
+    /// Discard a pending opportunity. Returns false if `id` is unknown.
+    pub async fn reject(&self, id: &str) -> bool {
+        let mut state = self.state.lock().await;
+        let before = state.pending.len();
+        state.pending.retain(|p| p.id != id);
+        let removed = state.pending.len() < before;
+        if removed {
+            info!("👎 Rejected opportunity {}", id);
+        }
+        removed
+    }
+
+    /// Remove and return approved opportunities, for the executor
+    pub async fn take_approved(&self) -> Vec<PendingOpportunity> {
+        std::mem::take(&mut self.state.lock().await.approved)
+    }
+
+    fn expire(&self, state: &mut QueueState) {
+        let cutoff = Utc::now() - self.ttl;
+        state.pending.retain(|p| p.queued_at > cutoff);
+    }
+}
@@ -1,10 +1,43 @@
-use crate::event::MarketPrices;
+use crate::event::{normalize_outcome, MarketPrices};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, RwLock};
 
-#[derive(Debug, Clone)]
+/// One order to place: side ("BUY"), outcome ("YES"/"NO" or an outcome name)
+/// and limit price per share
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderAction {
+    pub side: String,
+    pub outcome: String,
+    pub price: f64,
+}
+
+impl OrderAction {
+    pub fn new(side: impl Into<String>, outcome: impl Into<String>, price: f64) -> Self {
+        Self {
+            side: side.into(),
+            outcome: outcome.into(),
+            price,
+        }
+    }
+
+    pub fn buy(outcome: impl Into<String>, price: f64) -> Self {
+        Self::new("BUY", outcome, price)
+    }
+}
+
+/// e.g. "BUY YES @ 0.4500"
+impl fmt::Display for OrderAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} @ {:.4}", self.side, self.outcome, self.price)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbitrageOpportunity {
     pub strategy: String,
-    pub kalshi_action: (String, String, f64), // (action, outcome, price)
-    pub polymarket_action: (String, String, f64),
+    pub kalshi_action: OrderAction,
+    pub polymarket_action: OrderAction,
     pub total_cost: f64,
     pub gross_profit: f64,
     pub fees: f64,
@@ -12,15 +45,153 @@ pub struct ArbitrageOpportunity {
     pub roi_percent: f64,
 }
 
+impl ArbitrageOpportunity {
+    /// Flip the Kalshi leg's outcome for a match whose Kalshi market is phrased
+    /// with opposite polarity (its YES is Polymarket's NO)
+    pub fn invert_kalshi_outcome(&mut self) {
+        let flipped = if normalize_outcome(&self.kalshi_action.outcome) == "YES" { "NO" } else { "YES" };
+        self.kalshi_action.outcome = flipped.to_string();
+
+        let title_case = |outcome: &str| if outcome == "YES" { "Yes" } else { "No" };
+        self.strategy = format!(
+            "Buy {} on Kalshi + Buy {} on Polymarket (inverted match)",
+            title_case(&self.kalshi_action.outcome),
+            title_case(&self.polymarket_action.outcome)
+        );
+    }
+}
+
+/// e.g. "Buy Yes on Kalshi + Buy No on Polymarket [Kalshi BUY YES @ 0.4500,
+/// Polymarket BUY NO @ 0.5000] - Net profit: $0.0400, ROI: 4.21%"
+impl fmt::Display for ArbitrageOpportunity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [Kalshi {}, Polymarket {}] - Net profit: ${:.4}, ROI: {:.2}%",
+            self.strategy, self.kalshi_action, self.polymarket_action, self.net_profit, self.roi_percent
+        )
+    }
+}
+
+/// Buying one outcome on every leg of a Polymarket market with mutually
+/// exclusive outcomes, so the basket pays out whichever one resolves YES
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasketOpportunity {
+    pub strategy: String,
+    pub legs: Vec<OrderAction>, // One per outcome of the market
+    pub total_cost: f64,        // Price of one share of every leg
+    pub payout: f64,            // Guaranteed return on that basket
+    pub gross_profit: f64,
+    pub fees: f64,
+    pub net_profit: f64,
+    pub roi_percent: f64,
+}
+
+/// e.g. "Buy NO on all 3 outcomes on Polymarket [BUY Alice @ 0.4000, ...] -
+/// Net profit: $0.0700, ROI: 7.29%"
+impl fmt::Display for BasketOpportunity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [", self.strategy)?;
+        for (i, leg) in self.legs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", leg)?;
+        }
+        write!(
+            f,
+            "] - Net profit: ${:.4}, ROI: {:.2}%",
+            self.net_profit, self.roi_percent
+        )
+    }
+}
+
+#[derive(Clone)]
 pub struct ArbitrageDetector {
     min_profit_threshold: f64,
-    fees: Fees,
+    min_roi_percent: f64,      // Net profit as a % of cost must also reach this
+    polymarket_fee: Arc<dyn FeeModel>,
+    kalshi_fee: Arc<dyn FeeModel>,
+    gas_cost_usd: Arc<RwLock<Option<f64>>>, // Polygon gas per Polymarket order, shared by clones
+    slippage_bps: f64,         // Adverse price move tolerated between detection and fill
+}
+
+/// Highest limit price to order at - venues reject prices of $1 or more
+const MAX_LIMIT_PRICE: f64 = 0.99;
+
+/// Kalshi's trading fee coefficient (see `kalshi_fee`)
+const KALSHI_FEE_RATE: f64 = 0.07;
+
+/// Kalshi trading fee in dollars: `round_up(0.07 * contracts * price * (1 - price))`,
+/// rounded up to the next cent
+pub fn kalshi_fee(contracts: f64, price: f64) -> f64 {
+    let raw = KALSHI_FEE_RATE * contracts * price * (1.0 - price);
+    // Trim float noise before rounding up so exact cents don't bump a cent
+    (raw * 100.0 - 1e-9).ceil().max(0.0) / 100.0
+}
+
+/// A platform's trading fee, which may depend on price and order size
+pub trait FeeModel: Send + Sync {
+    /// Fee in dollars for buying `contracts` at `price`
+    fn fee(&self, price: f64, contracts: f64) -> f64;
+
+    /// Fee per contract when the order size isn't known yet (top-of-book checks)
+    fn rate(&self, price: f64) -> f64 {
+        self.fee(price, 1.0)
+    }
+}
+
+/// Flat fee per contract, independent of price and size
+#[derive(Debug, Clone, Copy)]
+pub struct FlatFee(pub f64);
+
+impl FeeModel for FlatFee {
+    fn fee(&self, _price: f64, contracts: f64) -> f64 {
+        self.0 * contracts
+    }
+}
+
+/// Kalshi's price-dependent fee (`kalshi_fee`), rounded up per order
+#[derive(Debug, Clone, Copy)]
+pub struct KalshiFee;
+
+impl FeeModel for KalshiFee {
+    fn fee(&self, price: f64, contracts: f64) -> f64 {
+        kalshi_fee(contracts, price)
+    }
+
+    // Without a size there's no order to round, so use the unrounded rate
+    fn rate(&self, price: f64) -> f64 {
+        KALSHI_FEE_RATE * price * (1.0 - price)
+    }
+}
+
+/// Fee per contract for a leg of `trade_size` USDC bought at `price`,
+/// including per-order rounding when the size is known
+fn fee_per_contract(model: &dyn FeeModel, price: f64, trade_size: Option<f64>) -> f64 {
+    match trade_size {
+        Some(size) if size > 0.0 && price > 0.0 => {
+            let contracts = size / price;
+            model.fee(price, contracts) / contracts
+        }
+        _ => model.rate(price),
+    }
 }
 
+/// Which fee model `Fees` selects for Kalshi
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeeKind {
+    Flat,   // Flat fraction per contract pair (`Fees` field)
+    Kalshi, // Kalshi's price-dependent formula (`kalshi_fee`)
+}
+
+/// Fee settings as configured; `models` turns them into `FeeModel`s
 #[derive(Debug, Clone)]
 pub struct Fees {
     pub polymarket: f64,
     pub kalshi: f64,
+    pub kalshi_model: FeeKind,
 }
 
 impl Default for Fees {
@@ -28,68 +199,358 @@ impl Default for Fees {
         Self {
             polymarket: 0.01, // 1%
             kalshi: 0.01,     // 1%
+            kalshi_model: FeeKind::Flat,
         }
     }
 }
 
+impl Fees {
+    /// Read `POLYMARKET_FEE` and `KALSHI_FEE` (as fractions, e.g. 0.01 = 1%),
+    /// falling back to the defaults for any that are unset or invalid
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let read = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+
+        // KALSHI_FEE_MODEL=kalshi selects the real fee formula over the flat rate
+        let kalshi_model = match std::env::var("KALSHI_FEE_MODEL").ok().as_deref() {
+            Some(model) if model.eq_ignore_ascii_case("kalshi") => FeeKind::Kalshi,
+            _ => defaults.kalshi_model,
+        };
+
+        Self {
+            polymarket: read("POLYMARKET_FEE", defaults.polymarket),
+            kalshi: read("KALSHI_FEE", defaults.kalshi),
+            kalshi_model,
+        }
+    }
+
+    /// (Polymarket, Kalshi) fee models for these settings
+    pub fn models(&self) -> (Arc<dyn FeeModel>, Arc<dyn FeeModel>) {
+        let kalshi: Arc<dyn FeeModel> = match self.kalshi_model {
+            FeeKind::Flat => Arc::new(FlatFee(self.kalshi)),
+            FeeKind::Kalshi => Arc::new(KalshiFee),
+        };
+        (Arc::new(FlatFee(self.polymarket)), kalshi)
+    }
+}
+
 impl ArbitrageDetector {
     pub fn new(min_profit_threshold: f64) -> Self {
+        let (polymarket_fee, kalshi_fee) = Fees::default().models();
         Self {
             min_profit_threshold,
-            fees: Fees::default(),
+            min_roi_percent: 0.0,
+            polymarket_fee,
+            kalshi_fee,
+            gas_cost_usd: Arc::new(RwLock::new(None)),
+            slippage_bps: 0.0,
         }
     }
 
-    pub fn with_fees(mut self, fees: Fees) -> Self {
-        self.fees = fees;
+    /// Also require `roi_percent` of at least this, so a cent of edge on a
+    /// near-$1 pair isn't taken just because it clears `min_profit_threshold`
+    pub fn with_min_roi_percent(mut self, min_roi_percent: f64) -> Self {
+        self.min_roi_percent = min_roi_percent;
+        self
+    }
+
+    pub fn with_slippage_bps(mut self, slippage_bps: f64) -> Self {
+        self.slippage_bps = slippage_bps;
+        self
+    }
+
+    /// Limit price for a leg, allowing for the configured slippage but capped
+    /// at `MAX_LIMIT_PRICE` (never below the price itself) so the order stays valid
+    fn limit_price(&self, price: f64) -> f64 {
+        (price * (1.0 + self.slippage_bps / 10_000.0)).min(MAX_LIMIT_PRICE.max(price))
+    }
+
+    /// Use flat/Kalshi fee settings (see `Fees::models`)
+    pub fn with_fees(self, fees: Fees) -> Self {
+        let (polymarket_fee, kalshi_fee) = fees.models();
+        self.with_fee_models(polymarket_fee, kalshi_fee)
+    }
+
+    /// Use custom fee models, e.g. a tiered schedule
+    pub fn with_fee_models(mut self, polymarket_fee: Arc<dyn FeeModel>, kalshi_fee: Arc<dyn FeeModel>) -> Self {
+        self.polymarket_fee = polymarket_fee;
+        self.kalshi_fee = kalshi_fee;
+        self
+    }
+
+    /// Start from a fixed gas cost, no longer shared with detectors this was cloned from
+    pub fn with_gas_cost(mut self, gas_cost_usd: f64) -> Self {
+        self.gas_cost_usd = Arc::new(RwLock::new(Some(gas_cost_usd)));
         self
     }
 
+    /// Update the gas cost for this detector and every clone of it (e.g. the
+    /// executor's copy used to re-check prices before ordering)
+    pub fn set_gas_cost(&self, gas_cost_usd: Option<f64>) {
+        *self.gas_cost_usd.write().unwrap() = gas_cost_usd;
+    }
+
+    pub fn gas_cost(&self) -> Option<f64> {
+        *self.gas_cost_usd.read().unwrap()
+    }
+
+    /// Check for arbitrage at the top-of-book asks, ignoring fixed per-trade costs (gas)
     pub fn check_arbitrage(
         &self,
         pm_prices: &MarketPrices,
         kalshi_prices: &MarketPrices,
     ) -> Option<ArbitrageOpportunity> {
+        self.evaluate(pm_prices, kalshi_prices, None)
+    }
+
+    /// Check for arbitrage for a trade of `trade_size` USDC, amortizing gas
+    /// across the `trade_size / total_cost` contract pairs bought
+    pub fn check_arbitrage_for_size(
+        &self,
+        pm_prices: &MarketPrices,
+        kalshi_prices: &MarketPrices,
+        trade_size: f64,
+    ) -> Option<ArbitrageOpportunity> {
+        self.evaluate(pm_prices, kalshi_prices, Some(trade_size))
+    }
+
+    /// Check a market with mutually exclusive outcomes (exactly one resolves YES),
+    /// given each outcome's YES price on Polymarket.
+    ///
+    /// - YES prices summing below $1: buy YES on every outcome, one pays $1
+    /// - YES prices summing above $1 (negative risk): buy NO on every outcome,
+    ///   all but one pay $1, so N - 1 is returned for `N - sum(YES)`
+    ///
+    /// The basket has one leg per outcome, all on the same side.
+    pub fn check_multi_outcome_arbitrage(&self, outcomes: &[(String, f64)]) -> Option<BasketOpportunity> {
+        if outcomes.len() < 2 || outcomes.iter().any(|(_, price)| *price <= 0.0 || *price >= 1.0) {
+            return None;
+        }
+
+        let n = outcomes.len() as f64;
+        let yes_sum: f64 = outcomes.iter().map(|(_, price)| price).sum();
+
+        let (side, cost, payout) = if yes_sum < 1.0 {
+            ("YES", yes_sum, 1.0)
+        } else {
+            ("NO", n - yes_sum, n - 1.0)
+        };
+        let gross_profit = payout - cost;
+
+        // One fee per contract bought, plus the slippage buffer on the basket
+        let fees: f64 = outcomes
+            .iter()
+            .map(|(_, yes_price)| {
+                let price = if side == "YES" { *yes_price } else { 1.0 - yes_price };
+                self.polymarket_fee.rate(price)
+            })
+            .sum();
+        let slippage = cost * self.slippage_bps / 10_000.0;
+        if gross_profit <= fees + self.min_profit_threshold + slippage {
+            return None;
+        }
+
+        let legs = outcomes
+            .iter()
+            .map(|(name, yes_price)| {
+                let price = if side == "YES" { *yes_price } else { 1.0 - yes_price };
+                OrderAction::buy(name.clone(), self.limit_price(price))
+            })
+            .collect();
+
+        let opportunity = BasketOpportunity {
+            strategy: format!("Buy {} on all {} outcomes on Polymarket", side, outcomes.len()),
+            legs,
+            total_cost: cost,
+            payout,
+            gross_profit,
+            fees,
+            net_profit: gross_profit - fees,
+            roi_percent: ((gross_profit - fees) / cost) * 100.0,
+        };
+        (opportunity.roi_percent >= self.min_roi_percent).then_some(opportunity)
+    }
+
+    fn meets_min_roi(&self, opportunity: &ArbitrageOpportunity) -> bool {
+        opportunity.roi_percent >= self.min_roi_percent
+    }
+
+    fn evaluate(
+        &self,
+        pm_prices: &MarketPrices,
+        kalshi_prices: &MarketPrices,
+        trade_size: Option<f64>,
+    ) -> Option<ArbitrageOpportunity> {
+        // Buying pays the ask on each leg - bids would show profit we can't get
+        let (pm_yes, pm_no) = (pm_prices.yes_buy_price(), pm_prices.no_buy_price());
+        let (kalshi_yes, kalshi_no) = (kalshi_prices.yes_buy_price(), kalshi_prices.no_buy_price());
+
         // Strategy 1: Buy Yes on Kalshi + Buy No on Polymarket
-        let cost_strategy_1 = kalshi_prices.yes + pm_prices.no;
+        let cost_strategy_1 = kalshi_yes + pm_no;
         let profit_strategy_1 = 1.0 - cost_strategy_1;
 
         // Strategy 2: Buy No on Kalshi + Buy Yes on Polymarket
-        let cost_strategy_2 = kalshi_prices.no + pm_prices.yes;
+        let cost_strategy_2 = kalshi_no + pm_yes;
         let profit_strategy_2 = 1.0 - cost_strategy_2;
 
-        // Account for fees
-        let total_fees = self.fees.polymarket + self.fees.kalshi;
+        // Fees per contract pair, from each leg's price and the intended size
+        let fees_for = |kalshi_price: f64, pm_price: f64| {
+            fee_per_contract(self.polymarket_fee.as_ref(), pm_price, trade_size)
+                + fee_per_contract(self.kalshi_fee.as_ref(), kalshi_price, trade_size)
+        };
+
+        // Gas is a fixed cost per trade - spread it over the pairs bought
+        let gas_cost_usd = self.gas_cost();
+        let gas_per_pair = |cost: f64| match (gas_cost_usd, trade_size) {
+            (Some(gas), Some(size)) if size > 0.0 => gas * cost / size,
+            _ => 0.0,
+        };
+
+        // Slippage buffer, proportional to what we pay for the pair
+        let slippage = |cost: f64| cost * self.slippage_bps / 10_000.0;
 
         // Check Strategy 1
-        if profit_strategy_1 > total_fees + self.min_profit_threshold {
-            return Some(ArbitrageOpportunity {
+        let total_fees_1 = fees_for(kalshi_yes, pm_no) + gas_per_pair(cost_strategy_1);
+        let strategy_1 = (profit_strategy_1 > total_fees_1 + self.min_profit_threshold + slippage(cost_strategy_1))
+            .then(|| ArbitrageOpportunity {
                 strategy: "Buy Yes on Kalshi + Buy No on Polymarket".to_string(),
-                kalshi_action: ("BUY".to_string(), "YES".to_string(), kalshi_prices.yes),
-                polymarket_action: ("BUY".to_string(), "NO".to_string(), pm_prices.no),
+                kalshi_action: OrderAction::buy("YES", self.limit_price(kalshi_yes)),
+                polymarket_action: OrderAction::buy("NO", self.limit_price(pm_no)),
                 total_cost: cost_strategy_1,
                 gross_profit: profit_strategy_1,
-                fees: total_fees,
-                net_profit: profit_strategy_1 - total_fees,
-                roi_percent: ((profit_strategy_1 - total_fees) / cost_strategy_1) * 100.0,
+                fees: total_fees_1,
+                net_profit: profit_strategy_1 - total_fees_1,
+                roi_percent: ((profit_strategy_1 - total_fees_1) / cost_strategy_1) * 100.0,
             });
-        }
 
         // Check Strategy 2
-        if profit_strategy_2 > total_fees + self.min_profit_threshold {
-            return Some(ArbitrageOpportunity {
+        let total_fees_2 = fees_for(kalshi_no, pm_yes) + gas_per_pair(cost_strategy_2);
+        let strategy_2 = (profit_strategy_2 > total_fees_2 + self.min_profit_threshold + slippage(cost_strategy_2))
+            .then(|| ArbitrageOpportunity {
                 strategy: "Buy No on Kalshi + Buy Yes on Polymarket".to_string(),
-                kalshi_action: ("BUY".to_string(), "NO".to_string(), kalshi_prices.no),
-                polymarket_action: ("BUY".to_string(), "YES".to_string(), pm_prices.yes),
+                kalshi_action: OrderAction::buy("NO", self.limit_price(kalshi_no)),
+                polymarket_action: OrderAction::buy("YES", self.limit_price(pm_yes)),
                 total_cost: cost_strategy_2,
                 gross_profit: profit_strategy_2,
-                fees: total_fees,
-                net_profit: profit_strategy_2 - total_fees,
-                roi_percent: ((profit_strategy_2 - total_fees) / cost_strategy_2) * 100.0,
+                fees: total_fees_2,
+                net_profit: profit_strategy_2 - total_fees_2,
+                roi_percent: ((profit_strategy_2 - total_fees_2) / cost_strategy_2) * 100.0,
             });
+
+        // If both sides are profitable, take the bigger edge
+        let strategy_1 = strategy_1.filter(|o| self.meets_min_roi(o));
+        let strategy_2 = strategy_2.filter(|o| self.meets_min_roi(o));
+        match (strategy_1, strategy_2) {
+            (Some(s1), Some(s2)) => Some(if s2.net_profit > s1.net_profit { s2 } else { s1 }),
+            (s1, s2) => s1.or(s2),
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::validate_order;
+    use crate::event::MarketPrices;
+
+    /// Detector with no fees, so only slippage stands between edge and profit
+    fn fee_free() -> ArbitrageDetector {
+        ArbitrageDetector::new(0.0).with_fee_models(Arc::new(FlatFee(0.0)), Arc::new(FlatFee(0.0)))
+    }
+
+    /// Polymarket YES and Kalshi NO together costing `cost`
+    fn pair_costing(cost: f64) -> (MarketPrices, MarketPrices) {
+        let half = cost / 2.0;
+        (MarketPrices::new(half, 1.0 - half, 10_000.0), MarketPrices::new(1.0 - half, half, 10_000.0))
+    }
+
+    #[test]
+    fn slippage_buffer_kills_thinner_edges() {
+        let detector = fee_free().with_slippage_bps(50.0);
+
+        // 40 bps of edge doesn't cover a 50 bps buffer, 100 bps does
+        let (pm, kalshi) = pair_costing(0.996);
+        assert!(detector.check_arbitrage(&pm, &kalshi).is_none());
+        let (pm, kalshi) = pair_costing(0.99);
+        let opportunity = detector.check_arbitrage(&pm, &kalshi).expect("100 bps edge survives");
+
+        // Each leg's limit is bumped by the buffer
+        assert!((opportunity.polymarket_action.price - 0.495 * 1.005).abs() < 1e-9);
+        assert!((opportunity.kalshi_action.price - 0.495 * 1.005).abs() < 1e-9);
+    }
+
+    #[test]
+    fn limit_price_stays_below_one() {
+        let detector = fee_free().with_slippage_bps(200.0);
+
+        let limit = detector.limit_price(0.985);
+        assert_eq!(limit, MAX_LIMIT_PRICE);
+        assert!(validate_order(10.0, limit).is_ok());
+        assert!((detector.limit_price(0.50) - 0.51).abs() < 1e-9);
+    }
+
+    /// Three mutually exclusive outcomes at these YES prices
+    fn three_way(prices: [f64; 3]) -> Vec<(String, f64)> {
+        ["Alice", "Bob", "Carol"].into_iter().map(String::from).zip(prices).collect()
+    }
+
+    #[test]
+    fn basket_below_par_buys_every_yes() {
+        let basket = fee_free()
+            .check_multi_outcome_arbitrage(&three_way([0.30, 0.30, 0.32]))
+            .expect("YES prices sum to 0.92");
+
+        assert!(basket.legs.iter().all(|leg| leg.side == "BUY"));
+        let prices: Vec<f64> = basket.legs.iter().map(|leg| leg.price).collect();
+        assert_eq!(prices, [0.30, 0.30, 0.32]);
+        assert_eq!(basket.payout, 1.0);
+        assert!((basket.net_profit - 0.08).abs() < 1e-9);
+    }
+
+    #[test]
+    fn basket_above_par_buys_every_no() {
+        // YES sums to 1.10: three NOs cost 1.90 and two of them pay out
+        let basket = fee_free()
+            .check_multi_outcome_arbitrage(&three_way([0.40, 0.35, 0.35]))
+            .expect("negative risk basket");
+
+        assert_eq!(basket.legs[0].outcome, "Alice");
+        assert!((basket.legs[0].price - 0.60).abs() < 1e-9);
+        assert!((basket.total_cost - 1.90).abs() < 1e-9);
+        assert_eq!(basket.payout, 2.0);
+        assert!((basket.net_profit - 0.10).abs() < 1e-9);
+        assert!(basket.strategy.contains("NO"));
+    }
+
+    #[test]
+    fn basket_at_par_or_after_fees_is_none() {
+        assert!(fee_free().check_multi_outcome_arbitrage(&three_way([0.30, 0.30, 0.40])).is_none());
+
+        // Three 1% fees eat a 2 cent edge
+        let detector = ArbitrageDetector::new(0.0);
+        assert!(detector.check_multi_outcome_arbitrage(&three_way([0.30, 0.30, 0.38])).is_none());
+    }
+
+    #[test]
+    fn kalshi_fee_peaks_at_even_odds() {
+        // 0.07 * 100 * 0.5 * 0.5 = $1.75 exactly, so no cent is added
+        assert_eq!(KalshiFee.fee(0.50, 100.0), 1.75);
+        // $0.0175 rounds up to two cents on a single contract
+        assert_eq!(KalshiFee.fee(0.50, 1.0), 0.02);
+        assert!((KalshiFee.rate(0.50) - 0.0175).abs() < 1e-12);
+    }
 
-        None
+    #[test]
+    fn kalshi_fee_near_certainty_rounds_up_to_a_cent() {
+        // 0.07 * 100 * 0.99 * 0.01 = $0.0693
+        assert_eq!(KalshiFee.fee(0.99, 100.0), 0.07);
+        assert_eq!(KalshiFee.fee(0.99, 1.0), 0.01);
+        assert!((KalshiFee.rate(0.99) - 0.000693).abs() < 1e-12);
     }
 }
 
@@ -0,0 +1,171 @@
+// Replay recorded price snapshots through the live matching and detection logic
+
+use crate::arbitrage_detector::ArbitrageDetector;
+use crate::event::{Event, MarketPrices};
+use crate::event_matcher::EventMatcher;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tracing::{debug, info};
+
+/// Top-of-book prices as recorded in a snapshot
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceSnapshot {
+    pub yes: f64,
+    pub no: f64,
+    pub liquidity: f64,
+}
+
+impl PriceSnapshot {
+    /// Market prices as they stood at `timestamp`
+    pub fn at(&self, timestamp: DateTime<Utc>) -> MarketPrices {
+        MarketPrices::new_at(self.yes, self.no, self.liquidity, timestamp)
+    }
+}
+
+/// One line of a backtest JSONL file
+#[derive(Debug, Clone, Deserialize)]
+pub struct BacktestRecord {
+    pub timestamp: DateTime<Utc>,
+    pub pm_event: Event,
+    pub kalshi_event: Event,
+    pub pm_prices: PriceSnapshot,
+    pub kalshi_prices: PriceSnapshot,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BacktestReport {
+    pub records: usize,
+    pub matched: usize,
+    pub opportunities: usize,
+    pub total_profit: f64, // Sum of net profit per contract pair
+}
+
+pub struct Backtester {
+    event_matcher: EventMatcher,
+    arbitrage_detector: ArbitrageDetector,
+}
+
+impl Backtester {
+    pub fn new(event_matcher: EventMatcher, arbitrage_detector: ArbitrageDetector) -> Self {
+        Self {
+            event_matcher,
+            arbitrage_detector,
+        }
+    }
+
+    /// Read records from a JSONL file (blank lines are ignored)
+    pub fn load_records(path: impl AsRef<Path>) -> Result<Vec<BacktestRecord>> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open backtest data {}", path.display()))?;
+
+        let mut records = Vec::new();
+        for (line_no, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: BacktestRecord = serde_json::from_str(&line)
+                .with_context(|| format!("Bad record on line {} of {}", line_no + 1, path.display()))?;
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    /// Run the backtest over a JSONL file
+    pub fn run_file(&self, path: impl AsRef<Path>) -> Result<BacktestReport> {
+        let records = Self::load_records(path)?;
+        Ok(self.run(&records))
+    }
+
+    /// Feed each record through the event matcher and arbitrage detector
+    pub fn run(&self, records: &[BacktestRecord]) -> BacktestReport {
+        let mut report = BacktestReport {
+            records: records.len(),
+            ..Default::default()
+        };
+
+        for record in records {
+            let matches = self.event_matcher.find_matches_with_confidence(
+                std::slice::from_ref(&record.pm_event),
+                std::slice::from_ref(&record.kalshi_event),
+            );
+            let Some((_, _, confidence)) = matches.into_iter().next() else {
+                continue;
+            };
+            report.matched += 1;
+
+            let pm_prices = record.pm_prices.at(record.timestamp);
+            let kalshi_prices = record.kalshi_prices.at(record.timestamp);
+            let kalshi_prices = if confidence.inverted {
+                kalshi_prices.inverted()
+            } else {
+                kalshi_prices
+            };
+
+            if let Some(opportunity) = self.arbitrage_detector.check_arbitrage(&pm_prices, &kalshi_prices) {
+                debug!(
+                    "{} {} - {} (${:.4})",
+                    record.timestamp, record.pm_event.title, opportunity.strategy, opportunity.net_profit
+                );
+                report.opportunities += 1;
+                report.total_profit += opportunity.net_profit;
+            }
+        }
+
+        info!(
+            "📼 Backtest: {} records, {} matched, {} opportunities, total profit ${:.4} per contract pair",
+            report.records, report.matched, report.opportunities, report.total_profit
+        );
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage_detector::FlatFee;
+    use std::sync::Arc;
+
+    const SAMPLE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/data/backtest_sample.jsonl");
+
+    fn backtester(min_profit_threshold: f64) -> Backtester {
+        let detector = ArbitrageDetector::new(min_profit_threshold)
+            .with_fee_models(Arc::new(FlatFee(0.0)), Arc::new(FlatFee(0.0)));
+        Backtester::new(EventMatcher::new(0.8), detector)
+    }
+
+    #[test]
+    fn sample_finds_the_two_wide_spreads() {
+        let report = backtester(0.02).run_file(SAMPLE).unwrap();
+
+        assert_eq!(report.records, 3);
+        assert_eq!(report.matched, 3);
+        // BTC at 12:00 (0.42 + 0.50) and the Lakers (0.60 + 0.30); BTC at 12:01
+        // (0.48 + 0.51) is below the threshold
+        assert_eq!(report.opportunities, 2);
+        assert!((report.total_profit - 0.18).abs() < 1e-9, "{}", report.total_profit);
+    }
+
+    #[test]
+    fn zero_threshold_keeps_every_positive_spread() {
+        let report = backtester(0.0).run_file(SAMPLE).unwrap();
+        assert_eq!(report.opportunities, 3);
+        assert!((report.total_profit - 0.19).abs() < 1e-9, "{}", report.total_profit);
+    }
+
+    #[test]
+    fn bad_lines_report_their_line_number() {
+        let path = std::env::temp_dir().join(format!("backtest-{}.jsonl", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "\n{\"timestamp\": 1}\n").unwrap();
+        let error = Backtester::load_records(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(error.to_string().contains("line 2"), "{}", error);
+    }
+}
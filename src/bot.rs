@@ -1,14 +1,151 @@
+use crate::approval::{ApprovalPolicy, ApprovalQueue};
 use crate::arbitrage_detector::{ArbitrageDetector, ArbitrageOpportunity};
-use crate::event::{Event, MarketPrices};
-use crate::event_matcher::EventMatcher;
+use crate::clock::{Clock, JitteredInterval, SystemClock};
+use crate::event::{Event, MarketPrices, Platform};
+use crate::event_matcher::{EventMatcher, MatchConfidence};
 use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 use std::time::Duration as StdDuration;
-use tokio::time;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
+/// A detected opportunity: (Polymarket event, Kalshi event, opportunity, trade size).
+/// From `scan_venues`, the events are from whichever pair of venues was cheapest.
+pub type ScannedOpportunity = (Event, Event, ArbitrageOpportunity, f64);
+
+/// Whether the executor can trade a pair. It places Polymarket and Kalshi legs
+/// only - Manifold pays out in play money, which can't hedge a USD leg.
+pub fn is_executable(pm_event: &Event, kalshi_event: &Event) -> bool {
+    pm_event.platform == Platform::Polymarket && kalshi_event.platform == Platform::Kalshi
+}
+
+/// Split `capital` across opportunities to maximize total expected net profit.
+/// Each opportunity can take up to its trade size per leg and costs twice that
+/// (both legs). Highest ROI is filled first. Each opportunity was priced with
+/// `gas_cost_usd` spread over its full trade size, so a partial allocation is
+/// re-priced with the gas spread over fewer pairs and dropped if that leaves
+/// no profit. Returns (index, per-leg USD) pairs, best ROI first;
+/// opportunities that get nothing are left out.
+pub fn allocate(opportunities: &[ScannedOpportunity], capital: f64, gas_cost_usd: Option<f64>) -> Vec<(usize, f64)> {
+    let mut by_roi: Vec<usize> = (0..opportunities.len()).collect();
+    by_roi.sort_by(|&a, &b| opportunities[b].2.roi_percent.total_cmp(&opportunities[a].2.roi_percent));
+
+    let mut remaining = capital.max(0.0);
+    let mut allocations = Vec::new();
+    for i in by_roi {
+        let (_, _, opportunity, max_size) = &opportunities[i];
+        if opportunity.net_profit <= 0.0 {
+            continue;
+        }
+        let size = max_size.min(remaining / 2.0);
+        if size <= 0.0 {
+            break;
+        }
+        if net_profit_at_size(opportunity, *max_size, size, gas_cost_usd) <= 0.0 {
+            continue;
+        }
+        remaining -= size * 2.0;
+        allocations.push((i, size));
+    }
+    allocations
+}
+
+/// Net profit per contract pair of `opportunity` (priced for `priced_size`)
+/// when only `size` is traded - the fixed gas is spread over fewer pairs
+fn net_profit_at_size(opportunity: &ArbitrageOpportunity, priced_size: f64, size: f64, gas_cost_usd: Option<f64>) -> f64 {
+    let gas = gas_cost_usd.unwrap_or(0.0);
+    if size >= priced_size || gas <= 0.0 {
+        return opportunity.net_profit;
+    }
+    opportunity.net_profit - gas * opportunity.total_cost * (1.0 / size - 1.0 / priced_size)
+}
+
+/// An event in a group matched across venues
+struct GroupMember {
+    venue: usize, // Index into the scanned venues
+    event: Event,
+    inverted: bool, // Opposite polarity to the group's first member
+}
+
+/// Events on different venues that all match each other, at most one per venue
+struct MatchGroup {
+    members: Vec<GroupMember>,
+    confidence: MatchConfidence, // The weakest match that joined the group
+}
+
+impl MatchGroup {
+    /// Polarity for an event on `venue` matched (with `confidence`) to the
+    /// member `event` on `member_venue`, or `None` if `venue` is already taken
+    fn join(&self, member_venue: usize, event: &Event, venue: usize, confidence: &MatchConfidence) -> Option<bool> {
+        if self.members.iter().any(|m| m.venue == venue) {
+            return None;
+        }
+        let member = self.members.iter().find(|m| m.venue == member_venue && m.event == *event)?;
+        Some(member.inverted != confidence.inverted)
+    }
+
+    fn add(&mut self, member: GroupMember, confidence: MatchConfidence) {
+        self.members.push(member);
+        if confidence.overall_score < self.confidence.overall_score {
+            self.confidence = confidence;
+        }
+    }
+}
+
+/// When each (event, venue) was last traded
+type TradeTimes = HashMap<(String, Platform), DateTime<Utc>>;
+
+/// Per-event trade cooldown. Clones share the same history, so an executor
+/// running apart from the scan loop can record trades the scanner respects.
+#[derive(Clone)]
+pub struct TradeCooldown {
+    period: Duration,
+    clock: Arc<dyn Clock>,
+    recent_trades: Arc<Mutex<TradeTimes>>, // Last trade per event
+}
+
+impl TradeCooldown {
+    pub fn new(period: StdDuration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            period: Duration::from_std(period).unwrap_or(Duration::MAX),
+            clock,
+            recent_trades: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Remember that both events of a trade were just traded, starting their cooldown
+    pub fn record(&self, first_event: &Event, second_event: &Event) {
+        let now = self.clock.now();
+        let period = self.period;
+        let mut recent_trades = self.recent_trades.lock().unwrap_or_else(|e| e.into_inner());
+        // Lazily drop entries whose cooldown has already passed
+        recent_trades.retain(|_, traded_at| now - *traded_at < period);
+
+        recent_trades.insert((first_event.event_id.clone(), first_event.platform), now);
+        recent_trades.insert((second_event.event_id.clone(), second_event.platform), now);
+    }
+
+    /// Whether `event_id` on `platform` was traded within the cooldown
+    pub fn is_active(&self, event_id: &str, platform: Platform) -> bool {
+        self.recent_trades
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&(event_id.to_string(), platform))
+            .is_some_and(|traded_at| self.clock.now() - *traded_at < self.period)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MarketFilters {
     pub categories: Vec<String>,
     pub max_hours_until_resolution: i64,
-    pub min_liquidity: f64,
+    pub min_liquidity_polymarket: f64, // Polymarket liquidity (USDC)
+    pub min_liquidity_kalshi: f64,     // Kalshi volume (contracts)
+    pub min_liquidity_manifold: f64,   // Manifold liquidity pool (mana)
+    pub max_position_size: f64, // Max USDC per leg
+    pub max_total_exposure: f64, // Max USDC across all open positions
 }
 
 impl Default for MarketFilters {
@@ -16,15 +153,52 @@ impl Default for MarketFilters {
         Self {
             categories: vec!["crypto".to_string(), "sports".to_string()],
             max_hours_until_resolution: 24,
-            min_liquidity: 100.0,
+            min_liquidity_polymarket: 100.0,
+            min_liquidity_kalshi: 100.0,
+            min_liquidity_manifold: 100.0,
+            max_position_size: 100.0,
+            max_total_exposure: 1000.0,
         }
     }
 }
 
+impl MarketFilters {
+    /// Apply the same liquidity floor to every venue
+    pub fn with_min_liquidity(mut self, min_liquidity: f64) -> Self {
+        self.min_liquidity_polymarket = min_liquidity;
+        self.min_liquidity_kalshi = min_liquidity;
+        self.min_liquidity_manifold = min_liquidity;
+        self
+    }
+
+    /// Liquidity floor for `platform`'s side of a pair
+    pub fn min_liquidity(&self, platform: Platform) -> f64 {
+        match platform {
+            Platform::Polymarket => self.min_liquidity_polymarket,
+            Platform::Kalshi => self.min_liquidity_kalshi,
+            Platform::Manifold => self.min_liquidity_manifold,
+        }
+    }
+
+    /// Whether a trade costing `trade_cost` (both legs) fits under `max_total_exposure`
+    /// given `open_cost` already at risk
+    pub fn within_exposure_limit(&self, open_cost: f64, trade_cost: f64) -> bool {
+        open_cost + trade_cost <= self.max_total_exposure
+    }
+}
+
 pub struct ShortTermArbitrageBot {
     filters: MarketFilters,
     event_matcher: EventMatcher,
     arbitrage_detector: ArbitrageDetector,
+    clock: Arc<dyn Clock>,
+    trade_cooldown: TradeCooldown,
+    max_price_age: Duration,
+    approval_policy: ApprovalPolicy,
+    approval_queue: ApprovalQueue,
+    price_fetch_concurrency: usize,
+    price_sum_tolerance: f64,
+    scan_jitter: f64, // Fraction of the scan interval each wait may vary by
 }
 
 impl ShortTermArbitrageBot {
@@ -33,16 +207,107 @@ impl ShortTermArbitrageBot {
         similarity_threshold: f64,
         min_profit_threshold: f64,
     ) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(SystemClock);
         Self {
             filters,
             event_matcher: EventMatcher::new(similarity_threshold),
             arbitrage_detector: ArbitrageDetector::new(min_profit_threshold),
+            trade_cooldown: TradeCooldown::new(StdDuration::from_secs(600), clock.clone()),
+            clock,
+            max_price_age: Duration::seconds(30),
+            approval_policy: ApprovalPolicy::default(),
+            approval_queue: ApprovalQueue::default(),
+            price_fetch_concurrency: 8,
+            price_sum_tolerance: 0.1,
+            scan_jitter: 0.0,
         }
     }
 
+    /// Randomize each wait in `run_continuous` by ± `jitter` (a fraction of
+    /// the scan interval, default 0) so bots don't poll in lockstep
+    pub fn with_scan_jitter(mut self, jitter: f64) -> Self {
+        self.scan_jitter = jitter;
+        self
+    }
+
+    /// Maximum price requests in flight during a scan (default 8)
+    pub fn with_price_fetch_concurrency(mut self, concurrency: usize) -> Self {
+        self.price_fetch_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// How far YES + NO may sit from 1.00 before a quote is skipped as
+    /// implausible (default 0.1)
+    pub fn with_price_sum_tolerance(mut self, tolerance: f64) -> Self {
+        self.price_sum_tolerance = tolerance;
+        self
+    }
+
+    /// Which opportunities need human approval before trading (default: none)
+    pub fn with_approval_policy(mut self, approval_policy: ApprovalPolicy) -> Self {
+        self.approval_policy = approval_policy;
+        self
+    }
+
+    /// Queue holding opportunities that await approval; clones share it
+    pub fn approval_queue(&self) -> ApprovalQueue {
+        self.approval_queue.clone()
+    }
+
+    /// Minimum time before an event traded once can be traded again (default 10 minutes)
+    pub fn with_trade_cooldown(mut self, cooldown: StdDuration) -> Self {
+        self.trade_cooldown = TradeCooldown::new(cooldown, self.clock.clone());
+        self
+    }
+
+    /// Shared handle to the cooldown, for executors running outside the bot
+    pub fn trade_cooldown(&self) -> TradeCooldown {
+        self.trade_cooldown.clone()
+    }
+
+    /// Skip pairs whose prices are older than `max_age` (default 30 seconds)
+    pub fn with_max_price_age(mut self, max_age: StdDuration) -> Self {
+        self.max_price_age = Duration::from_std(max_age).unwrap_or(Duration::MAX);
+        self
+    }
+
+    /// Remember that both events of a trade were just traded, starting their cooldown
+    pub fn record_trade(&self, pm_event: &Event, kalshi_event: &Event) {
+        self.trade_cooldown.record(pm_event, kalshi_event);
+    }
+
+    /// Whether `event_id` on `platform` was traded within the cooldown
+    pub fn in_cooldown(&self, event_id: &str, platform: Platform) -> bool {
+        self.trade_cooldown.is_active(event_id, platform)
+    }
+
+    /// Replace the time source (defaults to `SystemClock`)
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.trade_cooldown.clock = clock.clone();
+        self.clock = clock;
+        self
+    }
+
+    /// Replace the default matcher (e.g. to configure aliases or weights)
+    pub fn with_event_matcher(mut self, event_matcher: EventMatcher) -> Self {
+        self.event_matcher = event_matcher;
+        self
+    }
+
+    /// Replace the default detector (e.g. to configure fees or slippage)
+    pub fn with_arbitrage_detector(mut self, arbitrage_detector: ArbitrageDetector) -> Self {
+        self.arbitrage_detector = arbitrage_detector;
+        self
+    }
+
+    /// Update the Polygon gas cost (USD per Polymarket order) used in profit checks
+    pub fn set_gas_cost(&mut self, gas_cost_usd: Option<f64>) {
+        self.arbitrage_detector.set_gas_cost(gas_cost_usd);
+    }
+
     pub fn is_within_timeframe(&self, resolution_date: Option<DateTime<Utc>>) -> bool {
         if let Some(date) = resolution_date {
-            let now = Utc::now();
+            let now = self.clock.now();
             let time_until_resolution = date - now;
             let max_time = Duration::hours(self.filters.max_hours_until_resolution);
             let min_time = Duration::minutes(5);
@@ -83,21 +348,52 @@ impl ShortTermArbitrageBot {
 
         let text = event_title + " " + &event_desc;
 
-        if self.filters.categories.iter().any(|c| c == "crypto") {
-            if crypto_keywords.iter().any(|kw| text.contains(kw)) {
-                return true;
-            }
+        if self.filters.categories.iter().any(|c| c == "crypto")
+            && crypto_keywords.iter().any(|kw| text.contains(kw))
+        {
+            return true;
         }
 
-        if self.filters.categories.iter().any(|c| c == "sports") {
-            if sports_keywords.iter().any(|kw| text.contains(kw)) {
-                return true;
-            }
+        if self.filters.categories.iter().any(|c| c == "sports")
+            && sports_keywords.iter().any(|kw| text.contains(kw))
+        {
+            return true;
         }
 
         false
     }
 
+    /// Trade size per leg: the smaller of the liquidity available on the outcome
+    /// bought on each platform, capped at `max_position_size`
+    pub fn size_trade(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        pm_prices: &MarketPrices,
+        kalshi_prices: &MarketPrices,
+    ) -> f64 {
+        let pm_available = pm_prices.available_to_buy(&opportunity.polymarket_action.outcome);
+        let kalshi_available = kalshi_prices.available_to_buy(&opportunity.kalshi_action.outcome);
+
+        pm_available
+            .min(kalshi_available)
+            .min(self.filters.max_position_size)
+            .max(0.0)
+    }
+
+    /// Whether a trade costing `trade_cost` (both legs) fits under `max_total_exposure`
+    /// given `open_cost` already at risk
+    pub fn within_exposure_limit(&self, open_cost: f64, trade_cost: f64) -> bool {
+        self.filters.within_exposure_limit(open_cost, trade_cost)
+    }
+
+    pub fn filters(&self) -> &MarketFilters {
+        &self.filters
+    }
+
+    pub fn arbitrage_detector(&self) -> &ArbitrageDetector {
+        &self.arbitrage_detector
+    }
+
     pub fn filter_events(&self, events: &[Event]) -> Vec<Event> {
         events
             .iter()
@@ -108,88 +404,558 @@ impl ShortTermArbitrageBot {
             .collect()
     }
 
+    /// Scan Polymarket against Kalshi - `scan_venues` with two venues
+    #[tracing::instrument(
+        skip_all,
+        fields(pm_events = pm_events.len(), kalshi_events = kalshi_events.len())
+    )]
     pub async fn scan_for_opportunities<F, Fut>(
         &self,
         pm_events: &[Event],
         kalshi_events: &[Event],
         fetch_prices: F,
-    ) -> Vec<(Event, Event, ArbitrageOpportunity)>
+    ) -> Vec<ScannedOpportunity>
     where
-        F: Fn(&str, &str) -> Fut,
-        Fut: std::future::Future<Output = MarketPrices> + Send,
+        F: Fn(&str, Platform) -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<MarketPrices>> + Send,
     {
-        // Filter events
-        let pm_filtered = self.filter_events(pm_events);
-        let kalshi_filtered = self.filter_events(kalshi_events);
+        self.scan_venues(&[pm_events, kalshi_events], fetch_prices).await
+    }
 
-        if pm_filtered.is_empty() || kalshi_filtered.is_empty() {
-            return Vec::new();
-        }
+    /// Scan any number of venues (one slice of events each). Events matched
+    /// across venues form a group; for each group the pair of venues with the
+    /// cheapest YES and cheapest NO is checked for arbitrage. The first event of
+    /// each result is from the earlier venue in `venues`, and takes the detector's
+    /// Polymarket leg (and fee model); the second takes the Kalshi leg.
+    #[tracing::instrument(skip_all, fields(venues = venues.len()))]
+    pub async fn scan_venues<F, Fut>(
+        &self,
+        venues: &[&[Event]],
+        fetch_prices: F,
+    ) -> Vec<ScannedOpportunity>
+    where
+        F: Fn(&str, Platform) -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<MarketPrices>> + Send,
+    {
+        let filtered: Vec<Vec<Event>> = venues.iter().map(|events| self.filter_events(events)).collect();
 
-        // Match events
-        let matches = self.event_matcher.find_matches(&pm_filtered, &kalshi_filtered);
+        // Don't stack another position on an event we just traded
+        let groups: Vec<MatchGroup> = self
+            .group_matches(&filtered)
+            .into_iter()
+            .map(|mut group| {
+                group
+                    .members
+                    .retain(|m| !self.in_cooldown(&m.event.event_id, m.event.platform));
+                group
+            })
+            .filter(|group| group.members.len() >= 2)
+            .collect();
 
-        if matches.is_empty() {
+        if groups.is_empty() {
             return Vec::new();
         }
 
-        // Check arbitrage for each matched pair
+        let prices = self.fetch_match_prices(&groups, fetch_prices).await;
+
+        // Check arbitrage for each matched group
         let mut opportunities = Vec::new();
 
-        for (pm_event, kalshi_event, similarity) in matches {
-            // Fetch prices (placeholder - replace with actual API calls)
-            let pm_prices = fetch_prices(&pm_event.event_id, "polymarket").await;
-            let kalshi_prices = fetch_prices(&kalshi_event.event_id, "kalshi").await;
+        for group in groups {
+            // Members whose prices can be traded on, in the group's polarity
+            let priced: Vec<(&GroupMember, MarketPrices)> = group
+                .members
+                .iter()
+                .filter_map(|member| {
+                    // A failed fetch (already logged) drops the member rather than pricing it at zero
+                    let prices = prices.get(&(member.event.event_id.clone(), member.event.platform))?;
+                    let prices = if member.inverted { prices.inverted() } else { prices.clone() };
+                    self.is_tradable(&member.event, &prices).then_some((member, prices))
+                })
+                .collect();
 
-            // Check liquidity
-            if pm_prices.liquidity < self.filters.min_liquidity
-                || kalshi_prices.liquidity < self.filters.min_liquidity
-            {
-                continue;
+            // Best-priced pair: cheapest YES on one venue plus cheapest NO on
+            // another, preferring pairs the executor can trade over detection-only ones
+            let mut best: Option<(usize, usize, bool, f64)> = None;
+            for (i, (yes_member, yes_prices)) in priced.iter().enumerate() {
+                for (j, (no_member, no_prices)) in priced.iter().enumerate() {
+                    if i == j {
+                        continue;
+                    }
+                    let executable = is_executable(&yes_member.event, &no_member.event)
+                        || is_executable(&no_member.event, &yes_member.event);
+                    let cost = yes_prices.yes_buy_price() + no_prices.no_buy_price();
+                    let better = best.is_none_or(|(_, _, best_executable, best_cost)| {
+                        (executable, -cost) > (best_executable, -best_cost)
+                    });
+                    if better {
+                        best = Some((i, j, executable, cost));
+                    }
+                }
             }
+            let Some((i, j, _, _)) = best else {
+                continue;
+            };
+
+            // Keep venue order, so a two-venue scan is always (Polymarket, Kalshi)
+            let (first, second) = if priced[i].0.venue < priced[j].0.venue {
+                (&priced[i], &priced[j])
+            } else {
+                (&priced[j], &priced[i])
+            };
+            let (first_member, first_prices) = first;
+            let (second_member, second_prices) = second;
 
-            // Check arbitrage
-            if let Some(opportunity) = self.arbitrage_detector.check_arbitrage(&pm_prices, &kalshi_prices) {
-                opportunities.push((pm_event, kalshi_event, opportunity));
+            if let Some((mut opportunity, trade_size)) = self.evaluate_pair(first_prices, second_prices) {
+                // Map the second leg back to the outcome actually traded on its venue
+                if first_member.inverted != second_member.inverted {
+                    opportunity.invert_kalshi_outcome();
+                }
+                let first_event = first_member.event.clone();
+                let second_event = second_member.event.clone();
+                tracing::debug!(
+                    first_event_id = %first_event.event_id,
+                    second_event_id = %second_event.event_id,
+                    net_profit = opportunity.net_profit,
+                    trade_size,
+                    "Opportunity found"
+                );
+                // Less certain matches wait for a human instead of trading
+                if self.approval_policy.requires_approval(&group.confidence) {
+                    self.approval_queue
+                        .push(first_event, second_event, opportunity, trade_size, group.confidence)
+                        .await;
+                    continue;
+                }
+                opportunities.push((first_event, second_event, opportunity, trade_size));
             }
         }
 
         opportunities
     }
 
-    pub async fn run_continuous<F, Fut, P, PFut>(
+    /// Whether `prices` for `event` are sane, fresh and liquid enough to trade
+    fn is_tradable(&self, event: &Event, prices: &MarketPrices) -> bool {
+        // Zeroed or out-of-range prices mean a failed fetch, not a free lunch
+        if prices.is_degenerate() {
+            tracing::warn!(
+                "⚠️ Skipping {} on {} - degenerate prices (yes {:.4} / no {:.4})",
+                event.title,
+                event.platform,
+                prices.yes,
+                prices.no
+            );
+            return false;
+        }
+
+        if !prices.validate_with_tolerance(self.price_sum_tolerance) {
+            tracing::warn!(
+                "⚠️ Skipping {} on {} - implausible prices (yes {:.4} + no {:.4} = {:.4})",
+                event.title,
+                event.platform,
+                prices.yes,
+                prices.no,
+                prices.yes + prices.no
+            );
+            return false;
+        }
+
+        // Don't act on quotes that may no longer be on the book
+        if prices.is_stale_at(self.clock.now(), self.max_price_age) {
+            tracing::warn!(
+                event_id = %event.event_id,
+                platform = %event.platform,
+                "⚠️ Skipping event - stale prices"
+            );
+            return false;
+        }
+
+        // Each venue against its own floor, since the units differ
+        if prices.liquidity < self.filters.min_liquidity(event.platform) {
+            tracing::debug!(
+                event_id = %event.event_id,
+                platform = %event.platform,
+                liquidity = prices.liquidity,
+                "Skipping event - insufficient liquidity"
+            );
+            return false;
+        }
+
+        true
+    }
+
+    /// Check one pair of markets (both in the same polarity) for arbitrage,
+    /// returning the opportunity priced at its fill size and that size
+    fn evaluate_pair(
+        &self,
+        first_prices: &MarketPrices,
+        second_prices: &MarketPrices,
+    ) -> Option<(ArbitrageOpportunity, f64)> {
+        // Top-of-book check first - cheap filter before sizing
+        let top_opportunity = self.arbitrage_detector.check_arbitrage(first_prices, second_prices)?;
+
+        let trade_size = self.size_trade(&top_opportunity, first_prices, second_prices);
+        if trade_size <= 0.0 {
+            return None;
+        }
+
+        // Price both legs at the average fill for the trade size, not top-of-book
+        // (None if there isn't enough depth to fill the trade)
+        let first_fill = first_prices.at_size(trade_size)?;
+        let second_fill = second_prices.at_size(trade_size)?;
+
+        // Same legs must still be profitable at the fill price
+        self.arbitrage_detector
+            .check_arbitrage_for_size(&first_fill, &second_fill, trade_size)
+            .filter(|opportunity| opportunity.strategy == top_opportunity.strategy)
+            .map(|opportunity| (opportunity, trade_size))
+    }
+
+    /// Match every pair of venues and merge the matches into groups holding
+    /// at most one event per venue. Earlier venue pairs (and, within a pair,
+    /// the matcher's ordering) win when an event could join two groups.
+    fn group_matches(&self, venues: &[Vec<Event>]) -> Vec<MatchGroup> {
+        let mut groups: Vec<MatchGroup> = Vec::new();
+        let mut group_of: HashMap<Event, usize> = HashMap::new();
+
+        for i in 0..venues.len() {
+            for j in (i + 1)..venues.len() {
+                if venues[i].is_empty() || venues[j].is_empty() {
+                    continue;
+                }
+
+                for (a, b, confidence) in self.event_matcher.find_matches_with_confidence(&venues[i], &venues[j]) {
+                    match (group_of.get(&a).copied(), group_of.get(&b).copied()) {
+                        (None, None) => {
+                            group_of.insert(a.clone(), groups.len());
+                            group_of.insert(b.clone(), groups.len());
+                            groups.push(MatchGroup {
+                                members: vec![
+                                    GroupMember { venue: i, event: a, inverted: false },
+                                    GroupMember { venue: j, event: b, inverted: confidence.inverted },
+                                ],
+                                confidence,
+                            });
+                        }
+                        (Some(g), None) => {
+                            if let Some(inverted) = groups[g].join(i, &a, j, &confidence) {
+                                group_of.insert(b.clone(), g);
+                                groups[g].add(GroupMember { venue: j, event: b, inverted }, confidence);
+                            }
+                        }
+                        (None, Some(g)) => {
+                            if let Some(inverted) = groups[g].join(j, &b, i, &confidence) {
+                                group_of.insert(a.clone(), g);
+                                groups[g].add(GroupMember { venue: i, event: a, inverted }, confidence);
+                            }
+                        }
+                        // Both already grouped - keep the earlier grouping
+                        (Some(_), Some(_)) => {}
+                    }
+                }
+            }
+        }
+
+        groups
+    }
+
+    /// Fetch prices for every distinct event in `groups`, at most
+    /// `price_fetch_concurrency` at a time. Failed fetches are logged and left out.
+    async fn fetch_match_prices<F, Fut>(
+        &self,
+        groups: &[MatchGroup],
+        fetch_prices: F,
+    ) -> HashMap<(String, Platform), MarketPrices>
+    where
+        F: Fn(&str, Platform) -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<MarketPrices>> + Send,
+    {
+        let keys: HashSet<(String, Platform)> = groups
+            .iter()
+            .flat_map(|group| &group.members)
+            .map(|member| (member.event.event_id.clone(), member.event.platform))
+            .collect();
+
+        stream::iter(keys)
+            .map(|(event_id, platform)| {
+                let fetch = fetch_prices(&event_id, platform);
+                async move { (event_id, platform, fetch.await) }
+            })
+            .buffer_unordered(self.price_fetch_concurrency)
+            .filter_map(|(event_id, platform, result)| async move {
+                match result {
+                    Ok(prices) => Some(((event_id, platform), prices)),
+                    Err(e) => {
+                        tracing::warn!(
+                            event_id = %event_id,
+                            platform = %platform,
+                            error = %e,
+                            "Skipping pairs - price fetch failed"
+                        );
+                        None
+                    }
+                }
+            })
+            .collect()
+            .await
+    }
+
+    /// Fetch events once and scan them - a single pass of `run_continuous`,
+    /// for `--once` runs, scripts and tests
+    pub async fn scan_once<F, Fut, P, PFut>(
         &self,
-        scan_interval: StdDuration,
         fetch_events: F,
         fetch_prices: P,
-    ) -> Vec<(Event, Event, ArbitrageOpportunity)>
+    ) -> Vec<ScannedOpportunity>
     where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = (Vec<Event>, Vec<Event>)> + Send,
+        P: Fn(&str, Platform) -> PFut,
+        PFut: std::future::Future<Output = anyhow::Result<MarketPrices>> + Send,
+    {
+        let (pm_events, kalshi_events) = fetch_events().await;
+        self.scan_for_opportunities(&pm_events, &kalshi_events, fetch_prices).await
+    }
+
+    /// Scan every `scan_interval` (± the scan jitter) until `cancel` fires,
+    /// passing each non-empty batch of opportunities (with trade sizes) to
+    /// `on_opportunities`
+    pub async fn run_continuous<F, Fut, P, PFut, H, HFut>(
+        &self,
+        scan_interval: StdDuration,
+        fetch_events: F,
+        fetch_prices: P,
+        on_opportunities: H,
+        cancel: CancellationToken,
+    ) where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = (Vec<Event>, Vec<Event>)> + Send,
-        P: Fn(&str, &str) -> PFut + Clone + Send + Sync,
-        PFut: std::future::Future<Output = MarketPrices> + Send,
+        P: Fn(&str, Platform) -> PFut + Clone + Send + Sync,
+        PFut: std::future::Future<Output = anyhow::Result<MarketPrices>> + Send,
+        H: Fn(Vec<ScannedOpportunity>) -> HFut,
+        HFut: std::future::Future<Output = ()>,
     {
-        let mut interval = time::interval(scan_interval);
+        let mut interval = JitteredInterval::new(scan_interval, self.scan_jitter);
 
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    tracing::info!("Scan loop cancelled");
+                    return;
+                }
+                _ = interval.tick() => {}
+            }
 
-            let (pm_events, kalshi_events) = fetch_events().await;
-            let opportunities = self.scan_for_opportunities(&pm_events, &kalshi_events, fetch_prices.clone()).await;
+            let opportunities = self.scan_once(&fetch_events, fetch_prices.clone()).await;
 
             if !opportunities.is_empty() {
                 tracing::info!("Found {} arbitrage opportunities", opportunities.len());
-                for (pm_event, kalshi_event, opp) in &opportunities {
-                    tracing::info!(
-                        "Opportunity: {} - Profit: ${:.4}, ROI: {:.2}%",
-                        pm_event.title,
-                        opp.net_profit,
-                        opp.roi_percent
-                    );
+                for (pm_event, _kalshi_event, opp, size) in &opportunities {
+                    tracing::info!("Opportunity: {} - {}, Size: ${:.2}", pm_event.title, opp, size);
                 }
-                return opportunities; // Return opportunities for execution
+                on_opportunities(opportunities).await;
             }
         }
     }
+
+    /// Like `run_continuous`, but send each opportunity over `tx` for a
+    /// separate executor task. A full channel holds up the next scan
+    /// (backpressure); the loop stops once the receiver is dropped.
+    pub async fn run_with_channel<F, Fut, P, PFut>(
+        &self,
+        scan_interval: StdDuration,
+        fetch_events: F,
+        fetch_prices: P,
+        tx: mpsc::Sender<ScannedOpportunity>,
+        cancel: CancellationToken,
+    ) where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = (Vec<Event>, Vec<Event>)> + Send,
+        P: Fn(&str, Platform) -> PFut + Clone + Send + Sync,
+        PFut: std::future::Future<Output = anyhow::Result<MarketPrices>> + Send,
+    {
+        let send_all = |opportunities: Vec<ScannedOpportunity>| {
+            let tx = tx.clone();
+            let cancel = cancel.clone();
+            async move {
+                for opportunity in opportunities {
+                    if tx.send(opportunity).await.is_err() {
+                        tracing::info!("Opportunity receiver closed, stopping scans");
+                        cancel.cancel();
+                        return;
+                    }
+                }
+            }
+        };
+
+        self.run_continuous(scan_interval, fetch_events, fetch_prices, send_all, cancel.clone())
+            .await;
+    }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(platform: Platform, id: &str) -> Event {
+        Event::new(platform, id.to_string(), "Will BTC be above $70,000?".to_string(), String::new())
+            .with_category("Crypto".to_string())
+            .with_resolution_date(Utc::now() + Duration::hours(2))
+    }
+
+    #[tokio::test]
+    async fn three_venue_scan_picks_the_best_executable_pair() {
+        let bot = ShortTermArbitrageBot::new(MarketFilters::default(), 0.5, 0.0);
+        let pm = [event(Platform::Polymarket, "pm-btc")];
+        let kalshi = [event(Platform::Kalshi, "KXBTC")];
+        let manifold = [event(Platform::Manifold, "mf-btc")];
+
+        // Manifold YES + Kalshi NO is cheapest (0.75), but only Polymarket
+        // and Kalshi legs can be traded (0.85)
+        let opportunities = bot
+            .scan_venues(&[&pm, &kalshi, &manifold], |event_id: &str, _| {
+                let prices = match event_id {
+                    "pm-btc" => MarketPrices::new(0.40, 0.60, 10_000.0),
+                    "KXBTC" => MarketPrices::new(0.55, 0.45, 10_000.0),
+                    _ => MarketPrices::new(0.30, 0.70, 10_000.0),
+                };
+                async move { Ok(prices) }
+            })
+            .await;
+
+        assert_eq!(opportunities.len(), 1);
+        let (first, second, opportunity, _) = &opportunities[0];
+        assert!(is_executable(first, second));
+        assert_eq!(opportunity.polymarket_action.outcome, "YES");
+        assert_eq!(opportunity.kalshi_action.outcome, "NO");
+    }
+
+    #[tokio::test]
+    async fn scan_fetches_each_event_once_within_the_concurrency_cap() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let bot = ShortTermArbitrageBot::new(MarketFilters::default(), 0.8, 0.0).with_price_fetch_concurrency(2);
+        let titled = |platform, id: &str, title: &str| {
+            let mut event = event(platform, id);
+            event.title = title.to_string();
+            event
+        };
+        let pm = [
+            titled(Platform::Polymarket, "pm-btc", "Will BTC be above $70,000?"),
+            titled(Platform::Polymarket, "pm-eth", "Will ETH be above $4,000?"),
+            titled(Platform::Polymarket, "pm-sol", "Will SOL be above $200?"),
+        ];
+        let kalshi = [
+            titled(Platform::Kalshi, "KXBTC", "Will BTC be above $70,000?"),
+            titled(Platform::Kalshi, "KXETH", "Will ETH be above $4,000?"),
+            titled(Platform::Kalshi, "KXSOL", "Will SOL be above $200?"),
+        ];
+
+        let calls = Mutex::new(HashMap::<String, usize>::new());
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        bot.scan_venues(&[&pm, &kalshi], |event_id: &str, _| {
+            *calls.lock().unwrap().entry(event_id.to_string()).or_default() += 1;
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(StdDuration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok(MarketPrices::new(0.50, 0.50, 10_000.0))
+            }
+        })
+        .await;
+
+        let calls = calls.into_inner().unwrap();
+        assert_eq!(calls.len(), 6);
+        assert!(calls.values().all(|&count| count == 1), "{:?}", calls);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn continuous_scans_stay_within_the_jitter_band() {
+        let bot = ShortTermArbitrageBot::new(MarketFilters::default(), 0.8, 0.0).with_scan_jitter(0.5);
+        let scans = Mutex::new(Vec::new());
+        let cancel = CancellationToken::new();
+
+        // Scan every 20ms ± 10ms, stopping after nine scans
+        bot.run_continuous(
+            StdDuration::from_millis(20),
+            || {
+                let mut scans = scans.lock().unwrap();
+                scans.push(std::time::Instant::now());
+                if scans.len() == 9 {
+                    cancel.cancel();
+                }
+                async { (Vec::new(), Vec::new()) }
+            },
+            |_: &str, _| async { Ok(MarketPrices::new(0.50, 0.50, 10_000.0)) },
+            |_| async {},
+            cancel.clone(),
+        )
+        .await;
+
+        let scans = scans.into_inner().unwrap();
+        let gaps: Vec<StdDuration> = scans.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        assert_eq!(gaps.len(), 8);
+        for gap in &gaps {
+            assert!(*gap >= StdDuration::from_millis(10), "{:?} below the band", gap);
+            assert!(*gap <= StdDuration::from_millis(30 + 25), "{:?} above the band", gap);
+        }
+        let spread = *gaps.iter().max().unwrap() - *gaps.iter().min().unwrap();
+        assert!(spread > StdDuration::from_millis(1), "waits never varied: {:?}", gaps);
+    }
+
+    /// A scanned opportunity for the given asks, priced for `size` per leg
+    fn scanned(pm: MarketPrices, kalshi: MarketPrices, size: f64, gas: f64) -> ScannedOpportunity {
+        let opportunity = ArbitrageDetector::new(0.0)
+            .with_gas_cost(gas)
+            .check_arbitrage_for_size(&pm, &kalshi, size)
+            .expect("prices leave an arbitrage");
+        (event(Platform::Polymarket, "pm"), event(Platform::Kalshi, "k"), opportunity, size)
+    }
+
+    fn expected_profit(opportunities: &[ScannedOpportunity], allocations: &[(usize, f64)]) -> f64 {
+        allocations
+            .iter()
+            .map(|&(i, size)| size / opportunities[i].2.total_cost * opportunities[i].2.net_profit)
+            .sum()
+    }
+
+    #[test]
+    fn allocation_matches_the_hand_computed_optimum() {
+        // Pair costs 0.85, 0.90 and 0.95 - ROI falls in that order
+        let opportunities = vec![
+            scanned(MarketPrices::new(0.45, 0.55, 10_000.0), MarketPrices::new(0.50, 0.50, 10_000.0), 100.0, 0.0),
+            scanned(MarketPrices::new(0.40, 0.60, 10_000.0), MarketPrices::new(0.55, 0.45, 10_000.0), 50.0, 0.0),
+            scanned(MarketPrices::new(0.45, 0.55, 10_000.0), MarketPrices::new(0.55, 0.45, 10_000.0), 100.0, 0.0),
+        ];
+
+        // $250 fills the best (50/leg) and puts the remaining 75/leg in the next
+        let allocations = allocate(&opportunities, 250.0, None);
+        assert_eq!(allocations, vec![(1, 50.0), (2, 75.0)]);
+
+        // Any other split of the same capital earns less
+        let optimum = expected_profit(&opportunities, &allocations);
+        for alternative in [vec![(1, 50.0), (0, 75.0)], vec![(2, 100.0), (1, 25.0)], vec![(2, 100.0), (0, 25.0)]] {
+            assert!(expected_profit(&opportunities, &alternative) < optimum);
+        }
+    }
+
+    #[test]
+    fn allocation_drops_remainders_that_gas_makes_unprofitable() {
+        let opportunities = vec![
+            scanned(MarketPrices::new(0.40, 0.60, 10_000.0), MarketPrices::new(0.55, 0.45, 10_000.0), 50.0, 1.0),
+            scanned(MarketPrices::new(0.45, 0.55, 10_000.0), MarketPrices::new(0.55, 0.45, 10_000.0), 100.0, 1.0),
+        ];
+        assert!(opportunities[1].2.net_profit > 0.0);
+
+        // $4 is left for the second - $1 of gas outweighs its profit at $2/leg
+        assert_eq!(allocate(&opportunities, 104.0, Some(1.0)), vec![(0, 50.0)]);
+
+        // A remainder big enough to cover the gas is still traded
+        assert_eq!(allocate(&opportunities, 180.0, Some(1.0)), vec![(0, 50.0), (1, 40.0)]);
+    }
+}
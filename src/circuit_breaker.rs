@@ -0,0 +1,111 @@
+use std::time::{Duration, Instant};
+
+/// Consecutive failed trades that trip the breaker by default
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long a tripped breaker pauses trading by default
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(900);
+
+/// Pauses trading after too many consecutive failures.
+/// Trips once `threshold` failures occur in a row and stays tripped for `cooldown`.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: u32,
+    tripped_until: Option<Instant>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_FAILURE_THRESHOLD, DEFAULT_COOLDOWN)
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            consecutive_failures: 0,
+            tripped_until: None,
+        }
+    }
+
+    /// Record a successful trade - resets the failure count
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+    }
+
+    /// Record a failed trade, tripping the breaker once the threshold is reached
+    pub fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+
+        if self.consecutive_failures >= self.threshold {
+            self.tripped_until = Some(Instant::now() + self.cooldown);
+            self.consecutive_failures = 0;
+        }
+    }
+
+    /// Whether trading is currently paused
+    pub fn is_tripped(&self) -> bool {
+        self.remaining_cooldown().is_some()
+    }
+
+    /// Time left before trading resumes, if tripped
+    pub fn remaining_cooldown(&self) -> Option<Duration> {
+        self.tripped_until
+            .and_then(|until| until.checked_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trips_after_threshold_consecutive_failures() {
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.consecutive_failures(), 2);
+
+        breaker.record_failure();
+        assert!(breaker.is_tripped());
+        assert!(breaker.remaining_cooldown().unwrap() <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn resumes_once_the_cooldown_expires() {
+        let mut breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+
+        breaker.record_failure();
+        assert!(breaker.is_tripped());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(!breaker.is_tripped());
+        assert_eq!(breaker.remaining_cooldown(), None);
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        breaker.record_success();
+        assert_eq!(breaker.consecutive_failures(), 0);
+
+        // Two more failures aren't enough after the reset
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(!breaker.is_tripped());
+    }
+}
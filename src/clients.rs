@@ -1,27 +1,655 @@
-use crate::event::{Event, MarketPrices};
+use crate::event::{dedupe_events, normalize_outcome, Event, MarketPrices, MarketQuote, Outcome, Platform};
+use crate::error::{BotError, BotResult};
+use crate::exchange::{validate_order, Fill, Resolution, TimeInForce};
+use crate::order_book::OrderBook;
+use crate::polymarket_blockchain::{ClobCredentials, PolymarketBlockchain, DEFAULT_CLOB_URL};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use futures::{SinkExt, Stream, StreamExt};
+use rand::Rng;
+use reqwest::{Client, ClientBuilder, RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
 use std::str::FromStr;
-use tracing::{info, warn};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, Mutex, OnceCell, Semaphore};
+use tokio::time::Instant;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use tracing::{error, info, warn};
+
+/// Retry policy for transient HTTP failures (timeouts, 429, 5xx)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            base_delay,
+            ..Default::default()
+        }
+    }
+
+    /// Exponential backoff with up to 50% random jitter, capped at `max_delay`
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let jitter_ms = rand::thread_rng().gen_range(0..=backoff.as_millis() as u64 / 2);
+        backoff + Duration::from_millis(jitter_ms)
+    }
+
+    fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::INTERNAL_SERVER_ERROR
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+}
+
+/// Client-side rate limiter that spaces requests evenly at a fixed rate.
+/// Each caller reserves the next free slot, so concurrent callers queue up.
+#[derive(Debug)]
+pub struct RateLimiter {
+    min_interval: Duration,
+    next_slot: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: f64) -> Self {
+        Self {
+            min_interval: Duration::from_secs_f64(1.0 / requests_per_second.max(f64::EPSILON)),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait until a request slot is available
+    pub async fn acquire(&self) {
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + self.min_interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+/// How long fetched events are served from memory before refetching
+const DEFAULT_EVENT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// (fetched at, events)
+type CachedEvents = (Instant, Vec<Event>);
+
+/// In-memory TTL cache for a client's event list, shared across clones
+#[derive(Debug, Clone)]
+pub struct EventCache {
+    ttl: Duration,
+    entry: Arc<Mutex<Option<CachedEvents>>>,
+}
+
+impl EventCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entry: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Cached events, if fetched less than `ttl` ago
+    pub async fn get(&self) -> Option<Vec<Event>> {
+        match &*self.entry.lock().await {
+            Some((fetched_at, events)) if fetched_at.elapsed() < self.ttl => Some(events.clone()),
+            _ => None,
+        }
+    }
+
+    pub async fn store(&self, events: Vec<Event>) {
+        *self.entry.lock().await = Some((Instant::now(), events));
+    }
+}
+
+/// HTTP client settings shared by every venue: connection pooling and timeouts
+fn http_client_builder() -> ClientBuilder {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .pool_max_idle_per_host(10)
+        .pool_idle_timeout(std::time::Duration::from_secs(90))
+}
+
+/// Build a client with the shared settings that sends everything through `proxy_url`
+/// (`http://`, `https://`, `socks5://` or `socks5h://`, credentials in the URL if needed)
+fn proxied_http_client(proxy_url: &str) -> Result<Client> {
+    let scheme = proxy_url.split_once("://").map(|(scheme, _)| scheme);
+    if !matches!(scheme, Some("http" | "https" | "socks5" | "socks5h")) {
+        anyhow::bail!("Unsupported proxy URL {:?} (expected http, https, socks5 or socks5h)", proxy_url);
+    }
+    let proxy = reqwest::Proxy::all(proxy_url)
+        .with_context(|| format!("Invalid proxy URL {:?}", proxy_url))?;
+    http_client_builder()
+        .proxy(proxy)
+        .build()
+        .context("Failed to build proxied HTTP client")
+}
+
+/// Send a request, retrying timeouts and retryable status codes with backoff.
+/// After the last attempt the final response (or error) is returned unchanged.
+/// If a rate limiter is given, every attempt (including retries) acquires a slot first.
+/// If a request limit is given, each attempt holds one of its permits while in flight.
+async fn send_with_retry(
+    policy: &RetryPolicy,
+    rate_limiter: Option<&RateLimiter>,
+    request_limit: Option<&Semaphore>,
+    request: RequestBuilder,
+) -> reqwest::Result<Response> {
+    let mut attempt = 0;
+
+    loop {
+        if let Some(limiter) = rate_limiter {
+            limiter.acquire().await;
+        }
+
+        // Never closed, so acquiring only fails if the semaphore is dropped
+        let _permit = match request_limit {
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
+        };
+
+        // Bodies that can't be cloned (streams) can't be replayed - send once
+        let Some(req) = request.try_clone() else {
+            return request.send().await;
+        };
+
+        let result = req.send().await;
+        drop(_permit); // Don't hold a permit through the backoff sleep
+
+        match result {
+            Ok(response)
+                if attempt < policy.max_retries
+                    && RetryPolicy::is_retryable_status(response.status()) =>
+            {
+                warn!(
+                    "Request returned {} (attempt {}/{}), retrying",
+                    response.status(),
+                    attempt + 1,
+                    policy.max_retries + 1
+                );
+            }
+            Err(e) if attempt < policy.max_retries && e.is_timeout() => {
+                warn!(
+                    "Request timed out (attempt {}/{}), retrying",
+                    attempt + 1,
+                    policy.max_retries + 1
+                );
+            }
+            result => return result,
+        }
+
+        tokio::time::sleep(policy.delay_for(attempt)).await;
+        attempt += 1;
+    }
+}
+
+/// Events from one page of Polymarket's GraphQL `markets` query. Markets
+/// without an `id` are skipped, and missing text fields are left empty.
+pub(crate) fn parse_polymarket_markets(markets: &[serde_json::Value]) -> Vec<Event> {
+    markets
+        .iter()
+        .filter_map(|market| {
+            let Some(event_id) = market["id"].as_str().filter(|id| !id.is_empty()) else {
+                warn!("Skipping Polymarket market without an id");
+                return None;
+            };
+
+            // Parse end date
+            let resolution_date = market["endDate"]
+                .as_str()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc));
+
+            Some(Event {
+                platform: Platform::Polymarket,
+                event_id: event_id.to_string(),
+                title: market["question"].as_str().unwrap_or_default().to_string(),
+                description: market["description"].as_str().unwrap_or_default().to_string(),
+                resolution_date,
+                category: market["category"].as_str().map(|s| s.to_string()),
+                tags: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Events from one page of Kalshi's `/events` response. Events without an
+/// `event_ticker` are skipped; a payload without `events` yields none.
+pub(crate) fn parse_kalshi_events(data: &serde_json::Value) -> Vec<Event> {
+    let Some(events) = data["events"].as_array() else {
+        warn!("Kalshi response had no events array, treating as an empty page");
+        return Vec::new();
+    };
+
+    events
+        .iter()
+        .filter_map(|event_data| {
+            let Some(event_ticker) = event_data["event_ticker"].as_str().filter(|t| !t.is_empty()) else {
+                warn!("Skipping Kalshi event without an event_ticker");
+                return None;
+            };
+
+            // Parse expiration time
+            let resolution_date = event_data["expected_expiration_time"]
+                .as_str()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc));
+
+            Some(Event {
+                platform: Platform::Kalshi,
+                event_id: event_ticker.to_string(),
+                title: event_data["title"].as_str().unwrap_or_default().to_string(),
+                description: event_data["subtitle"].as_str().unwrap_or_default().to_string(),
+                resolution_date,
+                category: event_data["category"].as_str().map(|s| s.to_string()),
+                tags: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Parse a CLOB order-book payload (`bids`/`asks` arrays of string price/size levels)
+pub(crate) fn parse_clob_book(data: &serde_json::Value) -> OrderBook {
+    let parse_levels = |side: &serde_json::Value| -> Vec<(f64, f64)> {
+        side.as_array()
+            .map(|levels| {
+                levels
+                    .iter()
+                    .filter_map(|level| {
+                        let price = json_f64(&level["price"])?;
+                        let size = json_f64(&level["size"])?;
+                        Some((price, size))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    OrderBook::new(parse_levels(&data["bids"]), parse_levels(&data["asks"]))
+}
+
+/// Read a number that may be encoded as either a JSON number or a string
+fn json_f64(value: &serde_json::Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Payout per contract held on `outcome` of market `ticker` from a portfolio
+/// settlements payload: `revenue` (cents) over the contracts held, or 0 if the
+/// other side won. Settlements for other markets are ignored.
+fn parse_kalshi_settlement_payout(data: &serde_json::Value, ticker: &str, outcome: &str) -> Option<f64> {
+    let settlements: Vec<&serde_json::Value> = data["settlements"]
+        .as_array()?
+        .iter()
+        .filter(|s| s["ticker"].as_str() == Some(ticker))
+        .collect();
+    if settlements.is_empty() {
+        return None;
+    }
+    let side = normalize_outcome(outcome).to_lowercase();
+
+    let held: f64 = settlements
+        .iter()
+        .filter_map(|s| json_f64(&s[format!("{}_count", side).as_str()]))
+        .sum();
+    if held <= 0.0 {
+        return None;
+    }
+
+    let won = settlements
+        .iter()
+        .any(|s| s["market_result"].as_str().map(str::to_lowercase).as_deref() == Some(side.as_str()));
+    if !won {
+        return Some(0.0);
+    }
+
+    let revenue: f64 = settlements.iter().filter_map(|s| json_f64(&s["revenue"])).sum();
+    Some(revenue / 100.0 / held)
+}
+
+/// Parse a Kalshi order-book payload (`[price_cents, quantity]` levels per outcome)
+pub(crate) fn parse_kalshi_book(data: &serde_json::Value) -> OrderBook {
+    let parse_levels = |side: &serde_json::Value| -> Vec<(f64, f64)> {
+        side.as_array()
+            .map(|levels| {
+                levels
+                    .iter()
+                    .filter_map(|level| {
+                        let cents = json_f64(&level[0])?;
+                        let quantity = json_f64(&level[1])?;
+                        Some((cents / 100.0, quantity)) // Kalshi uses cents, convert to dollars
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let yes_bids = parse_levels(&data["orderbook"]["yes"]);
+    let yes_asks = parse_levels(&data["orderbook"]["no"])
+        .into_iter()
+        .map(|(price, quantity)| (1.0 - price, quantity))
+        .collect();
+
+    OrderBook::new(yes_bids, yes_asks)
+}
+
+/// Build a quote from a Gamma `/events/{id}` payload. Single-market events
+/// use the market's own outcomes; multi-market (categorical) events get one
+/// outcome per market, priced at that market's YES.
+fn parse_polymarket_quote(data: &serde_json::Value) -> MarketQuote {
+    // Gamma encodes these arrays as JSON strings, e.g. "[\"0.4\", \"0.6\"]"
+    let string_array = |value: &serde_json::Value| -> Vec<String> {
+        value
+            .as_str()
+            .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())
+            .unwrap_or_default()
+    };
+
+    let markets = data["markets"].as_array().cloned().unwrap_or_default();
+    let liquidity = markets.iter().filter_map(|m| json_f64(&m["liquidity"])).sum();
+
+    let outcomes = match markets.as_slice() {
+        [market] => {
+            let names = string_array(&market["outcomes"]);
+            let prices = string_array(&market["outcomePrices"]);
+            names
+                .into_iter()
+                .zip(prices)
+                .filter_map(|(name, price)| Some(Outcome::new(name.to_uppercase(), price.parse().ok()?)))
+                .collect()
+        }
+        markets => markets
+            .iter()
+            .filter_map(|market| {
+                let name = market["groupItemTitle"]
+                    .as_str()
+                    .or_else(|| market["question"].as_str())?;
+                let yes_price = string_array(&market["outcomePrices"]).first()?.parse().ok()?;
+                Some(Outcome::new(name, yes_price))
+            })
+            .collect(),
+    };
+
+    MarketQuote::new(outcomes, liquidity)
+}
+
+/// Build a quote from a Kalshi `/events/{ticker}` payload: YES/NO for a
+/// single-market event, otherwise one outcome per market at its YES ask
+fn parse_kalshi_quote(data: &serde_json::Value) -> MarketQuote {
+    let markets = data["markets"].as_array().cloned().unwrap_or_default();
+    let liquidity = markets.iter().filter_map(|m| json_f64(&m["liquidity"])).sum::<f64>() / 100.0; // cents
+
+    // Prices are in cents; prefer the ask (what we'd pay), then the last trade
+    let yes_price = |market: &serde_json::Value| {
+        json_f64(&market["yes_ask"])
+            .or_else(|| json_f64(&market["last_price"]))
+            .map(|cents| cents / 100.0)
+    };
+
+    let outcomes = match markets.as_slice() {
+        [market] => {
+            let no = json_f64(&market["no_ask"]).map(|cents| cents / 100.0);
+            match (yes_price(market), no) {
+                (Some(yes), Some(no)) => vec![Outcome::new("YES", yes), Outcome::new("NO", no)],
+                (Some(yes), None) => vec![Outcome::new("YES", yes), Outcome::new("NO", 1.0 - yes)],
+                _ => Vec::new(),
+            }
+        }
+        markets => markets
+            .iter()
+            .filter_map(|market| {
+                let name = market["yes_sub_title"]
+                    .as_str()
+                    .or_else(|| market["title"].as_str())?;
+                Some(Outcome::new(name, yes_price(market)?))
+            })
+            .collect(),
+    };
+
+    MarketQuote::new(outcomes, liquidity)
+}
+
+/// A list that may arrive as a JSON array or as a JSON-encoded string (Gamma
+/// sends `"[\"Yes\", \"No\"]"`)
+fn json_string_list(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        serde_json::Value::String(encoded) => serde_json::from_str(encoded).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// (YES, NO) token IDs from market metadata - either Gamma's `clobTokenIds`
+/// (ordered like `outcomes`) or the CLOB's `tokens: [{token_id, outcome}]`
+fn parse_market_tokens(data: &serde_json::Value) -> Option<(String, String)> {
+    let pairs: Vec<(String, String)> = match data["tokens"].as_array() {
+        Some(tokens) => tokens
+            .iter()
+            .filter_map(|t| {
+                Some((t["outcome"].as_str()?.to_string(), t["token_id"].as_str()?.to_string()))
+            })
+            .collect(),
+        None => {
+            let ids = json_string_list(&data["clobTokenIds"]);
+            let mut outcomes = json_string_list(&data["outcomes"]);
+            if outcomes.is_empty() {
+                outcomes = vec!["YES".to_string(), "NO".to_string()];
+            }
+            outcomes.into_iter().zip(ids).collect()
+        }
+    };
+
+    let token = |wanted: &str| {
+        pairs
+            .iter()
+            .find(|(outcome, _)| normalize_outcome(outcome) == wanted)
+            .map(|(_, id)| id.clone())
+    };
+    Some((token("YES")?, token("NO")?))
+}
+
+/// Tolerance for float error when flooring share counts to whole contracts
+const CONTRACT_EPSILON: f64 = 1e-6;
+
+/// Whole Kalshi contracts for a share count - floored so an order never spends
+/// more than its amount, with a small tolerance so float error
+/// (e.g. 0.3 / 0.1 = 2.9999...) doesn't drop a contract
+fn kalshi_contract_count(shares: f64) -> BotResult<i64> {
+    let count = (shares + CONTRACT_EPSILON).floor() as i64;
+    if count < 1 {
+        return Err(BotError::InvalidOrder(format!(
+            "Order for {:.4} contracts rounds to zero",
+            shares
+        )));
+    }
+    Ok(count)
+}
+
+/// Polymarket token amounts carry 6 decimals - reject sizes that round to zero
+fn polymarket_token_units(shares: f64) -> BotResult<u128> {
+    let units = (shares * 1_000_000.0).round() as u128;
+    if units == 0 {
+        return Err(BotError::InvalidOrder(format!(
+            "Order for {} shares rounds to zero tokens",
+            shares
+        )));
+    }
+    Ok(units)
+}
+
+/// Parse a Kalshi `/portfolio/fills` payload. Prices are in cents on the
+/// filled side; `fee_cost` is in dollars.
+fn parse_kalshi_fills(data: &serde_json::Value) -> Vec<Fill> {
+    data["fills"]
+        .as_array()
+        .map(|fills| {
+            fills
+                .iter()
+                .filter_map(|fill| {
+                    let price = if fill["side"].as_str() == Some("no") {
+                        json_f64(&fill["no_price"])?
+                    } else {
+                        json_f64(&fill["yes_price"])?
+                    };
+                    Some(Fill {
+                        shares: json_f64(&fill["count"])?,
+                        price: price / 100.0,
+                        fee: json_f64(&fill["fee_cost"]).unwrap_or(0.0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Default price range (in dollars) from the best price counted as liquidity
+pub(crate) const DEFAULT_DEPTH_RANGE: f64 = 0.02;
+
+const POLYMARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+
+/// Subscribe on an open CLOB WebSocket and forward book updates until it closes.
+/// Returns `Ok(())` only when the receiving side has been dropped.
+async fn stream_book_updates(
+    ws: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    market_ids: &[String],
+    depth_range: f64,
+    tx: &mpsc::Sender<(String, MarketPrices)>,
+) -> Result<()> {
+    let (mut write, mut read) = ws.split();
+
+    let subscribe = serde_json::json!({
+        "assets_ids": market_ids,
+        "type": "market",
+    });
+    write
+        .send(Message::Text(subscribe.to_string()))
+        .await
+        .context("Failed to subscribe to Polymarket price stream")?;
+
+    while let Some(message) = read.next().await {
+        match message.context("Polymarket price stream read failed")? {
+            Message::Text(text) => {
+                let Ok(data) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    continue;
+                };
+
+                // The server may batch several book updates into one array
+                let updates = match data {
+                    serde_json::Value::Array(items) => items,
+                    other => vec![other],
+                };
+
+                for update in updates {
+                    if let Some(market_id) = update["market"].as_str() {
+                        let prices = parse_clob_book(&update).to_market_prices(depth_range);
+                        if tx.send((market_id.to_string(), prices)).await.is_err() {
+                            return Ok(()); // Subscriber went away
+                        }
+                    }
+                }
+            }
+            Message::Ping(payload) => {
+                write.send(Message::Pong(payload)).await?;
+            }
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+
+    Err(anyhow::anyhow!("Polymarket price stream closed by server"))
+}
+
+/// Read CLOB API credentials from the environment, if all three are set
+fn clob_credentials_from_env() -> Option<ClobCredentials> {
+    Some(ClobCredentials {
+        api_key: std::env::var("POLYMARKET_API_KEY").ok()?,
+        api_secret: std::env::var("POLYMARKET_API_SECRET").ok()?,
+        passphrase: std::env::var("POLYMARKET_API_PASSPHRASE").ok()?,
+    })
+}
+
+/// A venue's reported winning outcome: YES, NO, or anything else (e.g.
+/// "void", "cancelled", "50-50") as a void resolution
+fn resolution_from_outcome(outcome: &str) -> Resolution {
+    match normalize_outcome(outcome).as_str() {
+        "YES" => Resolution::Yes,
+        "NO" => Resolution::No,
+        _ => Resolution::Void,
+    }
+}
+
+/// Whether `id` looks like a CTF condition ID (0x followed by 64 hex digits)
+fn is_condition_id(id: &str) -> bool {
+    id.strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Page size and safety cap for `PolymarketClient::fetch_events`
+const POLYMARKET_EVENT_PAGE_SIZE: usize = 1000;
+const POLYMARKET_MAX_EVENT_PAGES: usize = 50;
 
 // Polymarket API Client
 #[derive(Clone)]
 pub struct PolymarketClient {
     http_client: Client,
     polygon_rpc_url: String,
+    fallback_rpc_urls: Vec<String>,
     wallet_private_key: Option<String>,
+    clob_credentials: Option<ClobCredentials>,
     base_url: String,
+    clob_base_url: String,
+    ws_url: String,
+    retry_policy: RetryPolicy,
+    depth_range: f64,
+    token_cache: Arc<Mutex<HashMap<String, (String, String)>>>, // market -> (YES, NO) token IDs
+    event_cache: EventCache,
+    request_limit: Option<Arc<Semaphore>>, // Max in-flight requests, shared across clones
+    // Built on first use from the settings above, then shared across clones so
+    // every transaction draws from one nonce sequence
+    blockchain: Arc<OnceCell<PolymarketBlockchain>>,
+}
+
+impl Default for PolymarketClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PolymarketClient {
     pub fn new() -> Self {
-        // Create HTTP client with connection pooling and timeouts
-        let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .pool_max_idle_per_host(10)
-            .pool_idle_timeout(std::time::Duration::from_secs(90))
+        let http_client = http_client_builder()
             .build()
             .unwrap_or_else(|_| Client::new()); // Fallback to default if builder fails
         
@@ -29,11 +657,45 @@ impl PolymarketClient {
             http_client,
             polygon_rpc_url: std::env::var("POLYGON_RPC_URL")
                 .unwrap_or_else(|_| "https://polygon-rpc.com".to_string()),
+            fallback_rpc_urls: Vec::new(),
             wallet_private_key: std::env::var("POLYMARKET_WALLET_PRIVATE_KEY").ok(),
+            clob_credentials: clob_credentials_from_env(),
             base_url: "https://gamma-api.polymarket.com".to_string(),
+            clob_base_url: DEFAULT_CLOB_URL.to_string(),
+            ws_url: POLYMARKET_WS_URL.to_string(),
+            retry_policy: RetryPolicy::default(),
+            depth_range: DEFAULT_DEPTH_RANGE,
+            token_cache: Arc::new(Mutex::new(HashMap::new())),
+            event_cache: EventCache::new(DEFAULT_EVENT_CACHE_TTL),
+            request_limit: None,
+            blockchain: Arc::new(OnceCell::new()),
         }
     }
 
+    /// Cap in-flight requests with a semaphore, which may be shared with other clients
+    pub fn with_request_limit(mut self, request_limit: Arc<Semaphore>) -> Self {
+        self.request_limit = Some(request_limit);
+        self
+    }
+
+    /// Route all requests through an HTTP(S) or SOCKS5 proxy. Without one, the
+    /// `HTTP_PROXY`/`HTTPS_PROXY` environment variables are honoured as usual.
+    pub fn with_proxy(mut self, proxy_url: String) -> Result<Self> {
+        self.http_client = proxied_http_client(&proxy_url)?;
+        Ok(self)
+    }
+
+    /// Send through the retry policy and request limit
+    async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        send_with_retry(&self.retry_policy, None, self.request_limit.as_deref(), request).await
+    }
+
+    /// Serve `fetch_events` from memory for `ttl` between refreshes
+    pub fn with_event_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.event_cache = EventCache::new(ttl);
+        self
+    }
+
     pub fn with_wallet(mut self, private_key: String) -> Self {
         self.wallet_private_key = Some(private_key);
         self
@@ -44,12 +706,74 @@ impl PolymarketClient {
         self
     }
 
-    /// Fetch active markets/events from Polymarket
-    pub async fn fetch_events(&self) -> Result<Vec<Event>> {
+    /// Send event/settlement queries to `base_url` (e.g. a local mock server)
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Send order book and order requests to `base_url` instead of the CLOB
+    pub fn with_clob_base_url(mut self, base_url: String) -> Self {
+        self.clob_base_url = base_url;
+        self
+    }
+
+    /// Stream prices from `ws_url` instead of the CLOB WebSocket
+    pub fn with_ws_url(mut self, ws_url: String) -> Self {
+        self.ws_url = ws_url;
+        self
+    }
+
+    /// Extra RPC endpoints to fail over to when the primary one is down
+    pub fn with_fallback_rpcs(mut self, rpc_urls: Vec<String>) -> Self {
+        self.fallback_rpc_urls = rpc_urls;
+        self
+    }
+
+    /// Primary RPC URL followed by the fallbacks
+    fn rpc_urls(&self) -> Vec<String> {
+        std::iter::once(self.polygon_rpc_url.clone())
+            .chain(self.fallback_rpc_urls.iter().cloned())
+            .collect()
+    }
+
+    pub fn with_clob_credentials(mut self, credentials: ClobCredentials) -> Self {
+        self.clob_credentials = Some(credentials);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Count liquidity resting within `cents` of the best price
+    pub fn with_depth_range(mut self, cents: f64) -> Self {
+        self.depth_range = cents / 100.0;
+        self
+    }
+
+    /// Fetch active events, from the cache if it hasn't expired
+    pub async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        match self.event_cache.get().await {
+            Some(events) => Ok(events),
+            None => self.force_refresh().await,
+        }
+    }
+
+    /// Fetch events from the API regardless of the cache, then cache them
+    pub async fn force_refresh(&self) -> BotResult<Vec<Event>> {
+        let events = dedupe_events(self.fetch_events_uncached().await?);
+        self.event_cache.store(events.clone()).await;
+        Ok(events)
+    }
+
+    /// Fetch active markets/events from Polymarket, paging with `first`/`offset`
+    async fn fetch_events_uncached(&self) -> BotResult<Vec<Event>> {
         // Polymarket uses GraphQL API
         let query = r#"
-            query GetMarkets($active: Boolean) {
-                markets(active: $active, limit: 1000) {
+            query GetMarkets($active: Boolean, $first: Int, $offset: Int) {
+                markets(active: $active, limit: $first, offset: $offset) {
                     id
                     question
                     description
@@ -63,104 +787,212 @@ impl PolymarketClient {
             }
         "#;
 
-        let variables = serde_json::json!({
-            "active": true
-        });
+        let mut events = Vec::new();
 
-        let response = self
+        for page in 0..POLYMARKET_MAX_EVENT_PAGES {
+            let variables = serde_json::json!({
+                "active": true,
+                "first": POLYMARKET_EVENT_PAGE_SIZE,
+                "offset": page * POLYMARKET_EVENT_PAGE_SIZE,
+            });
+
+            let request = self
+                .http_client
+                .post(format!("{}/graphql", self.base_url))
+                .json(&serde_json::json!({
+                    "query": query,
+                    "variables": variables
+                }));
+            let response = self
+                .send(request)
+                .await
+                .context("Failed to fetch Polymarket events")?;
+
+            let data: serde_json::Value = response
+                .json()
+                .await
+                .context("Failed to parse Polymarket response")?;
+
+            let markets = data["data"]["markets"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            if data["data"]["markets"].is_null() {
+                warn!("Polymarket response had no data.markets, treating as an empty page");
+            }
+
+            events.extend(parse_polymarket_markets(&markets));
+
+            // A short page means there is nothing left to fetch
+            if markets.len() < POLYMARKET_EVENT_PAGE_SIZE {
+                break;
+            }
+
+            if page + 1 == POLYMARKET_MAX_EVENT_PAGES {
+                warn!(
+                    "Polymarket event pagination hit the {} page cap - results truncated",
+                    POLYMARKET_MAX_EVENT_PAGES
+                );
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Fetch the CLOB order book for a market
+    pub async fn fetch_order_book(&self, event_id: &str) -> BotResult<OrderBook> {
+        // Use Polymarket's CLOB API for prices
+        let url = format!("{}/book", self.clob_base_url);
+
+        let request = self
             .http_client
-            .post(&format!("{}/graphql", self.base_url))
-            .json(&serde_json::json!({
-                "query": query,
-                "variables": variables
-            }))
-            .send()
+            .get(&url)
+            .query(&[("market", event_id)]);
+        let response = self
+            .send(request)
             .await
-            .context("Failed to fetch Polymarket events")?;
+            .context("Failed to fetch Polymarket order book")?;
 
         let data: serde_json::Value = response
             .json()
             .await
-            .context("Failed to parse Polymarket response")?;
+            .context("Failed to parse order book response")?;
 
-        let mut events = Vec::new();
+        Ok(parse_clob_book(&data))
+    }
 
-        if let Some(markets) = data["data"]["markets"].as_array() {
-            for market in markets {
-                let event_id = market["id"]
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_string();
-                let title = market["question"]
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_string();
-                let description = market["description"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-                let category = market["category"]
-                    .as_str()
-                    .map(|s| s.to_string());
-                
-                // Parse end date
-                let resolution_date = market["endDate"]
-                    .as_str()
-                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                    .map(|dt| dt.with_timezone(&Utc));
-
-                events.push(Event {
-                    platform: "polymarket".to_string(),
-                    event_id,
-                    title,
-                    description,
-                    resolution_date,
-                    category,
-                    tags: Vec::new(),
-                });
-            }
+    /// Fetch current prices for a market, with liquidity measured from book depth
+    pub async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        let quote = self.fetch_order_book(event_id).await?.to_market_quote(self.depth_range);
+        Ok(quote.binary_prices().expect("order book quotes are binary"))
+    }
+
+    /// (YES, NO) CLOB token IDs for a market, cached after the first lookup
+    pub async fn fetch_market_tokens(&self, market_id: &str) -> BotResult<(String, String)> {
+        if let Some(tokens) = self.token_cache.lock().await.get(market_id) {
+            return Ok(tokens.clone());
         }
 
-        Ok(events)
+        let request = self
+            .http_client
+            .get(format!("{}/markets/{}", self.base_url, market_id));
+        let response = self
+            .send(request)
+            .await
+            .context("Failed to fetch Polymarket market metadata")?;
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse market metadata")?;
+
+        let tokens = parse_market_tokens(&data)
+            .with_context(|| format!("No CLOB token IDs for market {}", market_id))?;
+        self.token_cache
+            .lock()
+            .await
+            .insert(market_id.to_string(), tokens.clone());
+        Ok(tokens)
     }
 
-    /// Fetch current prices for a market
-    pub async fn fetch_prices(&self, event_id: &str) -> Result<MarketPrices> {
-        // Use Polymarket's CLOB API for prices
-        let url = format!("https://clob.polymarket.com/book", event_id);
-        
-        let response = self
+    /// CLOB token ID for one outcome ("YES"/"NO") of a market
+    async fn token_id_for(&self, market_id: &str, outcome: &str) -> BotResult<String> {
+        let (yes_token, no_token) = self.fetch_market_tokens(market_id).await?;
+        match normalize_outcome(outcome).as_str() {
+            "YES" => Ok(yes_token),
+            "NO" => Ok(no_token),
+            other => Err(BotError::InvalidOrder(format!("unknown outcome {} for market {}", other, market_id))),
+        }
+    }
+
+    /// Fetch prices for every outcome of an event (binary or categorical)
+    pub async fn fetch_quote(&self, event_id: &str) -> BotResult<MarketQuote> {
+        let request = self
             .http_client
-            .get(&url)
-            .query(&[("market", event_id)])
-            .send()
+            .get(format!("{}/events/{}", self.base_url, event_id));
+        let response = self
+            .send(request)
             .await
-            .context("Failed to fetch Polymarket prices")?;
+            .context("Failed to fetch Polymarket event quote")?;
 
         let data: serde_json::Value = response
             .json()
             .await
-            .context("Failed to parse price response")?;
+            .context("Failed to parse Polymarket event response")?;
 
-        // Extract Yes and No prices from order book
-        let yes_price = data["yes"]
-            .as_object()
-            .and_then(|o| o.get("bestBid"))
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
+        Ok(parse_polymarket_quote(&data))
+    }
 
-        let no_price = data["no"]
-            .as_object()
-            .and_then(|o| o.get("bestBid"))
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
+    /// Subscribe to live order-book updates from the CLOB WebSocket.
+    /// Reconnects with backoff on disconnect and resubscribes to the same markets;
+    /// the background task stops once the returned stream is dropped.
+    pub fn subscribe_prices(&self, market_ids: Vec<String>) -> impl Stream<Item = (String, MarketPrices)> {
+        let (tx, rx) = mpsc::channel(256);
+        let retry_policy = self.retry_policy.clone();
+        let depth_range = self.depth_range;
+        let ws_url = self.ws_url.clone();
+
+        tokio::spawn(async move {
+            let mut attempt = 0;
+
+            while !tx.is_closed() {
+                match connect_async(ws_url.as_str()).await {
+                    Ok((ws, _)) => {
+                        info!("Connected to Polymarket price stream ({} markets)", market_ids.len());
+                        attempt = 0;
+                        if let Err(e) = stream_book_updates(ws, &market_ids, depth_range, &tx).await {
+                            warn!("Polymarket price stream disconnected: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to connect to Polymarket price stream: {}", e);
+                    }
+                }
 
-        // Calculate liquidity (sum of order book depth)
-        let liquidity = data["liquidity"]
-            .as_f64()
-            .unwrap_or(0.0);
+                if tx.is_closed() {
+                    break;
+                }
+
+                tokio::time::sleep(retry_policy.delay_for(attempt.min(retry_policy.max_retries))).await;
+                attempt += 1;
+            }
+        });
+
+        futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|update| (update, rx))
+        })
+    }
+
+    /// The shared blockchain/CLOB client, with the wallet and API credentials
+    /// when configured
+    async fn blockchain(&self) -> BotResult<&PolymarketBlockchain> {
+        self.blockchain
+            .get_or_try_init(|| async {
+                let mut blockchain = PolymarketBlockchain::with_rpcs(&self.rpc_urls())?
+                    .with_clob_base_url(self.clob_base_url.clone());
+                if let Some(private_key) = &self.wallet_private_key {
+                    blockchain = blockchain
+                        .with_wallet(private_key)
+                        .context("Failed to initialize blockchain client")?;
+                }
+                if let Some(credentials) = &self.clob_credentials {
+                    blockchain = blockchain.with_clob_credentials(credentials.clone());
+                }
+                if let Some(request_limit) = &self.request_limit {
+                    blockchain = blockchain.with_request_limit(request_limit.clone());
+                }
+                Ok(blockchain)
+            })
+            .await
+    }
 
-        Ok(MarketPrices::new(yes_price, no_price, liquidity))
+    /// The shared blockchain client, erroring unless a wallet is configured
+    async fn trading_client(&self) -> BotResult<&PolymarketBlockchain> {
+        // Check if wallet is configured
+        self.wallet_private_key
+            .as_ref()
+            .context("Polymarket wallet private key not configured. Set POLYMARKET_WALLET_PRIVATE_KEY environment variable")?;
+        self.blockchain().await
     }
 
     /// Place a buy order on Polymarket (requires wallet and blockchain interaction)
@@ -170,19 +1002,20 @@ impl PolymarketClient {
         outcome: String, // "YES" or "NO"
         amount: f64,
         max_price: f64,
-    ) -> Result<Option<String>> {
-        // Check if wallet is configured
-        let private_key = self
-            .wallet_private_key
-            .as_ref()
-            .context("Polymarket wallet private key not configured. Set POLYMARKET_WALLET_PRIVATE_KEY environment variable")?;
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        let shares = validate_order(amount, max_price)?;
+        polymarket_token_units(shares)?;
+        let outcome = normalize_outcome(&outcome);
 
         // Use blockchain client for order placement
-        use crate::polymarket_blockchain::PolymarketBlockchain;
-        
-        let blockchain = PolymarketBlockchain::new(&self.polygon_rpc_url)?
-            .with_wallet(private_key)
-            .context("Failed to initialize blockchain client")?;
+        let blockchain = self.trading_client().await?;
+
+        // Orders revert unless the exchange may spend our USDC
+        blockchain
+            .ensure_exchange_allowance(amount)
+            .await
+            .context("Failed to set USDC allowance for the exchange")?;
 
         // Try blockchain method first, fall back to CLOB if needed
         match blockchain.place_order_via_blockchain(&event_id, &outcome, amount, max_price).await {
@@ -192,18 +1025,85 @@ impl PolymarketClient {
             }
             Ok(None) => {
                 warn!("Polymarket order returned None (may need contract addresses)");
-                Err(anyhow::anyhow!("Order placement failed - contract addresses may be missing"))
+                Err(BotError::Blockchain(
+                    "Order placement failed - contract addresses may be missing".to_string(),
+                ))
             }
             Err(e) => {
                 warn!("Blockchain order failed: {:?}. Attempting CLOB API...", e);
                 // Fall back to CLOB API (if implemented)
-                blockchain.place_order_via_clob(&self.http_client, &event_id, &outcome, amount, max_price).await
+                // The CLOB trades the outcome's ERC-1155 token, not the market
+                let token_id = self.token_id_for(&event_id, &outcome).await?;
+                Ok(blockchain
+                    .place_order_via_clob(&self.http_client, &token_id, &outcome, amount, max_price, time_in_force)
+                    .await?)
             }
         }
     }
 
+    /// Sell `shares` tokens of `event_id` at no less than `min_price` each (CLOB only)
+    pub async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>> {
+        validate_order(shares * min_price, min_price)?;
+        polymarket_token_units(shares)?;
+
+        info!("Selling {:.2} {} shares of {} on Polymarket", shares, outcome, event_id);
+        let token_id = self.token_id_for(&event_id, &outcome).await?;
+        Ok(self
+            .trading_client()
+            .await?
+            .place_sell_order_via_clob(&self.http_client, &token_id, shares, min_price)
+            .await?)
+    }
+
+    /// Cancel whatever is still resting of a CLOB order
+    pub async fn cancel_order(&self, order_id: &str) -> BotResult<()> {
+        Ok(self
+            .trading_client()
+            .await?
+            .cancel_clob_order(&self.http_client, order_id)
+            .await?)
+    }
+
+    /// CLOB order status, lowercased (e.g. "live", "matched", "canceled")
+    pub async fn get_order_status(&self, order_id: &str) -> BotResult<String> {
+        let order = self
+            .trading_client().await?
+            .get_clob_order(&self.http_client, order_id)
+            .await?;
+        Ok(order["status"].as_str().unwrap_or("unknown").to_lowercase())
+    }
+
+    /// Fills so far for a CLOB order
+    pub async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>> {
+        Ok(self
+            .trading_client()
+            .await?
+            .get_clob_fills(&self.http_client, order_id)
+            .await
+            .context("Failed to fetch Polymarket fills")?)
+    }
+
     /// Check if an event is settled and get the outcome
-    pub async fn check_settlement(&self, event_id: &str) -> Result<Option<bool>> {
+    pub async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>> {
+        // Prefer the on-chain payout when the ID is a condition ID (0x + 32 bytes);
+        // the API can lag the oracle
+        if is_condition_id(event_id) {
+            let onchain = match self.blockchain().await {
+                Ok(blockchain) => blockchain.check_resolution(event_id).await.map_err(BotError::from),
+                Err(e) => Err(e),
+            };
+            match onchain {
+                Ok(resolution) => return Ok(resolution),
+                Err(e) => warn!("On-chain resolution check failed for {}, using API: {}", event_id, e),
+            }
+        }
+
         // Query Polymarket API for market status
         let query = r#"
             query GetMarket($id: ID!) {
@@ -218,14 +1118,15 @@ impl PolymarketClient {
             "id": event_id
         });
 
-        let response = self
+        let request = self
             .http_client
-            .post(&format!("{}/graphql", self.base_url))
+            .post(format!("{}/graphql", self.base_url))
             .json(&serde_json::json!({
                 "query": query,
                 "variables": variables
-            }))
-            .send()
+            }));
+        let response = self
+            .send(request)
             .await
             .context("Failed to check Polymarket settlement")?;
 
@@ -237,7 +1138,7 @@ impl PolymarketClient {
         if let Some(resolved) = data["data"]["market"]["resolved"].as_bool() {
             if resolved {
                 if let Some(outcome) = data["data"]["market"]["outcome"].as_str() {
-                    return Ok(Some(outcome == "YES"));
+                    return Ok(Some(resolution_from_outcome(outcome)));
                 }
             }
         }
@@ -245,59 +1146,212 @@ impl PolymarketClient {
         Ok(None) // Not yet settled
     }
 
+    /// Estimate the USD gas cost of one Polymarket order, given a MATIC price
+    pub async fn estimate_gas_cost_usd(&self, matic_usd: f64) -> BotResult<f64> {
+        Ok(self
+            .blockchain()
+            .await?
+            .estimate_order_gas_cost_usd(matic_usd)
+            .await?)
+    }
+
     /// Get wallet balance (USDC on Polygon)
-    pub async fn get_balance(&self) -> Result<f64> {
-        let private_key = self
-            .wallet_private_key
+    pub async fn get_balance(&self) -> BotResult<f64> {
+        self.wallet_private_key
             .as_ref()
             .context("Wallet private key required for balance check")?;
 
         // Use blockchain client for balance check
-        use crate::polymarket_blockchain::PolymarketBlockchain;
-        
-        let blockchain = PolymarketBlockchain::new(&self.polygon_rpc_url)?
-            .with_wallet(private_key)
-            .context("Failed to initialize blockchain client")?;
+        Ok(self.blockchain().await?.get_usdc_balance().await?)
+    }
+
+    /// Payout per share of `outcome` from the on-chain payout numerators. Only
+    /// condition IDs can be checked; other IDs return `None`.
+    pub async fn settlement_payout_per_share(&self, event_id: &str, outcome: &str) -> BotResult<Option<f64>> {
+        if !is_condition_id(event_id) {
+            return Ok(None);
+        }
+        let index = if normalize_outcome(outcome) == "NO" { 1 } else { 0 };
+        Ok(self.blockchain().await?.payout_per_share(event_id, index).await?)
+    }
+
+    /// Get the wallet's MATIC balance (for gas)
+    pub async fn get_native_balance(&self) -> BotResult<f64> {
+        self.wallet_private_key
+            .as_ref()
+            .context("Wallet private key required for balance check")?;
+
+        Ok(self.blockchain().await?.get_native_balance().await?)
+    }
+}
+
+/// Kalshi deployment to trade against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Environment {
+    #[default]
+    Production,
+    Demo, // Sandbox with fake funds
+}
+
+impl Environment {
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Environment::Production => "https://api.cfexchange.com",
+            Environment::Demo => "https://demo-api.kalshi.co",
+        }
+    }
+}
+
+impl FromStr for Environment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "production" | "prod" => Ok(Environment::Production),
+            "demo" | "sandbox" => Ok(Environment::Demo),
+            _ => Err(anyhow::anyhow!("Unknown Kalshi environment: {}", s)),
+        }
+    }
+}
 
-        blockchain.get_usdc_balance().await
+/// RSA keys for signing Kalshi requests (and checking our own signatures)
+struct KalshiSigningKeys {
+    signing: rsa::pkcs1v15::SigningKey<sha2::Sha256>,
+    verifying: rsa::pkcs1v15::VerifyingKey<sha2::Sha256>,
+}
+
+impl KalshiSigningKeys {
+    /// Parse a PKCS#8 or PKCS#1 PEM private key; `None` (with a warning) if neither works
+    fn from_pem(pem: &str) -> Option<Self> {
+        use rsa::pkcs1::DecodeRsaPrivateKey;
+        use rsa::pkcs8::DecodePrivateKey;
+        use rsa::RsaPrivateKey;
+
+        let Some(private_key) = RsaPrivateKey::from_pkcs8_pem(pem)
+            .ok()
+            .or_else(|| RsaPrivateKey::from_pkcs1_pem(pem).ok())
+        else {
+            warn!("Failed to parse RSA private key from API secret. Using API key only authentication.");
+            return None;
+        };
+
+        Some(Self {
+            verifying: rsa::pkcs1v15::VerifyingKey::new(private_key.to_public_key()),
+            signing: rsa::pkcs1v15::SigningKey::new(private_key),
+        })
     }
 }
 
+/// Safety cap on cursor pages followed by `KalshiClient::fetch_events`
+const KALSHI_MAX_EVENT_PAGES: usize = 50;
+
 // Kalshi API Client
 #[derive(Clone)]
 pub struct KalshiClient {
     http_client: Client,
     api_key: String,
-    api_secret: String,
+    signing_keys: Option<Arc<KalshiSigningKeys>>, // Parsed from the API secret once
     base_url: String,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>, // Shared across clones
+    depth_range: f64,
+    verify_signatures: bool, // Check each request signature against our public key
+    event_cache: EventCache,
+    request_limit: Option<Arc<Semaphore>>, // Max in-flight requests, shared across clones
 }
 
 impl KalshiClient {
     pub fn new(api_key: String, api_secret: String) -> Self {
-        // Create HTTP client with connection pooling and timeouts
-        let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
-            .pool_max_idle_per_host(10)
-            .pool_idle_timeout(std::time::Duration::from_secs(90))
+        let http_client = http_client_builder()
             .build()
             .unwrap_or_else(|_| Client::new()); // Fallback to default if builder fails
         
         Self {
             http_client,
             api_key,
-            api_secret,
-            base_url: "https://api.cfexchange.com".to_string(), // Kalshi API base URL
+            signing_keys: KalshiSigningKeys::from_pem(&api_secret).map(Arc::new),
+            base_url: Environment::Production.base_url().to_string(), // Kalshi API base URL
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: Some(Arc::new(RateLimiter::new(10.0))), // Kalshi basic tier limit
+            depth_range: DEFAULT_DEPTH_RANGE,
+            verify_signatures: cfg!(debug_assertions),
+            event_cache: EventCache::new(DEFAULT_EVENT_CACHE_TTL),
+            request_limit: None,
         }
     }
 
+    /// Cap in-flight requests with a semaphore, which may be shared with other clients
+    pub fn with_request_limit(mut self, request_limit: Arc<Semaphore>) -> Self {
+        self.request_limit = Some(request_limit);
+        self
+    }
+
+    /// Route all requests through an HTTP(S) or SOCKS5 proxy. Without one, the
+    /// `HTTP_PROXY`/`HTTPS_PROXY` environment variables are honoured as usual.
+    pub fn with_proxy(mut self, proxy_url: String) -> Result<Self> {
+        self.http_client = proxied_http_client(&proxy_url)?;
+        Ok(self)
+    }
+
+    /// Send through the retry policy, rate limiter and request limit
+    async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        send_with_retry(
+            &self.retry_policy,
+            self.rate_limiter.as_deref(),
+            self.request_limit.as_deref(),
+            request,
+        )
+        .await
+    }
+
+    /// Serve `fetch_events` from memory for `ttl` between refreshes
+    pub fn with_event_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.event_cache = EventCache::new(ttl);
+        self
+    }
+
+    /// Send requests to `base_url` (e.g. a local mock server)
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Point the client at Kalshi production or the demo sandbox
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.base_url = environment.base_url().to_string();
+        self
+    }
+
+    /// Verify every request signature locally before sending (on by default in
+    /// debug builds). Catches key-format or digest mismatches before the API does.
+    pub fn with_signature_verification(mut self, enabled: bool) -> Self {
+        self.verify_signatures = enabled;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Count liquidity resting within `cents` of the best price
+    pub fn with_depth_range(mut self, cents: f64) -> Self {
+        self.depth_range = cents / 100.0;
+        self
+    }
+
+    /// Limit outbound requests to `requests_per_second` (shared by all clones)
+    pub fn with_rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(requests_per_second)));
+        self
+    }
+
     /// Generate authentication headers for Kalshi API
     /// Uses RSA-PSS signature for secure authentication
     fn get_auth_headers(&self, method: &str, path: &str, body: &str) -> Result<reqwest::header::HeaderMap> {
         use reqwest::header::{HeaderMap, HeaderValue};
         use std::time::{SystemTime, UNIX_EPOCH};
-        use rsa::{RsaPrivateKey, pkcs1v15::{SigningKey, VerifyingKey}};
-        use rsa::signature::{Signer, Verifier};
-        use sha2::Sha256;
+        use rsa::signature::{SignatureEncoding, Signer, Verifier};
         use base64::{engine::general_purpose, Engine as _};
 
         let mut headers = HeaderMap::new();
@@ -312,26 +1366,21 @@ impl KalshiClient {
         // Create signature string: timestamp\nmethod\npath\nbody
         let signature_string = format!("{}\n{}\n{}\n{}", timestamp, method, path, body);
 
-        // Try to parse API secret as RSA private key (PEM format)
-        // Kalshi API secret might be in different formats, so we try multiple
-        let signature_b64 = if let Ok(private_key) = RsaPrivateKey::from_pkcs8_pem(&self.api_secret) {
-            // PEM format - create signing key
-            let signing_key = SigningKey::<Sha256>::new(private_key);
-            
+        // Sign with the key parsed once in `new`; without one, fall back to
+        // API key only (some endpoints may work with just the key)
+        let signature_b64 = if let Some(keys) = &self.signing_keys {
             // Sign the message
-            let signature = signing_key.sign(signature_string.as_bytes());
+            let signature = keys.signing.sign(signature_string.as_bytes());
+
+            if self.verify_signatures {
+                if let Err(e) = keys.verifying.verify(signature_string.as_bytes(), &signature) {
+                    error!("❌ Kalshi request signature failed local verification ({}) - check KALSHI_API_SECRET", e);
+                }
+            }
             
             // Encode signature in Base64
-            general_purpose::STANDARD.encode(&signature.to_bytes())
-        } else if let Ok(private_key) = RsaPrivateKey::from_pkcs1_pem(&self.api_secret) {
-            // Try PKCS1 format
-            let signing_key = SigningKey::<Sha256>::new(private_key);
-            let signature = signing_key.sign(signature_string.as_bytes());
-            general_purpose::STANDARD.encode(&signature.to_bytes())
+            general_purpose::STANDARD.encode(signature.to_bytes())
         } else {
-            // If RSA parsing fails, fall back to API key only
-            // Some endpoints may work with just API key
-            warn!("Failed to parse RSA private key from API secret. Using API key only authentication.");
             String::new()
         };
 
@@ -364,125 +1413,145 @@ impl KalshiClient {
         Ok(headers)
     }
 
-    /// Fetch active events from Kalshi
-    pub async fn fetch_events(&self) -> Result<Vec<Event>> {
+    /// Fetch active events, from the cache if it hasn't expired
+    pub async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        match self.event_cache.get().await {
+            Some(events) => Ok(events),
+            None => self.force_refresh().await,
+        }
+    }
+
+    /// Fetch events from the API regardless of the cache, then cache them
+    pub async fn force_refresh(&self) -> BotResult<Vec<Event>> {
+        let events = dedupe_events(self.fetch_events_uncached().await?);
+        self.event_cache.store(events.clone()).await;
+        Ok(events)
+    }
+
+    /// Fetch active events from Kalshi, following the pagination cursor
+    async fn fetch_events_uncached(&self) -> BotResult<Vec<Event>> {
         let path = "/trade-api/v2/events";
-        let headers = self.get_auth_headers("GET", path, "")?;
+        let mut events = Vec::new();
+        let mut cursor: Option<String> = None;
 
-        let response = self
-            .http_client
-            .get(&format!("{}{}", self.base_url, path))
-            .headers(headers)
-            .query(&[("status", "open"), ("limit", "1000")])
-            .send()
-            .await
-            .context("Failed to fetch Kalshi events")?;
+        for page in 0..KALSHI_MAX_EVENT_PAGES {
+            let headers = self.get_auth_headers("GET", path, "")?;
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Kalshi API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            ));
-        }
+            let mut query = vec![("status", "open"), ("limit", "1000")];
+            if let Some(cursor) = cursor.as_deref() {
+                query.push(("cursor", cursor));
+            }
 
-        let data: serde_json::Value = response
-            .json()
-            .await
-            .context("Failed to parse Kalshi response")?;
+            let request = self
+                .http_client
+                .get(format!("{}{}", self.base_url, path))
+                .headers(headers)
+                .query(&query);
+            let response = self
+                .send(request)
+                .await
+                .context("Failed to fetch Kalshi events")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(BotError::from_status(status, error_text));
+            }
 
-        let mut events = Vec::new();
+            let data: serde_json::Value = response
+                .json()
+                .await
+                .context("Failed to parse Kalshi response")?;
 
-        if let Some(events_array) = data["events"].as_array() {
-            for event_data in events_array {
-                let event_ticker = event_data["event_ticker"]
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_string();
-                let title = event_data["title"]
-                    .as_str()
-                    .unwrap_or_default()
-                    .to_string();
-                let subtitle = event_data["subtitle"]
-                    .as_str()
-                    .unwrap_or("")
-                    .to_string();
-                let category = event_data["category"]
-                    .as_str()
-                    .map(|s| s.to_string());
+            events.extend(parse_kalshi_events(&data));
 
-                // Parse expiration time
-                let resolution_date = event_data["expected_expiration_time"]
-                    .as_str()
-                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                    .map(|dt| dt.with_timezone(&Utc));
-
-                events.push(Event {
-                    platform: "kalshi".to_string(),
-                    event_id: event_ticker,
-                    title,
-                    description: subtitle,
-                    resolution_date,
-                    category,
-                    tags: Vec::new(),
-                });
+            // An empty or missing cursor means this was the last page
+            cursor = data["cursor"]
+                .as_str()
+                .filter(|c| !c.is_empty())
+                .map(|c| c.to_string());
+
+            if cursor.is_none() {
+                break;
+            }
+
+            if page + 1 == KALSHI_MAX_EVENT_PAGES {
+                warn!(
+                    "Kalshi event pagination hit the {} page cap - results truncated",
+                    KALSHI_MAX_EVENT_PAGES
+                );
             }
         }
 
         Ok(events)
     }
 
-    /// Fetch current prices for a Kalshi event
-    pub async fn fetch_prices(&self, event_id: &str) -> Result<MarketPrices> {
-        let path = format!("/trade-api/v2/events/{}/markets", event_id);
+    /// Fetch the order book for a Kalshi market.
+    /// Kalshi publishes YES and NO bids in cents; a NO bid at p is a YES ask at 100 - p.
+    pub async fn fetch_order_book(&self, event_id: &str) -> BotResult<OrderBook> {
+        let path = format!("/trade-api/v2/markets/{}/orderbook", event_id);
         let headers = self.get_auth_headers("GET", &path, "")?;
 
-        let response = self
+        let request = self
             .http_client
-            .get(&format!("{}{}", self.base_url, path))
-            .headers(headers)
-            .send()
+            .get(format!("{}{}", self.base_url, path))
+            .headers(headers);
+        let response = self
+            .send(request)
             .await
-            .context("Failed to fetch Kalshi prices")?;
+            .context("Failed to fetch Kalshi order book")?;
 
         if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Kalshi API error: {} - {}",
-                response.status(),
-                response.text().await.unwrap_or_default()
-            ));
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::from_status(status, error_text));
         }
 
         let data: serde_json::Value = response
             .json()
             .await
-            .context("Failed to parse Kalshi price response")?;
+            .context("Failed to parse Kalshi order book response")?;
 
-        let mut yes_price = 0.0;
-        let mut no_price = 0.0;
-        let mut liquidity = 0.0;
+        Ok(parse_kalshi_book(&data))
+    }
 
-        if let Some(markets) = data["markets"].as_array() {
-            for market in markets {
-                let subtitle = market["subtitle"].as_str().unwrap_or("");
-                let last_price = market["last_price"]
-                    .as_i64()
-                    .unwrap_or(0) as f64
-                    / 100.0; // Kalshi uses cents, convert to dollars
+    /// Fetch current prices for a Kalshi event, with liquidity measured from book depth
+    pub async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        let quote = self.fetch_order_book(event_id).await?.to_market_quote(self.depth_range);
+        Ok(quote.binary_prices().expect("order book quotes are binary"))
+    }
 
-                if subtitle == "Yes" {
-                    yes_price = last_price;
-                } else if subtitle == "No" {
-                    no_price = last_price;
-                }
+    /// Fetch prices for every market of an event (binary or categorical)
+    pub async fn fetch_quote(&self, event_id: &str) -> BotResult<MarketQuote> {
+        let path = format!("/trade-api/v2/events/{}", event_id);
+        let headers = self.get_auth_headers("GET", &path, "")?;
 
-                if let Some(vol) = market["volume"].as_f64() {
-                    liquidity += vol;
-                }
-            }
+        let request = self
+            .http_client
+            .get(format!("{}{}", self.base_url, path))
+            .query(&[("with_nested_markets", "true")])
+            .headers(headers);
+        let response = self
+            .send(request)
+            .await
+            .context("Failed to fetch Kalshi event quote")?;
+
+        if !response.status().is_success() {
+            return Err(BotError::from_status(response.status(), "Kalshi event fetch failed"));
         }
 
-        Ok(MarketPrices::new(yes_price, no_price, liquidity))
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse Kalshi event response")?;
+
+        // Markets may be nested in the event or returned alongside it
+        let markets = if data["event"]["markets"].is_array() {
+            &data["event"]
+        } else {
+            &data
+        };
+        Ok(parse_kalshi_quote(markets))
     }
 
     /// Place a buy order on Kalshi
@@ -492,37 +1561,72 @@ impl KalshiClient {
         outcome: String, // "YES" or "NO"
         amount: f64,
         price: f64,
-    ) -> Result<Option<String>> {
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        let count = kalshi_contract_count(validate_order(amount, price)?)?;
+        self.submit_order(event_id, "buy", outcome, count, price, time_in_force)
+            .await
+    }
+
+    /// Sell `shares` contracts at no less than `min_price` each
+    pub async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>> {
+        validate_order(shares * min_price, min_price)?;
+        let count = kalshi_contract_count(shares)?;
+
+        info!("Selling {} {} contracts of {} on Kalshi", count, outcome, event_id);
+        self.submit_order(event_id, "sell", outcome, count, min_price, TimeInForce::Ioc)
+            .await
+    }
+
+    async fn submit_order(
+        &self,
+        event_id: String,
+        side: &str,
+        outcome: String,
+        count: i64,
+        price: f64,
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
         let path = "/trade-api/v2/orders";
         
         // Kalshi order format
-        let order_data = serde_json::json!({
+        let mut order_data = serde_json::json!({
             "event_ticker": event_id,
-            "side": "buy",
-            "outcome": outcome,
-            "count": (amount / price) as i64, // Number of shares
-            "price": (price * 100) as i64,    // Kalshi uses cents
+            "side": side,
+            "outcome": normalize_outcome(&outcome),
+            "count": count,                                 // Number of shares
+            "price": (price * 100.0).round() as i64,        // Kalshi uses cents
         });
+        match time_in_force {
+            TimeInForce::Gtc => {}
+            TimeInForce::Ioc => order_data["time_in_force"] = "immediate_or_cancel".into(),
+            TimeInForce::Fok => order_data["time_in_force"] = "fill_or_kill".into(),
+            TimeInForce::Gtd(expiry) => order_data["expiration_ts"] = expiry.timestamp().into(),
+        }
 
-        let body = serde_json::to_string(&order_data)?;
+        let body = serde_json::to_string(&order_data).context("Failed to serialize Kalshi order")?;
         let headers = self.get_auth_headers("POST", path, &body)?;
 
-        let response = self
+        let request = self
             .http_client
-            .post(&format!("{}{}", self.base_url, path))
+            .post(format!("{}{}", self.base_url, path))
             .headers(headers)
-            .json(&order_data)
-            .send()
+            .json(&order_data);
+        let response = self
+            .send(request)
             .await
             .context("Failed to place Kalshi order")?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow::anyhow!(
-                "Kalshi order failed: {} - {}",
-                response.status(),
-                error_text
-            ));
+            return Err(BotError::from_status(status, format!("Kalshi order failed: {}", error_text)));
         }
 
         let data: serde_json::Value = response
@@ -537,16 +1641,94 @@ impl KalshiClient {
         Ok(order_id)
     }
 
+    /// Cancel the resting remainder of an order
+    pub async fn cancel_order(&self, order_id: &str) -> BotResult<()> {
+        let path = format!("/trade-api/v2/portfolio/orders/{}", order_id);
+        let headers = self.get_auth_headers("DELETE", &path, "")?;
+
+        let request = self
+            .http_client
+            .delete(format!("{}{}", self.base_url, path))
+            .headers(headers);
+        let response = self
+            .send(request)
+            .await
+            .context("Failed to cancel Kalshi order")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::from_status(status, format!("Kalshi cancel failed: {}", error_text)));
+        }
+
+        info!("Cancelled Kalshi order {}", order_id);
+        Ok(())
+    }
+
+    /// Order status, lowercased (e.g. "resting", "executed", "canceled")
+    pub async fn get_order_status(&self, order_id: &str) -> BotResult<String> {
+        let path = format!("/trade-api/v2/portfolio/orders/{}", order_id);
+        let headers = self.get_auth_headers("GET", &path, "")?;
+
+        let request = self
+            .http_client
+            .get(format!("{}{}", self.base_url, path))
+            .headers(headers);
+        let response = self
+            .send(request)
+            .await
+            .context("Failed to fetch Kalshi order")?;
+
+        if !response.status().is_success() {
+            return Err(BotError::from_status(response.status(), "Kalshi order lookup failed"));
+        }
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse Kalshi order")?;
+
+        Ok(data["order"]["status"].as_str().unwrap_or("unknown").to_lowercase())
+    }
+
+    /// Fills so far for an order (`GET /portfolio/fills?order_id=`)
+    pub async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>> {
+        let path = "/trade-api/v2/portfolio/fills";
+        let headers = self.get_auth_headers("GET", path, "")?;
+
+        let request = self
+            .http_client
+            .get(format!("{}{}", self.base_url, path))
+            .query(&[("order_id", order_id)])
+            .headers(headers);
+        let response = self
+            .send(request)
+            .await
+            .context("Failed to fetch Kalshi fills")?;
+
+        if !response.status().is_success() {
+            return Err(BotError::from_status(response.status(), "Kalshi fills request failed"));
+        }
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse Kalshi fills")?;
+
+        Ok(parse_kalshi_fills(&data))
+    }
+
     /// Check if an event is settled and get the outcome
-    pub async fn check_settlement(&self, event_id: &str) -> Result<Option<bool>> {
+    pub async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>> {
         let path = format!("/trade-api/v2/events/{}", event_id);
         let headers = self.get_auth_headers("GET", &path, "")?;
 
-        let response = self
+        let request = self
             .http_client
-            .get(&format!("{}{}", self.base_url, path))
-            .headers(headers)
-            .send()
+            .get(format!("{}{}", self.base_url, path))
+            .headers(headers);
+        let response = self
+            .send(request)
             .await
             .context("Failed to check Kalshi settlement")?;
 
@@ -564,7 +1746,7 @@ impl KalshiClient {
             if status == "resolved" {
                 // Get outcome
                 if let Some(outcome) = data["event"]["outcome"].as_str() {
-                    return Ok(Some(outcome == "Yes" || outcome == "YES"));
+                    return Ok(Some(resolution_from_outcome(outcome)));
                 }
             }
         }
@@ -572,24 +1754,50 @@ impl KalshiClient {
         Ok(None) // Not yet settled
     }
 
+    /// Payout per contract of `outcome` from our portfolio settlements for
+    /// an event, or `None` if it hasn't settled for us yet
+    pub async fn settlement_payout_per_share(&self, event_id: &str, outcome: &str) -> BotResult<Option<f64>> {
+        let path = "/trade-api/v2/portfolio/settlements";
+        let headers = self.get_auth_headers("GET", path, "")?;
+
+        let request = self
+            .http_client
+            .get(format!("{}{}", self.base_url, path))
+            .query(&[("ticker", event_id)])
+            .headers(headers);
+        let response = self
+            .send(request)
+            .await
+            .context("Failed to fetch Kalshi settlements")?;
+
+        if !response.status().is_success() {
+            return Err(BotError::from_status(response.status(), "Kalshi settlements fetch failed"));
+        }
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse Kalshi settlements")?;
+
+        Ok(parse_kalshi_settlement_payout(&data, event_id, outcome))
+    }
+
     /// Get account balance
-    pub async fn get_balance(&self) -> Result<f64> {
+    pub async fn get_balance(&self) -> BotResult<f64> {
         let path = "/trade-api/v2/portfolio/balance";
         let headers = self.get_auth_headers("GET", path, "")?;
 
-        let response = self
+        let request = self
             .http_client
-            .get(&format!("{}{}", self.base_url, path))
-            .headers(headers)
-            .send()
+            .get(format!("{}{}", self.base_url, path))
+            .headers(headers);
+        let response = self
+            .send(request)
             .await
             .context("Failed to fetch Kalshi balance")?;
 
         if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Kalshi balance check failed: {}",
-                response.status()
-            ));
+            return Err(BotError::from_status(response.status(), "Kalshi balance check failed"));
         }
 
         let data: serde_json::Value = response
@@ -605,3 +1813,516 @@ impl KalshiClient {
         Ok(balance)
     }
 }
+
+/// Safety cap on pages followed by `ManifoldClient::fetch_events`
+const MANIFOLD_MAX_EVENT_PAGES: usize = 5;
+
+/// Map a Manifold market into an `Event`; `None` for non-binary or resolved markets
+fn parse_manifold_event(data: &serde_json::Value) -> Option<Event> {
+    if data["outcomeType"].as_str() != Some("BINARY") || data["isResolved"].as_bool() == Some(true) {
+        return None;
+    }
+
+    let id = data["id"].as_str()?.to_string();
+    let question = data["question"].as_str().unwrap_or_default().to_string();
+    let description = data["textDescription"].as_str().unwrap_or_default().to_string();
+    let groups: Vec<String> = data["groupSlugs"]
+        .as_array()
+        .map(|slugs| slugs.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+
+    // closeTime is in milliseconds since the epoch
+    let resolution_date = data["closeTime"]
+        .as_i64()
+        .and_then(DateTime::<Utc>::from_timestamp_millis);
+
+    Some(Event {
+        platform: Platform::Manifold,
+        event_id: id,
+        title: question,
+        description,
+        resolution_date,
+        category: groups.first().cloned(),
+        tags: groups,
+    })
+}
+
+/// YES/NO prices from a market's AMM probability, with its liquidity pool size
+fn parse_manifold_prices(data: &serde_json::Value) -> Option<MarketPrices> {
+    let probability = json_f64(&data["probability"])?;
+    let liquidity = json_f64(&data["totalLiquidity"]).unwrap_or(0.0);
+    // An AMM has no spread - both sides trade at the probability (before slippage)
+    Some(MarketPrices::new(probability, 1.0 - probability, liquidity).with_yes_bid_ask(Some(probability), Some(probability)))
+}
+
+/// Resolution of a market, `None` while open. Cancelled markets refund every
+/// bet, so they're void; partial ("MKT") resolutions are errors.
+fn parse_manifold_resolution(data: &serde_json::Value) -> BotResult<Option<Resolution>> {
+    if data["isResolved"].as_bool() != Some(true) {
+        return Ok(None);
+    }
+
+    match data["resolution"].as_str().map(normalize_outcome).as_deref() {
+        Some("YES") => Ok(Some(Resolution::Yes)),
+        Some("NO") => Ok(Some(Resolution::No)),
+        Some("CANCEL") => Ok(Some(Resolution::Void)),
+        other => Err(BotError::Settlement(format!(
+            "Manifold market {} resolved {}",
+            data["id"].as_str().unwrap_or_default(),
+            other.unwrap_or("without an outcome")
+        ))),
+    }
+}
+
+/// Fills of a Manifold bet: the immediate AMM fill plus any matched limit fills
+fn parse_manifold_fills(bet: &serde_json::Value) -> Vec<Fill> {
+    bet["fills"]
+        .as_array()
+        .map(|fills| {
+            fills
+                .iter()
+                .filter_map(|fill| {
+                    let shares = json_f64(&fill["shares"])?;
+                    let amount = json_f64(&fill["amount"])?;
+                    (shares > 0.0).then(|| Fill {
+                        shares,
+                        price: amount / shares,
+                        fee: 0.0,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whole mana to bet for an amount - floored so a bet never spends more than
+/// asked (with the same float tolerance as Kalshi contracts). Manifold's
+/// minimum bet is 1 mana, so smaller amounts are rejected.
+fn manifold_bet_amount(amount: f64) -> BotResult<f64> {
+    let mana = (amount + CONTRACT_EPSILON).floor();
+    if mana < 1.0 {
+        return Err(BotError::InvalidOrder(format!(
+            "Manifold bet of {:.4} mana is below the 1 mana minimum",
+            amount
+        )));
+    }
+    Ok(mana)
+}
+
+/// Order status of a Manifold bet, in the lowercase form the executor expects
+fn manifold_bet_status(bet: &serde_json::Value) -> String {
+    if bet["isCancelled"].as_bool() == Some(true) {
+        "canceled".to_string()
+    } else if bet["isFilled"].as_bool() == Some(false) {
+        "open".to_string()
+    } else {
+        "filled".to_string()
+    }
+}
+
+/// Client for Manifold Markets. Balances, bets and payouts are in mana
+/// (Manifold's play-money currency), not USD.
+#[derive(Clone)]
+pub struct ManifoldClient {
+    http_client: Client,
+    api_key: String,
+    base_url: String,
+    retry_policy: RetryPolicy,
+    event_cache: EventCache,
+    request_limit: Option<Arc<Semaphore>>, // Max in-flight requests, shared across clones
+}
+
+impl ManifoldClient {
+    pub fn new(api_key: String) -> Self {
+        let http_client = http_client_builder()
+            .build()
+            .unwrap_or_else(|_| Client::new());
+
+        Self {
+            http_client,
+            api_key,
+            base_url: "https://api.manifold.markets/v0".to_string(),
+            retry_policy: RetryPolicy::default(),
+            event_cache: EventCache::new(DEFAULT_EVENT_CACHE_TTL),
+            request_limit: None,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Serve `fetch_events` from memory for `ttl` between refreshes
+    pub fn with_event_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.event_cache = EventCache::new(ttl);
+        self
+    }
+
+    /// Cap in-flight requests with a semaphore, which may be shared with other clients
+    pub fn with_request_limit(mut self, request_limit: Arc<Semaphore>) -> Self {
+        self.request_limit = Some(request_limit);
+        self
+    }
+
+    /// Route all requests through an HTTP(S) or SOCKS5 proxy. Without one, the
+    /// `HTTP_PROXY`/`HTTPS_PROXY` environment variables are honoured as usual.
+    pub fn with_proxy(mut self, proxy_url: String) -> Result<Self> {
+        self.http_client = proxied_http_client(&proxy_url)?;
+        Ok(self)
+    }
+
+    /// Send through the retry policy and request limit
+    async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        send_with_retry(&self.retry_policy, None, self.request_limit.as_deref(), request).await
+    }
+
+    /// GET `path` (relative to the API root) as JSON, with the API key attached
+    async fn get_json(&self, path: &str, query: &[(&str, &str)], what: &str) -> BotResult<serde_json::Value> {
+        let request = self
+            .http_client
+            .get(format!("{}{}", self.base_url, path))
+            .header("Authorization", format!("Key {}", self.api_key))
+            .query(query);
+        let response = self
+            .send(request)
+            .await
+            .with_context(|| format!("Failed to fetch Manifold {}", what))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::from_status(status, error_text));
+        }
+
+        Ok(response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse Manifold {}", what))?)
+    }
+
+    /// POST a JSON body to `path`, returning the JSON response
+    async fn post_json(&self, path: &str, body: serde_json::Value, what: &str) -> BotResult<serde_json::Value> {
+        let request = self
+            .http_client
+            .post(format!("{}{}", self.base_url, path))
+            .header("Authorization", format!("Key {}", self.api_key))
+            .json(&body);
+        let response = self
+            .send(request)
+            .await
+            .with_context(|| format!("Failed to send Manifold {}", what))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::from_status(status, error_text));
+        }
+
+        Ok(response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse Manifold {}", what))?)
+    }
+
+    /// Fetch active events, from the cache if it hasn't expired
+    pub async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        match self.event_cache.get().await {
+            Some(events) => Ok(events),
+            None => self.force_refresh().await,
+        }
+    }
+
+    /// Fetch events from the API regardless of the cache, then cache them
+    pub async fn force_refresh(&self) -> BotResult<Vec<Event>> {
+        let events = dedupe_events(self.fetch_events_uncached().await?);
+        self.event_cache.store(events.clone()).await;
+        Ok(events)
+    }
+
+    /// Fetch open binary markets, paging backwards with `before`
+    async fn fetch_events_uncached(&self) -> BotResult<Vec<Event>> {
+        let mut events = Vec::new();
+        let mut before: Option<String> = None;
+
+        for _ in 0..MANIFOLD_MAX_EVENT_PAGES {
+            let mut query = vec![("limit", "1000")];
+            if let Some(before) = before.as_deref() {
+                query.push(("before", before));
+            }
+
+            let data = self.get_json("/markets", &query, "markets").await?;
+            let Some(markets) = data.as_array().filter(|m| !m.is_empty()) else {
+                break;
+            };
+
+            events.extend(markets.iter().filter_map(parse_manifold_event));
+            before = markets.last().and_then(|m| m["id"].as_str()).map(String::from);
+        }
+
+        Ok(events)
+    }
+
+    /// Current prices from the market's probability
+    pub async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        let data = self.get_json(&format!("/market/{}", event_id), &[], "market").await?;
+        Ok(parse_manifold_prices(&data)
+            .with_context(|| format!("Manifold market {} has no probability", event_id))?)
+    }
+
+    /// Place a limit bet of `amount` mana on `outcome`, stopping once the
+    /// price reaches `price`. IOC and FOK bets have their unfilled remainder
+    /// cancelled (Manifold has no fill-or-kill, so FOK behaves like IOC).
+    pub async fn place_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        amount: f64,
+        price: f64,
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        validate_order(amount, price)?;
+        let amount = manifold_bet_amount(amount)?;
+        let outcome = normalize_outcome(&outcome);
+
+        // limitProb is the market probability to stop at, in whole percents
+        let limit_prob = if outcome == "NO" { 1.0 - price } else { price };
+        let limit_prob = (limit_prob * 100.0).round().clamp(1.0, 99.0) / 100.0;
+
+        let mut body = serde_json::json!({
+            "contractId": event_id,
+            "outcome": outcome,
+            "amount": amount,
+            "limitProb": limit_prob,
+        });
+        if let Some(expires_at) = time_in_force.expiration_ts() {
+            body["expiresAt"] = serde_json::json!(expires_at * 1000);
+        }
+
+        let bet = self.post_json("/bet", body, "bet").await?;
+        let bet_id = bet["betId"].as_str().map(String::from);
+
+        if let (Some(id), TimeInForce::Ioc | TimeInForce::Fok) = (&bet_id, &time_in_force) {
+            if bet["isFilled"].as_bool() == Some(false) {
+                self.cancel_order(id).await?;
+            }
+        }
+
+        info!("✅ Manifold bet placed: {:?}", bet_id);
+        Ok(bet_id)
+    }
+
+    /// Sell `shares` of `outcome` back to the AMM. Manifold sells at market
+    /// with no limit, so the sale is refused (`InvalidPrice`) if the outcome
+    /// already trades below `min_price`; slippage on the sale itself isn't capped.
+    pub async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>> {
+        let outcome = normalize_outcome(&outcome);
+        let prices = self.fetch_prices(&event_id).await?;
+        let current = if outcome == "NO" { prices.no } else { prices.yes };
+        if current < min_price {
+            return Err(BotError::InvalidPrice(format!(
+                "Manifold {} {} trades at {:.4}, below the {:.4} minimum",
+                event_id, outcome, current, min_price
+            )));
+        }
+
+        let body = serde_json::json!({
+            "outcome": outcome,
+            "shares": shares,
+        });
+        let bet = self
+            .post_json(&format!("/market/{}/sell", event_id), body, "sell")
+            .await?;
+        Ok(bet["betId"].as_str().map(String::from))
+    }
+
+    /// Cancel the unfilled part of a limit bet
+    pub async fn cancel_order(&self, order_id: &str) -> BotResult<()> {
+        self.post_json(&format!("/bet/cancel/{}", order_id), serde_json::json!({}), "cancel")
+            .await?;
+        Ok(())
+    }
+
+    /// Look up one of our own bets (Manifold lists bets per user, not by ID)
+    async fn fetch_own_bet(&self, bet_id: &str) -> BotResult<serde_json::Value> {
+        let me = self.get_json("/me", &[], "user").await?;
+        let user_id = me["id"].as_str().context("Manifold user has no ID")?;
+
+        let bets = self
+            .get_json("/bets", &[("userId", user_id), ("limit", "1000")], "bets")
+            .await?;
+        Ok(bets
+            .as_array()
+            .and_then(|bets| bets.iter().find(|b| b["id"].as_str() == Some(bet_id)))
+            .cloned()
+            .with_context(|| format!("Manifold bet {} not found", bet_id))?)
+    }
+
+    pub async fn get_order_status(&self, order_id: &str) -> BotResult<String> {
+        Ok(manifold_bet_status(&self.fetch_own_bet(order_id).await?))
+    }
+
+    pub async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>> {
+        Ok(parse_manifold_fills(&self.fetch_own_bet(order_id).await?))
+    }
+
+    /// Check if a market has resolved - `Some(true)` if it resolved YES
+    pub async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>> {
+        let data = self.get_json(&format!("/market/{}", event_id), &[], "market").await?;
+        parse_manifold_resolution(&data)
+    }
+
+    /// Mana balance of the API key's account
+    pub async fn get_balance(&self) -> BotResult<f64> {
+        let me = self.get_json("/me", &[], "user").await?;
+        Ok(json_f64(&me["balance"]).unwrap_or(0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contract_count_floors_without_losing_float_error() {
+        assert_eq!(kalshi_contract_count(0.3 / 0.1).unwrap(), 3);
+        assert_eq!(kalshi_contract_count(1.98).unwrap(), 1);
+        assert!(matches!(kalshi_contract_count(0.6), Err(BotError::InvalidOrder(_))));
+    }
+
+    #[test]
+    fn settlement_payout_ignores_other_markets() {
+        let data = serde_json::json!({
+            "settlements": [
+                {"ticker": "KXBTC-T70000", "market_result": "yes", "yes_count": 10, "no_count": 0, "revenue": 980},
+                {"ticker": "KXBTC-T75000", "market_result": "no", "yes_count": 5, "no_count": 0, "revenue": 0},
+            ]
+        });
+
+        let payout = parse_kalshi_settlement_payout(&data, "KXBTC-T70000", "YES").unwrap();
+        assert!((payout - 0.98).abs() < 1e-9);
+        assert_eq!(parse_kalshi_settlement_payout(&data, "KXBTC-T75000", "YES"), Some(0.0));
+        assert_eq!(parse_kalshi_settlement_payout(&data, "KXETH-T4000", "YES"), None);
+    }
+
+    #[test]
+    fn categorical_quotes_have_one_outcome_per_market() {
+        let polymarket = serde_json::json!({
+            "markets": [
+                {"groupItemTitle": "Alice", "outcomePrices": "[\"0.5\", \"0.5\"]", "liquidity": "100"},
+                {"groupItemTitle": "Bob", "outcomePrices": "[\"0.3\", \"0.7\"]", "liquidity": "50"},
+                {"groupItemTitle": "Carol", "outcomePrices": "[\"0.15\", \"0.85\"]", "liquidity": 25},
+            ]
+        });
+        let kalshi = serde_json::json!({
+            "markets": [
+                {"yes_sub_title": "Alice", "yes_ask": 52, "liquidity": 10000},
+                {"yes_sub_title": "Bob", "last_price": 31, "liquidity": 5000},
+                {"yes_sub_title": "Carol", "yes_ask": 14},
+            ]
+        });
+
+        for (quote, liquidity) in [(parse_polymarket_quote(&polymarket), 175.0), (parse_kalshi_quote(&kalshi), 150.0)] {
+            let names: Vec<&str> = quote.outcomes.iter().map(|o| o.name.as_str()).collect();
+            assert_eq!(names, ["Alice", "Bob", "Carol"]);
+            assert!(!quote.is_binary());
+            assert!(quote.binary_prices().is_none());
+            assert!((quote.liquidity - liquidity).abs() < 1e-9);
+        }
+        let kalshi = parse_kalshi_quote(&kalshi);
+        let prices: Vec<f64> = kalshi.outcomes.iter().map(|o| o.price).collect();
+        assert_eq!(prices, [0.52, 0.31, 0.14]);
+    }
+
+    #[test]
+    fn book_quotes_derive_prices_with_bid_and_ask() {
+        let book = OrderBook::new(vec![(0.42, 100.0)], vec![(0.44, 100.0)]);
+
+        let quote = book.to_market_quote(DEFAULT_DEPTH_RANGE);
+        assert_eq!(quote.outcome("YES").unwrap().price, 0.42);
+        assert!((quote.outcome("NO").unwrap().price - 0.56).abs() < 1e-9);
+
+        let prices = quote.binary_prices().unwrap();
+        assert_eq!((prices.yes_bid, prices.yes_ask), (Some(0.42), Some(0.44)));
+        assert!(prices.book.is_some());
+    }
+
+    #[test]
+    fn manifold_binary_markets_become_events() {
+        let market = serde_json::json!({
+            "id": "mf-btc",
+            "question": "Will BTC be above $70,000?",
+            "textDescription": "Resolves at close",
+            "outcomeType": "BINARY",
+            "isResolved": false,
+            "closeTime": 1_717_329_600_000i64,
+            "groupSlugs": ["crypto", "bitcoin"],
+        });
+
+        let event = parse_manifold_event(&market).unwrap();
+        assert_eq!(event.platform, Platform::Manifold);
+        assert_eq!(event.event_id, "mf-btc");
+        assert_eq!(event.category.as_deref(), Some("crypto"));
+        assert_eq!(event.tags, ["crypto", "bitcoin"]);
+        assert_eq!(event.resolution_date.unwrap().to_rfc3339(), "2024-06-02T12:00:00+00:00");
+
+        let mut multi = market.clone();
+        multi["outcomeType"] = "MULTIPLE_CHOICE".into();
+        assert!(parse_manifold_event(&multi).is_none());
+        let mut resolved = market;
+        resolved["isResolved"] = true.into();
+        assert!(parse_manifold_event(&resolved).is_none());
+    }
+
+    #[test]
+    fn manifold_prices_and_resolutions() {
+        let prices = parse_manifold_prices(&serde_json::json!({"probability": 0.62, "totalLiquidity": 500})).unwrap();
+        assert_eq!((prices.yes, prices.liquidity), (0.62, 500.0));
+        assert!((prices.no - 0.38).abs() < 1e-9);
+        assert!(parse_manifold_prices(&serde_json::json!({})).is_none());
+
+        let resolution = |resolved: bool, resolution: &str| {
+            parse_manifold_resolution(&serde_json::json!({"id": "mf", "isResolved": resolved, "resolution": resolution}))
+        };
+        assert_eq!(resolution(false, "YES").unwrap(), None);
+        assert_eq!(resolution(true, "YES").unwrap(), Some(Resolution::Yes));
+        assert_eq!(resolution(true, "NO").unwrap(), Some(Resolution::No));
+        assert_eq!(resolution(true, "CANCEL").unwrap(), Some(Resolution::Void));
+        assert!(matches!(resolution(true, "MKT"), Err(BotError::Settlement(_))));
+    }
+
+    #[test]
+    fn manifold_bets_report_fills_and_status() {
+        let bet = serde_json::json!({
+            "isFilled": false,
+            "fills": [{"shares": 20, "amount": 10}, {"shares": 0, "amount": 0}],
+        });
+
+        let fills = parse_manifold_fills(&bet);
+        assert_eq!(fills.len(), 1);
+        assert_eq!((fills[0].shares, fills[0].price), (20.0, 0.5));
+        assert_eq!(manifold_bet_status(&bet), "open");
+        assert_eq!(manifold_bet_status(&serde_json::json!({"isFilled": true})), "filled");
+        assert_eq!(manifold_bet_status(&serde_json::json!({"isCancelled": true})), "canceled");
+    }
+
+    #[test]
+    fn manifold_bets_are_whole_mana() {
+        assert_eq!(manifold_bet_amount(10.9).unwrap(), 10.0);
+        assert_eq!(manifold_bet_amount(0.3 / 0.1).unwrap(), 3.0);
+        assert!(matches!(manifold_bet_amount(0.4), Err(BotError::InvalidOrder(_))));
+        assert!(matches!(manifold_bet_amount(0.9), Err(BotError::InvalidOrder(_))));
+    }
+}
+
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Duration;
+use tokio::time::{self, Instant};
+
+/// Source of the current time, so time-dependent logic can be tested
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Always returns the same instant
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Like `tokio::time::interval`, but each wait is randomized within
+/// ±`jitter` (a fraction of `period`) so restarts and multiple bots don't hit
+/// the APIs in lockstep. The first tick completes immediately.
+#[derive(Debug)]
+pub struct JitteredInterval {
+    period: Duration,
+    jitter: f64,
+    rng: StdRng,
+    deadline: Instant,
+}
+
+impl JitteredInterval {
+    pub fn new(period: Duration, jitter: f64) -> Self {
+        Self {
+            period,
+            jitter: jitter.clamp(0.0, 1.0),
+            rng: StdRng::from_entropy(),
+            deadline: Instant::now(),
+        }
+    }
+
+    /// Use a fixed RNG seed, for reproducible delays
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Draw the next wait: `period` scaled by a random factor in [1 - jitter, 1 + jitter]
+    pub fn next_delay(&mut self) -> Duration {
+        let factor = 1.0 + self.rng.gen_range(-self.jitter..=self.jitter);
+        self.period.mul_f64(factor)
+    }
+
+    /// Wait for the next tick. Cancel-safe: the deadline only advances once a
+    /// tick completes, so dropping this future (e.g. in `select!`) loses nothing.
+    pub async fn tick(&mut self) {
+        time::sleep_until(self.deadline).await;
+        self.deadline = Instant::now() + self.next_delay();
+    }
+}
@@ -0,0 +1,406 @@
+// Bot configuration loaded from config.toml, with env var and default fallbacks
+
+use crate::approval::ApprovalPolicy;
+use crate::arbitrage_detector::{FeeKind, Fees};
+use crate::bot::MarketFilters;
+use crate::circuit_breaker::{self, CircuitBreaker};
+use crate::event_matcher::{default_aliases, EventMatcher};
+use crate::price_oracle::{CoingeckoOracle, MaticPriceSource, PriceOracle, StaticOracle};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Settings read from `config.toml`. Fields missing from the file fall back
+/// to their env var (see `from_env`), then to the built-in default.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default = "Config::from_env", deny_unknown_fields)]
+pub struct Config {
+    // Market filters
+    pub categories: Vec<String>,
+    pub max_hours_until_resolution: i64,
+    pub min_liquidity: f64,
+    pub min_liquidity_polymarket: Option<f64>, // Overrides min_liquidity for Polymarket
+    pub min_liquidity_kalshi: Option<f64>,     // Overrides min_liquidity for Kalshi
+    pub min_liquidity_manifold: Option<f64>,   // Overrides min_liquidity for Manifold
+
+    // Trade sizing
+    pub max_position_size: f64,  // Max USDC per leg
+    pub max_total_exposure: f64, // Max USDC across open positions
+    pub trade_cooldown_secs: u64,
+    pub max_open_age_hours: Option<i64>, // Flag positions still open after this as stale
+    pub max_price_age_secs: u64, // Skip quotes older than this
+    pub price_sum_tolerance: f64, // Skip quotes whose YES + NO is further than this from 1.00
+    pub circuit_breaker_threshold: u32, // Consecutive failed trades that pause trading
+    pub circuit_breaker_cooldown_secs: u64, // How long trading stays paused
+
+    // Thresholds (fractions in [0, 1])
+    pub similarity_threshold: f64,
+    pub min_profit_threshold: f64,
+    pub min_roi_percent: f64, // Net profit / cost, in percent (not a fraction)
+    pub approval_policy: ApprovalPolicy, // Queue medium-confidence matches for a human
+
+    // Extra matcher aliases (word -> canonical token), added to the built-in map
+    pub aliases: HashMap<String, String>,
+
+    // Fees
+    pub polymarket_fee: f64,
+    pub kalshi_fee: f64,
+    pub kalshi_fee_model: FeeKind,
+
+    // Intervals
+    pub scan_interval_secs: u64,
+    pub settlement_interval_secs: u64,
+    pub event_cache_ttl_secs: u64, // Reuse fetched event lists for this long
+    pub interval_jitter: f64,      // Randomize scan/settlement waits by ± this fraction
+    pub max_concurrent_requests: usize, // HTTP requests in flight across both venues
+    pub proxy_url: Option<String>, // http(s):// or socks5:// proxy for all venue API requests
+
+    // Polygon
+    pub polygon_rpc_url: String,
+    pub polygon_fallback_rpc_urls: Vec<String>, // Tried in order if the primary fails
+    pub matic_usd_price: f64,
+    pub matic_price_source: MaticPriceSource, // "static" (matic_usd_price) or "coingecko"
+    pub min_matic_balance: f64, // Don't trade with less than this for gas
+    pub min_balance_alert: Option<f64>, // Alert when a venue's USD balance drops below this
+    pub rebalance_target_ratio: Option<f64>, // Suggest transfers to keep this share of capital on Polymarket
+
+    pub positions_file: String,
+    pub opportunity_log_file: Option<String>, // JSONL record of detected opportunities
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let fees = Fees::default();
+        Self {
+            categories: vec!["crypto".to_string(), "sports".to_string()],
+            max_hours_until_resolution: 24,
+            min_liquidity: 100.0,
+            min_liquidity_polymarket: None,
+            min_liquidity_kalshi: None,
+            min_liquidity_manifold: None,
+            max_position_size: 100.0,
+            max_total_exposure: 1000.0,
+            trade_cooldown_secs: 600,
+            max_open_age_hours: None,
+            max_price_age_secs: 30,
+            price_sum_tolerance: 0.1,
+            circuit_breaker_threshold: circuit_breaker::DEFAULT_FAILURE_THRESHOLD,
+            circuit_breaker_cooldown_secs: circuit_breaker::DEFAULT_COOLDOWN.as_secs(),
+            similarity_threshold: 0.80,
+            min_profit_threshold: 0.02,
+            min_roi_percent: 0.0,
+            approval_policy: ApprovalPolicy::default(),
+            aliases: HashMap::new(),
+            polymarket_fee: fees.polymarket,
+            kalshi_fee: fees.kalshi,
+            kalshi_fee_model: fees.kalshi_model,
+            scan_interval_secs: 60,
+            settlement_interval_secs: 300,
+            event_cache_ttl_secs: 300,
+            interval_jitter: 0.1,
+            max_concurrent_requests: 16,
+            proxy_url: None,
+            polygon_rpc_url: "https://polygon-rpc.com".to_string(),
+            polygon_fallback_rpc_urls: Vec::new(),
+            matic_usd_price: 0.50,
+            matic_price_source: MaticPriceSource::default(),
+            min_matic_balance: 0.1,
+            min_balance_alert: None,
+            rebalance_target_ratio: None,
+            positions_file: "positions.json".to_string(),
+            opportunity_log_file: None,
+        }
+    }
+}
+
+impl Config {
+    /// Defaults overridden by any of `POLYGON_RPC_URL`, `POLYGON_FALLBACK_RPC_URLS`
+    /// (comma-separated), `MATIC_USD_PRICE`, `HTTPS_PROXY`,
+    /// `POSITIONS_FILE`, `OPPORTUNITY_LOG_FILE` and the fee variables read by `Fees::from_env`
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let fees = Fees::from_env();
+
+        Self {
+            polygon_rpc_url: std::env::var("POLYGON_RPC_URL").unwrap_or(defaults.polygon_rpc_url),
+            polygon_fallback_rpc_urls: std::env::var("POLYGON_FALLBACK_RPC_URLS")
+                .map(|urls| {
+                    urls.split(',')
+                        .map(|url| url.trim().to_string())
+                        .filter(|url| !url.is_empty())
+                        .collect()
+                })
+                .unwrap_or(defaults.polygon_fallback_rpc_urls),
+            matic_usd_price: std::env::var("MATIC_USD_PRICE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.matic_usd_price),
+            proxy_url: std::env::var("HTTPS_PROXY").ok().filter(|url| !url.is_empty()),
+            positions_file: std::env::var("POSITIONS_FILE").unwrap_or(defaults.positions_file),
+            opportunity_log_file: std::env::var("OPPORTUNITY_LOG_FILE").ok(),
+            polymarket_fee: fees.polymarket,
+            kalshi_fee: fees.kalshi,
+            kalshi_fee_model: fees.kalshi_model,
+            ..defaults
+        }
+    }
+
+    /// Parse and validate a TOML config string
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let config: Self = toml::from_str(contents).context("Invalid config TOML")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load `path` if it exists, otherwise use env vars and defaults
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            info!("No config file at {}, using environment and defaults", path.display());
+            let config = Self::from_env();
+            config.validate()?;
+            return Ok(config);
+        }
+
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        let config = Self::from_toml(&contents)
+            .with_context(|| format!("Bad config in {}", path.display()))?;
+
+        info!("⚙️ Loaded config from {}", path.display());
+        Ok(config)
+    }
+
+    /// Reject values that would make the bot misbehave
+    #[allow(clippy::neg_cmp_op_on_partial_ord)] // Negated comparisons reject NaN too
+    pub fn validate(&self) -> Result<()> {
+        let fractions = [
+            ("similarity_threshold", self.similarity_threshold),
+            ("min_profit_threshold", self.min_profit_threshold),
+            ("polymarket_fee", self.polymarket_fee),
+            ("kalshi_fee", self.kalshi_fee),
+        ];
+        for (name, value) in fractions {
+            if !(0.0..=1.0).contains(&value) {
+                bail!("{} must be between 0 and 1, got {}", name, value);
+            }
+        }
+
+        let positives = [
+            ("max_position_size", self.max_position_size),
+            ("max_total_exposure", self.max_total_exposure),
+            ("matic_usd_price", self.matic_usd_price),
+            ("price_sum_tolerance", self.price_sum_tolerance),
+        ];
+        for (name, value) in positives {
+            if !(value > 0.0) {
+                bail!("{} must be positive, got {}", name, value);
+            }
+        }
+
+        if !(self.min_matic_balance >= 0.0) {
+            bail!("min_matic_balance must not be negative, got {}", self.min_matic_balance);
+        }
+        if !(self.min_roi_percent >= 0.0) {
+            bail!("min_roi_percent must not be negative, got {}", self.min_roi_percent);
+        }
+        if let Some(threshold) = self.min_balance_alert {
+            if !(threshold >= 0.0) {
+                bail!("min_balance_alert must not be negative, got {}", threshold);
+            }
+        }
+        if let Some(ratio) = self.rebalance_target_ratio {
+            if !(0.0..=1.0).contains(&ratio) {
+                bail!("rebalance_target_ratio must be between 0 and 1, got {}", ratio);
+            }
+        }
+        let liquidity_floors = [
+            ("min_liquidity", Some(self.min_liquidity)),
+            ("min_liquidity_polymarket", self.min_liquidity_polymarket),
+            ("min_liquidity_kalshi", self.min_liquidity_kalshi),
+            ("min_liquidity_manifold", self.min_liquidity_manifold),
+        ];
+        for (name, value) in liquidity_floors {
+            if let Some(value) = value {
+                if !(value >= 0.0) {
+                    bail!("{} must not be negative, got {}", name, value);
+                }
+            }
+        }
+        if self.max_hours_until_resolution <= 0 {
+            bail!(
+                "max_hours_until_resolution must be positive, got {}",
+                self.max_hours_until_resolution
+            );
+        }
+        if self.max_concurrent_requests == 0 {
+            bail!("max_concurrent_requests must be positive");
+        }
+        if self.scan_interval_secs == 0 {
+            bail!("scan_interval_secs must be positive");
+        }
+        // Queued matches can only be approved through the API
+        #[cfg(not(feature = "api"))]
+        if self.approval_policy == ApprovalPolicy::ApproveMediumConfidence {
+            bail!("approval_policy ApproveMediumConfidence needs the \"api\" feature to approve matches");
+        }
+        if let Some(hours) = self.max_open_age_hours {
+            if hours <= 0 {
+                bail!("max_open_age_hours must be positive, got {}", hours);
+            }
+        }
+        if self.max_price_age_secs == 0 {
+            bail!("max_price_age_secs must be positive");
+        }
+        if self.circuit_breaker_threshold == 0 {
+            bail!("circuit_breaker_threshold must be positive");
+        }
+        if !(0.0..1.0).contains(&self.interval_jitter) {
+            bail!("interval_jitter must be in [0, 1), got {}", self.interval_jitter);
+        }
+        if self.settlement_interval_secs == 0 {
+            bail!("settlement_interval_secs must be positive");
+        }
+        if self.max_total_exposure < self.max_position_size * 2.0 {
+            bail!(
+                "max_total_exposure ({}) must cover both legs of one max-size trade ({})",
+                self.max_total_exposure,
+                self.max_position_size * 2.0
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn filters(&self) -> MarketFilters {
+        MarketFilters {
+            categories: self.categories.clone(),
+            max_hours_until_resolution: self.max_hours_until_resolution,
+            min_liquidity_polymarket: self.min_liquidity_polymarket.unwrap_or(self.min_liquidity),
+            min_liquidity_kalshi: self.min_liquidity_kalshi.unwrap_or(self.min_liquidity),
+            min_liquidity_manifold: self.min_liquidity_manifold.unwrap_or(self.min_liquidity),
+            max_position_size: self.max_position_size,
+            max_total_exposure: self.max_total_exposure,
+        }
+    }
+
+    /// Matcher using the configured threshold, with `aliases` layered over the defaults
+    pub fn event_matcher(&self) -> EventMatcher {
+        let mut aliases = default_aliases();
+        aliases.extend(self.aliases.clone());
+        EventMatcher::new(self.similarity_threshold).with_aliases(aliases)
+    }
+
+    /// MATIC/USD source for gas estimates, per `matic_price_source`
+    pub fn price_oracle(&self) -> Arc<dyn PriceOracle> {
+        match self.matic_price_source {
+            MaticPriceSource::Static => Arc::new(StaticOracle(self.matic_usd_price)),
+            MaticPriceSource::Coingecko => Arc::new(CoingeckoOracle::new()),
+        }
+    }
+
+    pub fn fees(&self) -> Fees {
+        Fees {
+            polymarket: self.polymarket_fee,
+            kalshi: self.kalshi_fee,
+            kalshi_model: self.kalshi_fee_model.clone(),
+        }
+    }
+
+    pub fn scan_interval(&self) -> Duration {
+        Duration::from_secs(self.scan_interval_secs)
+    }
+
+    pub fn settlement_interval(&self) -> Duration {
+        Duration::from_secs(self.settlement_interval_secs)
+    }
+
+    pub fn event_cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.event_cache_ttl_secs)
+    }
+
+    pub fn trade_cooldown(&self) -> Duration {
+        Duration::from_secs(self.trade_cooldown_secs)
+    }
+
+    pub fn max_price_age(&self) -> Duration {
+        Duration::from_secs(self.max_price_age_secs)
+    }
+
+    /// Breaker pausing trades after `circuit_breaker_threshold` failures in a row
+    pub fn circuit_breaker(&self) -> CircuitBreaker {
+        CircuitBreaker::new(
+            self.circuit_breaker_threshold,
+            Duration::from_secs(self.circuit_breaker_cooldown_secs),
+        )
+    }
+
+    pub fn max_open_age(&self) -> Option<chrono::Duration> {
+        self.max_open_age_hours.map(chrono::Duration::hours)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_config_loads() {
+        let config = Config::from_toml(include_str!("../config.example.toml")).unwrap();
+        assert_eq!(config.circuit_breaker_threshold, 5);
+        assert_eq!(config.categories, ["crypto", "sports"]);
+    }
+
+    #[test]
+    fn load_reads_the_file_and_falls_back_without_one() {
+        let path = std::env::temp_dir().join(format!("config-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "max_position_size = 25.0\nmax_total_exposure = 200.0\n").unwrap();
+        let config = Config::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.max_position_size, 25.0);
+        assert_eq!(config.max_total_exposure, 200.0);
+        // Everything else keeps its default
+        assert_eq!(config.scan_interval_secs, Config::default().scan_interval_secs);
+
+        let missing = Config::load(&path).unwrap();
+        assert_eq!(missing.max_position_size, Config::default().max_position_size);
+    }
+
+    #[test]
+    fn env_vars_fill_fields_missing_from_the_file() {
+        // The only test touching these vars, so parallel tests don't race on them
+        std::env::set_var("POSITIONS_FILE", "from-env.json");
+        std::env::set_var("MATIC_USD_PRICE", "0.75");
+        let config = Config::from_toml("matic_usd_price = 0.60\n");
+        std::env::remove_var("POSITIONS_FILE");
+        std::env::remove_var("MATIC_USD_PRICE");
+
+        let config = config.unwrap();
+        assert_eq!(config.positions_file, "from-env.json");
+        // The file wins over the env var
+        assert_eq!(config.matic_usd_price, 0.60);
+    }
+
+    #[test]
+    fn validation_names_the_bad_setting() {
+        let error = |toml: &str| format!("{:#}", Config::from_toml(toml).unwrap_err());
+
+        assert!(error("similarity_threshold = 1.5").contains("similarity_threshold"));
+        assert!(error("max_position_size = 0.0").contains("max_position_size"));
+        assert!(error("circuit_breaker_threshold = 0").contains("circuit_breaker_threshold"));
+        assert!(error("interval_jitter = 1.0").contains("interval_jitter"));
+        assert!(error("max_position_size = 600.0").contains("must cover both legs"));
+        assert!(error("min_liquidity_kalshi = -1.0").contains("min_liquidity_kalshi"));
+    }
+
+    #[test]
+    fn unknown_settings_are_rejected() {
+        let error = format!("{:#}", Config::from_toml("max_positon_size = 10.0").unwrap_err());
+        assert!(error.contains("max_positon_size"), "{}", error);
+    }
+}
@@ -0,0 +1,76 @@
+// Typed errors for failures callers may want to handle differently.
+// Venue clients, the `Exchange` trait and the executors return `BotResult`;
+// `anyhow` is kept for internal plumbing and the binary's top level.
+
+use reqwest::StatusCode;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BotError {
+    /// Credentials rejected (HTTP 401/403) or missing
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    /// Transport failure talking to a venue
+    #[error("Network error: {0}")]
+    Network(#[from] reqwest::Error),
+
+    /// Non-success response that isn't an auth failure
+    #[error("API error {status}: {message}")]
+    Api { status: u16, message: String },
+
+    #[error("Insufficient balance: {0}")]
+    InsufficientBalance(String),
+
+    #[error("Settlement error: {0}")]
+    Settlement(String),
+
+    /// A transaction reverted or the chain returned something unusable
+    #[error("Blockchain error: {0}")]
+    Blockchain(String),
+
+    #[error("Invalid price: {0}")]
+    InvalidPrice(String),
+
+    /// Order size that can't be sent (non-positive, or rounds to zero)
+    #[error("Invalid order: {0}")]
+    InvalidOrder(String),
+
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+/// Recover a `BotError` that was carried inside an `anyhow::Error` (e.g. from
+/// a helper that still returns `anyhow::Result`) instead of wrapping it in `Other`
+impl From<anyhow::Error> for BotError {
+    fn from(err: anyhow::Error) -> Self {
+        err.downcast::<BotError>().unwrap_or_else(BotError::Other)
+    }
+}
+
+impl BotError {
+    /// Classify a failed HTTP response: 401/403 are auth failures
+    pub fn from_status(status: StatusCode, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                BotError::Auth(format!("{} - {}", status, message))
+            }
+            _ => BotError::Api {
+                status: status.as_u16(),
+                message,
+            },
+        }
+    }
+
+    /// Whether retrying later could succeed (as opposed to a config or logic problem)
+    pub fn is_transient(&self) -> bool {
+        match self {
+            BotError::Network(_) => true,
+            BotError::Api { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+pub type BotResult<T> = std::result::Result<T, BotError>;
@@ -1,9 +1,61 @@
-use chrono::{DateTime, Utc};
+use crate::order_book::OrderBook;
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
+/// Trading venue. Serializes as the lowercase name ("polymarket"/"kalshi"/"manifold").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Polymarket,
+    Kalshi,
+    Manifold,
+}
+
+impl Platform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Platform::Polymarket => "polymarket",
+            Platform::Kalshi => "kalshi",
+            Platform::Manifold => "manifold",
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Platform {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "polymarket" => Ok(Platform::Polymarket),
+            "kalshi" => Ok(Platform::Kalshi),
+            "manifold" => Ok(Platform::Manifold),
+            _ => Err(anyhow::anyhow!("Unknown platform: {}", s)),
+        }
+    }
+}
+
+/// Canonical form of a binary outcome ("yes", " Yes" -> "YES"), so outcomes
+/// from detectors, venues and saved positions compare equal
+pub fn normalize_outcome(outcome: &str) -> String {
+    outcome.trim().to_uppercase()
+}
+
+/// A market on one venue. Equality and hashing are identity-based: two
+/// `Event`s are equal when they share `platform` and `event_id`, whatever
+/// their titles, descriptions or dates (which venues may edit).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
-    pub platform: String,
+    pub platform: Platform,
     pub event_id: String,
     pub title: String,
     pub description: String,
@@ -12,9 +64,24 @@ pub struct Event {
     pub tags: Vec<String>,
 }
 
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.platform == other.platform && self.event_id == other.event_id
+    }
+}
+
+impl Eq for Event {}
+
+impl Hash for Event {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.platform.hash(state);
+        self.event_id.hash(state);
+    }
+}
+
 impl Event {
     pub fn new(
-        platform: String,
+        platform: Platform,
         event_id: String,
         title: String,
         description: String,
@@ -46,25 +113,274 @@ impl Event {
     }
 }
 
+/// Lowercased title with punctuation dropped and whitespace collapsed
+fn normalized_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Drop repeated events within a venue: exact `event_id` duplicates, and
+/// entries sharing a normalized title and resolution date. The first
+/// occurrence is kept and order is preserved.
+pub fn dedupe_events(events: Vec<Event>) -> Vec<Event> {
+    let mut seen_ids = HashSet::new();
+    let mut seen_titles = HashSet::new();
+
+    events
+        .into_iter()
+        .filter(|event| {
+            let id_key = (event.platform, event.event_id.clone());
+            let title_key = (
+                event.platform,
+                normalized_title(&event.title),
+                event.resolution_date,
+            );
+            // Both keys are recorded so later entries can't slip past either check
+            let new_id = seen_ids.insert(id_key);
+            let new_title = seen_titles.insert(title_key);
+            new_id && new_title
+        })
+        .collect()
+}
+
+/// One outcome of a market and its current YES price
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub name: String,
+    pub price: f64,
+}
+
+impl Outcome {
+    pub fn new(name: impl Into<String>, price: f64) -> Self {
+        Self {
+            name: name.into(),
+            price,
+        }
+    }
+}
+
+/// Prices for every outcome of a market - two for binary YES/NO markets,
+/// N for categorical ones (e.g. one per candidate)
 #[derive(Debug, Clone)]
+pub struct MarketQuote {
+    pub outcomes: Vec<Outcome>,
+    pub liquidity: f64,
+    pub book: Option<OrderBook>, // Source book of a binary market, in YES terms
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl MarketQuote {
+    pub fn new(outcomes: Vec<Outcome>, liquidity: f64) -> Self {
+        Self {
+            outcomes,
+            liquidity,
+            book: None,
+            fetched_at: Utc::now(),
+        }
+    }
+
+    pub fn with_book(mut self, book: OrderBook) -> Self {
+        self.book = Some(book);
+        self
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.outcomes.len() == 2
+    }
+
+    pub fn outcome(&self, name: &str) -> Option<&Outcome> {
+        self.outcomes.iter().find(|o| o.name.eq_ignore_ascii_case(name))
+    }
+
+    /// (name, price) pairs, as taken by `ArbitrageDetector::check_multi_outcome_arbitrage`
+    pub fn outcome_prices(&self) -> Vec<(String, f64)> {
+        self.outcomes.iter().map(|o| (o.name.clone(), o.price)).collect()
+    }
+
+    /// Binary YES/NO view, with bids and asks when the quote came from a
+    /// book; `None` for categorical markets
+    pub fn binary_prices(&self) -> Option<MarketPrices> {
+        if !self.is_binary() {
+            return None;
+        }
+
+        let (yes, no) = match (self.outcome("YES"), self.outcome("NO")) {
+            (Some(yes), Some(no)) => (yes.price, no.price),
+            _ => (self.outcomes[0].price, self.outcomes[1].price), // YES listed first
+        };
+        let prices = MarketPrices::new_at(yes, no, self.liquidity, self.fetched_at);
+        Some(match &self.book {
+            Some(book) => prices
+                .with_yes_bid_ask(book.bids.best_price(), book.asks.best_price())
+                .with_book(book.clone()),
+            None => prices,
+        })
+    }
+}
+
+impl From<&MarketPrices> for MarketQuote {
+    fn from(prices: &MarketPrices) -> Self {
+        Self {
+            outcomes: vec![Outcome::new("YES", prices.yes), Outcome::new("NO", prices.no)],
+            liquidity: prices.liquidity,
+            book: prices.book.clone(),
+            fetched_at: prices.fetched_at,
+        }
+    }
+}
+
+/// Furthest YES + NO may sit from 1.00 before prices are treated as bad data
+const MAX_PRICE_SUM_DEVIATION: f64 = 0.2;
+
+/// Last/summary YES and NO prices, plus the best bid and ask on each side
+/// when the venue reports them (asks are what buying actually costs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketPrices {
     pub yes: f64,
     pub no: f64,
+    #[serde(default)]
+    pub yes_bid: Option<f64>,
+    #[serde(default)]
+    pub yes_ask: Option<f64>,
+    #[serde(default)]
+    pub no_bid: Option<f64>,
+    #[serde(default)]
+    pub no_ask: Option<f64>,
     pub liquidity: f64,
+    #[serde(skip)]
+    pub book: Option<OrderBook>, // Source book, if prices were derived from one
+    pub fetched_at: DateTime<Utc>,
 }
 
 impl MarketPrices {
     pub fn new(yes: f64, no: f64, liquidity: f64) -> Self {
+        Self::new_at(yes, no, liquidity, Utc::now())
+    }
+
+    /// Prices observed at `fetched_at` rather than now
+    pub fn new_at(yes: f64, no: f64, liquidity: f64, fetched_at: DateTime<Utc>) -> Self {
         Self {
             yes,
             no,
+            yes_bid: None,
+            yes_ask: None,
+            no_bid: None,
+            no_ask: None,
             liquidity,
+            book: None,
+            fetched_at,
         }
     }
 
+    /// Set the YES bid/ask; the NO side follows, since buying NO at p is
+    /// selling YES at 1 - p (a NO bid is a YES ask, and vice versa)
+    pub fn with_yes_bid_ask(mut self, bid: Option<f64>, ask: Option<f64>) -> Self {
+        self.yes_bid = bid;
+        self.yes_ask = ask;
+        self.no_bid = ask.map(|ask| 1.0 - ask);
+        self.no_ask = bid.map(|bid| 1.0 - bid);
+        self
+    }
+
+    /// What buying YES costs now: the best ask, or `yes` when no ask is known
+    pub fn yes_buy_price(&self) -> f64 {
+        self.yes_ask.unwrap_or(self.yes)
+    }
+
+    /// What buying NO costs now: the best ask, or `no` when no ask is known
+    pub fn no_buy_price(&self) -> f64 {
+        self.no_ask.unwrap_or(self.no)
+    }
+
+    /// YES ask minus YES bid, when both are known
+    pub fn yes_spread(&self) -> Option<f64> {
+        Some(self.yes_ask? - self.yes_bid?)
+    }
+
+    /// Whether these prices are older than `max_age`
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.is_stale_at(Utc::now(), max_age)
+    }
+
+    /// Whether these prices are older than `max_age` as of `now`
+    pub fn is_stale_at(&self, now: DateTime<Utc>, max_age: Duration) -> bool {
+        now - self.fetched_at > max_age
+    }
+
+    pub fn with_book(mut self, book: OrderBook) -> Self {
+        self.book = Some(book);
+        self
+    }
+
+    /// Prices achievable when buying `size_usdc` of each outcome (VWAP across the book).
+    /// Returns `None` if the book is too thin; prices without a book are returned as-is.
+    pub fn at_size(&self, size_usdc: f64) -> Option<MarketPrices> {
+        let Some(book) = &self.book else {
+            return Some(self.clone());
+        };
+
+        let yes = book.vwap_for_size(size_usdc)?;
+        let no = book.complement().vwap_for_size(size_usdc)?;
+        Some(MarketPrices {
+            yes,
+            no,
+            // The fill price is the effective ask at this size
+            yes_ask: Some(yes),
+            no_ask: Some(no),
+            ..self.clone()
+        })
+    }
+
+    /// Prices with YES and NO swapped, for a market phrased with opposite polarity
+    pub fn inverted(&self) -> MarketPrices {
+        MarketPrices {
+            yes: self.no,
+            no: self.yes,
+            yes_bid: self.no_bid,
+            yes_ask: self.no_ask,
+            no_bid: self.yes_bid,
+            no_ask: self.yes_ask,
+            liquidity: self.liquidity,
+            book: self.book.as_ref().map(|book| book.complement()),
+            fetched_at: self.fetched_at,
+        }
+    }
+
+    /// USDC notional available to buy `outcome` ("YES"/"NO"); falls back to
+    /// overall liquidity when no book is attached
+    pub fn available_to_buy(&self, outcome: &str) -> f64 {
+        match &self.book {
+            Some(book) if outcome == "NO" => book.complement().asks.total_notional(),
+            Some(book) => book.asks.total_notional(),
+            None => self.liquidity,
+        }
+    }
+
+    /// Whether these prices look like missing or broken data rather than a real
+    /// market: a side at or beyond 0/1, or YES + NO far from 1.00
+    pub fn is_degenerate(&self) -> bool {
+        let in_range = |price: f64| price > 0.0 && price < 1.0;
+
+        !in_range(self.yes)
+            || !in_range(self.no)
+            || (self.yes + self.no - 1.0).abs() > MAX_PRICE_SUM_DEVIATION
+    }
+
+    /// Whether YES + NO is within 0.01 of 1.00 (allowing for small rounding)
     pub fn validate(&self) -> bool {
-        // Yes + No should equal ~1.00 (allowing for small rounding)
-        (self.yes + self.no - 1.0).abs() < 0.01
+        self.validate_with_tolerance(0.01)
+    }
+
+    /// Whether YES + NO is within `tolerance` of 1.00. Venues with wide spreads
+    /// need a looser tolerance than the rounding-only default.
+    pub fn validate_with_tolerance(&self, tolerance: f64) -> bool {
+        (self.yes + self.no - 1.0).abs() < tolerance
     }
 }
 
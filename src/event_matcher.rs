@@ -1,16 +1,48 @@
 use crate::event::Event;
-use chrono::{DateTime, Utc, FixedOffset, TimeZone};
+use chrono::{DateTime, Utc, NaiveDate, NaiveDateTime};
+use rayon::prelude::*;
 use regex::Regex;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// Date formats spotted in titles and descriptions, compiled once - matching
+/// scores every event pair, so compiling per call dominated scan time
+fn date_patterns() -> &'static [Regex] {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            r"\b\d{1,2}[/-]\d{1,2}[/-]\d{2,4}\b",
+            r"\b(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)[a-z]*\s+\d{1,2},?\s+\d{4}\b",
+            r"\b\d{4}\b",
+            r"\b\d{4}-\d{2}-\d{2}\b", // ISO format
+            r"\b\d{1,2}\s+(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)[a-z]*\s+\d{4}\b",
+        ]
+        .iter()
+        .map(|pattern| Regex::new(pattern).expect("valid date pattern"))
+        .collect()
+    })
+}
+
+/// Numbers with an optional currency sign, magnitude suffix or percent
+fn number_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?i)(\$)?\b((?:\d{1,3}(?:,\d{3})+|\d+)(?:\.\d+)?)\s?(bn|b|k|m)?\b(%)?").expect("valid number pattern")
+    })
+}
 
 /// Confidence score for event matches
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchConfidence {
     pub text_similarity: f64,
-    pub date_match: bool,
+    pub date_match: bool,     // Date proximity at or above `DATE_MATCH_THRESHOLD`
+    pub date_proximity: f64,  // 1.0 for the same instant, decaying to 0.0 over the date window
     pub category_match: bool,
     pub keyword_overlap: f64,
     pub number_match: bool,
+    pub inverted: bool, // Titles have opposite polarity - YES on one is NO on the other
     pub overall_score: f64,
 }
 
@@ -24,23 +56,184 @@ impl MatchConfidence {
     }
 }
 
+/// What a number in a title denotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberKind {
+    Percent,  // "50%"
+    Currency, // "$60k"
+    Plain,    // "60,000"
+}
+
+/// A number found in a title: its text as written and normalized value
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberToken {
+    pub raw: String,
+    pub value: f64,
+    pub kind: NumberKind,
+}
+
+/// Weights for combining match signals into `overall_score`
+#[derive(Debug, Clone)]
+pub struct MatchWeights {
+    pub text: f64,
+    pub keyword: f64,
+    pub date: f64,
+    pub category: f64,
+    pub number: f64,
+}
+
+impl Default for MatchWeights {
+    fn default() -> Self {
+        Self {
+            text: 0.4,
+            keyword: 0.25,
+            date: 0.15,
+            category: 0.1,
+            number: 0.1,
+        }
+    }
+}
+
+impl MatchWeights {
+    /// Scale the weights so they sum to 1.0 (falls back to defaults if they sum to zero)
+    pub fn normalized(&self) -> Self {
+        let total = self.text + self.keyword + self.date + self.category + self.number;
+        if total <= 0.0 {
+            return Self::default();
+        }
+
+        Self {
+            text: self.text / total,
+            keyword: self.keyword / total,
+            date: self.date / total,
+            category: self.category / total,
+            number: self.number / total,
+        }
+    }
+}
+
+/// Date proximity at which two events count as a `date_match`
+const DATE_MATCH_THRESHOLD: f64 = 0.5;
+
+/// Blocking key: (normalized category, resolution day since the epoch)
+type BlockKey = (String, i64);
+
+/// Built-in aliases: crypto names to tickers and common team nicknames
+const DEFAULT_ALIASES: &[(&str, &str)] = &[
+    ("bitcoin", "btc"),
+    ("xbt", "btc"),
+    ("ethereum", "eth"),
+    ("ether", "eth"),
+    ("solana", "sol"),
+    ("dogecoin", "doge"),
+    ("ripple", "xrp"),
+    ("cardano", "ada"),
+    ("niners", "49ers"),
+    ("sixers", "76ers"),
+    ("cavs", "cavaliers"),
+    ("mavs", "mavericks"),
+    ("wolves", "timberwolves"),
+];
+
+/// The built-in alias map used by `EventMatcher::new`
+pub fn default_aliases() -> HashMap<String, String> {
+    DEFAULT_ALIASES
+        .iter()
+        .map(|(alias, canonical)| (alias.to_string(), canonical.to_string()))
+        .collect()
+}
+
 pub struct EventMatcher {
     similarity_threshold: f64,
+    blocking: bool,
+    comparisons: AtomicUsize, // Pairs scored by the last `find_matches` call
+    date_window: chrono::Duration,
+    weights: MatchWeights,
+    aliases: HashMap<String, String>, // Lowercase word -> canonical token
 }
 
 impl EventMatcher {
     pub fn new(similarity_threshold: f64) -> Self {
         Self {
             similarity_threshold,
+            blocking: true,
+            comparisons: AtomicUsize::new(0),
+            date_window: chrono::Duration::hours(48), // 24h apart scores 0.5
+            weights: MatchWeights::default(),
+            aliases: default_aliases(),
         }
     }
 
+    /// Use custom scoring weights (normalized to sum to 1.0)
+    pub fn with_weights(mut self, weights: MatchWeights) -> Self {
+        self.weights = weights.normalized();
+        self
+    }
+
+    /// Window over which date proximity decays from 1.0 to 0.0
+    pub fn with_date_window(mut self, date_window: chrono::Duration) -> Self {
+        self.date_window = date_window;
+        self
+    }
+
+    /// Replace the alias map; words are canonicalized during normalization,
+    /// so "Bitcoin" and "BTC" become the same keyword
+    pub fn with_aliases(mut self, aliases: HashMap<String, String>) -> Self {
+        self.aliases = aliases
+            .into_iter()
+            .map(|(alias, canonical)| (alias.to_lowercase(), canonical.to_lowercase()))
+            .collect();
+        self
+    }
+
+    /// Only score pairs sharing a category/date bucket (enabled by default)
+    pub fn with_blocking(mut self, blocking: bool) -> Self {
+        self.blocking = blocking;
+        self
+    }
+
+    /// Number of pairs scored by the most recent `find_matches` call
+    pub fn last_comparison_count(&self) -> usize {
+        self.comparisons.load(Ordering::Relaxed)
+    }
+
+    /// Bucket key for blocking; `None` if the event lacks a category or date
+    fn block_key(&self, event: &Event) -> Option<BlockKey> {
+        let category = event.category.as_ref()?.trim().to_lowercase();
+        let day = event.resolution_date?.timestamp().div_euclid(86400);
+        Some((category, day))
+    }
+
+    /// Candidates worth scoring against `event`: the same category in the same or
+    /// adjacent day (dates within 24h can straddle midnight), plus every candidate
+    /// missing a category/date. Events without a key are compared against everything.
+    fn blocked_candidates<'a>(
+        &self,
+        event: &Event,
+        index: &HashMap<BlockKey, Vec<&'a Event>>,
+        fallback: &[&'a Event],
+        all: &'a [Event],
+    ) -> Vec<&'a Event> {
+        let Some((category, day)) = self.block_key(event) else {
+            return all.iter().collect();
+        };
+
+        let mut candidates: Vec<&Event> = (day - 1..=day + 1)
+            .filter_map(|d| index.get(&(category.clone(), d)))
+            .flatten()
+            .copied()
+            .collect();
+        candidates.extend_from_slice(fallback);
+        candidates
+    }
+
     pub fn normalize_text(&self, text: &str) -> String {
         text.to_lowercase()
             .chars()
             .filter(|c| c.is_alphanumeric() || c.is_whitespace())
             .collect::<String>()
             .split_whitespace()
+            .map(|word| self.aliases.get(word).map(String::as_str).unwrap_or(word))
             .collect::<Vec<_>>()
             .join(" ")
     }
@@ -61,51 +254,74 @@ impl EventMatcher {
             .collect()
     }
 
-    pub fn extract_dates(&self, text: &str) -> Vec<String> {
-        let patterns = [
-            r"\b\d{1,2}[/-]\d{1,2}[/-]\d{2,4}\b",
-            r"\b(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)[a-z]*\s+\d{1,2},?\s+\d{4}\b",
-            r"\b\d{4}\b",
-            r"\b\d{4}-\d{2}-\d{2}\b", // ISO format
-            r"\b\d{1,2}\s+(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec)[a-z]*\s+\d{4}\b",
-        ];
+    /// Whether the text is phrased negatively ("Will X not happen?", "...fail to...")
+    pub fn is_negated(&self, text: &str) -> bool {
+        let negations: HashSet<&str> = [
+            "not", "no", "never", "fail", "fails", "wont", "doesnt", "isnt", "cant",
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        // normalize_text strips apostrophes, so "won't" becomes "wont"
+        self.normalize_text(text)
+            .split_whitespace()
+            .any(|w| negations.contains(w))
+    }
 
+    pub fn extract_dates(&self, text: &str) -> Vec<String> {
         let mut dates = Vec::new();
-        for pattern in &patterns {
-            if let Ok(re) = Regex::new(pattern) {
-                for cap in re.captures_iter(text) {
-                    dates.push(cap[0].to_string());
-                }
+        for re in date_patterns() {
+            for cap in re.captures_iter(text) {
+                dates.push(cap[0].to_string());
             }
         }
         dates
     }
 
-    /// Parse resolution date with multiple format support
+    /// Parse resolution date with multiple format support. Strings with an
+    /// offset ("Z", "+02:00") are converted to UTC; those without are taken as UTC,
+    /// and bare dates as midnight UTC.
     pub fn parse_resolution_date(&self, date_str: &str) -> Option<DateTime<Utc>> {
-        // Try multiple date formats
-        let formats = [
-            "%Y-%m-%dT%H:%M:%S%.fZ",
-            "%Y-%m-%dT%H:%M:%SZ",
-            "%Y-%m-%d %H:%M:%S",
+        let date_str = date_str.trim();
+
+        // RFC3339 covers "2025-06-01T12:00:00Z" and "...+02:00"
+        if let Ok(dt) = DateTime::parse_from_rfc3339(date_str) {
+            return Some(dt.with_timezone(&Utc));
+        }
+
+        // Other offset-bearing formats
+        let offset_formats = ["%Y-%m-%d %H:%M:%S %z", "%Y-%m-%d %H:%M:%S%z"];
+        for format in &offset_formats {
+            if let Ok(dt) = DateTime::parse_from_str(date_str, format) {
+                return Some(dt.with_timezone(&Utc));
+            }
+        }
+
+        // Date and time without an offset
+        let datetime_formats = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+        for format in &datetime_formats {
+            if let Ok(dt) = NaiveDateTime::parse_from_str(date_str, format) {
+                return Some(dt.and_utc());
+            }
+        }
+
+        // Bare dates (US month/day order tried before day/month)
+        let date_formats = [
             "%Y-%m-%d",
             "%m/%d/%Y",
             "%d/%m/%Y",
             "%B %d, %Y",
             "%b %d, %Y",
+            "%d %B %Y",
+            "%d %b %Y",
         ];
-
-        for format in &formats {
-            if let Ok(dt) = DateTime::parse_from_str(date_str, format) {
-                return Some(dt.with_timezone(&Utc));
+        for format in &date_formats {
+            if let Ok(date) = NaiveDate::parse_from_str(date_str, format) {
+                return date.and_hms_opt(0, 0, 0).map(|dt| dt.and_utc());
             }
         }
 
-        // Try RFC3339
-        if let Ok(dt) = DateTime::parse_from_rfc3339(date_str) {
-            return Some(dt.with_timezone(&Utc));
-        }
-
         None
     }
 
@@ -120,22 +336,67 @@ impl EventMatcher {
         }
     }
 
-    pub fn extract_numbers(&self, text: &str) -> Vec<String> {
-        let patterns = [
-            r"\$[\d,]+(?:\.\d+)?",
-            r"\d+%",
-            r"\b\d{1,3}(?:,\d{3})*(?:\.\d+)?\b",
-        ];
-
-        let mut numbers = Vec::new();
-        for pattern in &patterns {
-            if let Ok(re) = Regex::new(pattern) {
-                for cap in re.captures_iter(text) {
-                    numbers.push(cap[0].to_string());
-                }
+    /// Continuous date similarity: 1.0 at zero difference, decaying linearly to
+    /// 0.0 at `date_window` apart. Missing dates score 0.0.
+    pub fn date_proximity(&self, date1: Option<DateTime<Utc>>, date2: Option<DateTime<Utc>>) -> f64 {
+        match (date1, date2) {
+            (Some(d1), Some(d2)) => {
+                let diff = (d1 - d2).num_seconds().abs() as f64;
+                let window = self.date_window.num_seconds().max(1) as f64;
+                (1.0 - diff / window).max(0.0)
             }
+            _ => 0.0,
+        }
+    }
+
+    /// Extract numbers with their raw text, normalizing thousands separators
+    /// and `k`/`m`/`bn` magnitudes ("$60k" and "$60,000" both give 60000.0)
+    pub fn extract_number_tokens(&self, text: &str) -> Vec<NumberToken> {
+        let mut tokens = Vec::new();
+        for cap in number_pattern().captures_iter(text) {
+            let Ok(value) = cap[2].replace(',', "").parse::<f64>() else {
+                continue;
+            };
+
+            let multiplier = match cap.get(3).map(|m| m.as_str().to_lowercase()).as_deref() {
+                Some("k") => 1e3,
+                Some("m") => 1e6,
+                Some("b") | Some("bn") => 1e9,
+                _ => 1.0,
+            };
+
+            let kind = if cap.get(4).is_some() {
+                NumberKind::Percent
+            } else if cap.get(1).is_some() {
+                NumberKind::Currency
+            } else {
+                NumberKind::Plain
+            };
+
+            tokens.push(NumberToken {
+                raw: cap[0].trim().to_string(),
+                value: value * multiplier,
+                kind,
+            });
         }
-        numbers
+        tokens
+    }
+
+    /// Normalized values of `extract_number_tokens`
+    pub fn extract_numbers(&self, text: &str) -> Vec<f64> {
+        self.extract_number_tokens(text).into_iter().map(|t| t.value).collect()
+    }
+
+    /// Whether two extracted values are the same threshold (0.1% relative tolerance)
+    fn numbers_equal(a: f64, b: f64) -> bool {
+        (a - b).abs() <= a.abs().max(b.abs()) * 1e-3
+    }
+
+    /// Same value, and not e.g. "50%" against "$50" (plain numbers match either)
+    fn tokens_match(a: &NumberToken, b: &NumberToken) -> bool {
+        let kinds_compatible =
+            a.kind == b.kind || a.kind == NumberKind::Plain || b.kind == NumberKind::Plain;
+        kinds_compatible && Self::numbers_equal(a.value, b.value)
     }
 
     pub fn calculate_similarity(&self, event1: &Event, event2: &Event) -> f64 {
@@ -160,9 +421,9 @@ impl EventMatcher {
             0.0
         };
 
-        // Date matching - improved with resolution date comparison
-        let date_match = self.dates_match(event1.resolution_date, event2.resolution_date);
-        
+        // Date matching - continuous proximity of resolution dates
+        let resolution_proximity = self.date_proximity(event1.resolution_date, event2.resolution_date);
+
         // Also check extracted dates from text
         let dates1 = self.extract_dates(&(event1.title.clone() + " " + &event1.description));
         let dates2 = self.extract_dates(&(event2.title.clone() + " " + &event2.description));
@@ -174,7 +435,9 @@ impl EventMatcher {
             false
         };
         
-        let date_match_final = date_match || date_text_match;
+        // A date mentioned in both texts counts as an exact match
+        let date_proximity = if date_text_match { 1.0 } else { resolution_proximity };
+        let date_match = date_proximity >= DATE_MATCH_THRESHOLD;
 
         // Category matching
         let category_match = match (&event1.category, &event2.category) {
@@ -182,30 +445,38 @@ impl EventMatcher {
             _ => false,
         };
 
-        // Number matching
-        let numbers1 = self.extract_numbers(&event1.title);
-        let numbers2 = self.extract_numbers(&event2.title);
-        let number_match = if !numbers1.is_empty() && !numbers2.is_empty() {
-            let set1: HashSet<_> = numbers1.iter().collect();
-            let set2: HashSet<_> = numbers2.iter().collect();
-            !set1.is_disjoint(&set2) // Check if any numbers overlap
-        } else {
-            false
-        };
+        // Number matching - compare parsed thresholds, not their spelling
+        let numbers1 = self.extract_number_tokens(&event1.title);
+        let numbers2 = self.extract_number_tokens(&event2.title);
+        let both_have_numbers = !numbers1.is_empty() && !numbers2.is_empty();
+        let number_match = both_have_numbers
+            && numbers1
+                .iter()
+                .any(|a| numbers2.iter().any(|b| Self::tokens_match(a, b)));
+        // Different thresholds ("above $60k" vs "above $70k") are different events
+        let number_mismatch = both_have_numbers && !number_match;
+
+        // Opposite polarity means the YES/NO legs must be swapped
+        let inverted = self.is_negated(&event1.title) != self.is_negated(&event2.title);
 
         // Weighted combination
-        let overall_score = text_similarity * 0.4
-            + keyword_overlap * 0.25
-            + if date_match_final { 0.15 } else { 0.0 }
-            + if category_match { 0.1 } else { 0.0 }
-            + if number_match { 0.1 } else { 0.0 };
+        let weights = &self.weights;
+        let overall_score = text_similarity * weights.text
+            + keyword_overlap * weights.keyword
+            + date_proximity * weights.date
+            + if category_match { weights.category } else { 0.0 }
+            + if number_match { weights.number } else { 0.0 }
+            - if number_mismatch { weights.number } else { 0.0 };
+        let overall_score = overall_score.max(0.0);
 
         MatchConfidence {
             text_similarity,
-            date_match: date_match_final,
+            date_match,
+            date_proximity,
             category_match,
             keyword_overlap,
             number_match,
+            inverted,
             overall_score,
         }
     }
@@ -226,52 +497,151 @@ impl EventMatcher {
         polymarket_events: &[Event],
         kalshi_events: &[Event],
     ) -> Vec<(Event, Event, MatchConfidence)> {
-        let mut matches = Vec::new();
-
-        for pm_event in polymarket_events {
+        // Index Kalshi events by bucket so each Polymarket event only sees likely pairs
+        let mut index: HashMap<BlockKey, Vec<&Event>> = HashMap::new();
+        let mut fallback: Vec<&Event> = Vec::new();
+        if self.blocking {
             for kalshi_event in kalshi_events {
-                let confidence = self.calculate_similarity_with_confidence(pm_event, kalshi_event);
-
-                if confidence.overall_score >= self.similarity_threshold {
-                    matches.push((
-                        pm_event.clone(),
-                        kalshi_event.clone(),
-                        confidence,
-                    ));
+                match self.block_key(kalshi_event) {
+                    Some(key) => index.entry(key).or_default().push(kalshi_event),
+                    None => fallback.push(kalshi_event),
                 }
             }
         }
 
-        // Sort by overall score (highest first)
+        self.comparisons.store(0, Ordering::Relaxed);
+
+        // Score Polymarket events in parallel; each worker scans its candidate Kalshi events
+        let mut matches: Vec<(Event, Event, MatchConfidence)> = polymarket_events
+            .par_iter()
+            .flat_map_iter(|pm_event| {
+                let candidates = if self.blocking {
+                    self.blocked_candidates(pm_event, &index, &fallback, kalshi_events)
+                } else {
+                    kalshi_events.iter().collect()
+                };
+                self.comparisons.fetch_add(candidates.len(), Ordering::Relaxed);
+
+                candidates.into_iter().filter_map(move |kalshi_event| {
+                    let confidence = self.calculate_similarity_with_confidence(pm_event, kalshi_event);
+
+                    (confidence.overall_score >= self.similarity_threshold).then(|| {
+                        (pm_event.clone(), kalshi_event.clone(), confidence)
+                    })
+                })
+            })
+            .collect();
+
+        // Sort by overall score (highest first), tie-breaking on event IDs so
+        // the order is stable across runs
         matches.sort_by(|a, b| {
-            b.2.overall_score.partial_cmp(&a.2.overall_score)
-                .unwrap_or(std::cmp::Ordering::Equal)
+            b.2.overall_score
+                .total_cmp(&a.2.overall_score)
+                .then_with(|| a.0.event_id.cmp(&b.0.event_id))
+                .then_with(|| a.1.event_id.cmp(&b.1.event_id))
         });
 
         matches
     }
 
+    /// Best-scoring candidate at or above the threshold. Ties go to the
+    /// lowest `event_id`, so the choice doesn't depend on input order.
     pub fn find_best_match(
         &self,
         target_event: &Event,
         candidate_events: &[Event],
     ) -> Option<(Event, f64)> {
-        let mut best_match: Option<(Event, f64)> = None;
-        let mut best_similarity = 0.0;
-
-        for candidate in candidate_events {
-            let similarity = self.calculate_similarity(target_event, candidate);
-            if similarity > best_similarity {
-                best_similarity = similarity;
-                best_match = Some((candidate.clone(), similarity));
-            }
-        }
+        self.find_top_matches(target_event, candidate_events)
+            .into_iter()
+            .next()
+    }
 
-        if best_similarity >= self.similarity_threshold {
-            best_match
-        } else {
-            None
+    /// Every candidate sharing the top score (at or above the threshold),
+    /// ordered by `event_id`, for callers that want to disambiguate ties
+    pub fn find_top_matches(
+        &self,
+        target_event: &Event,
+        candidate_events: &[Event],
+    ) -> Vec<(Event, f64)> {
+        let scored: Vec<(&Event, f64)> = candidate_events
+            .iter()
+            .map(|candidate| (candidate, self.calculate_similarity(target_event, candidate)))
+            .collect();
+
+        let best_similarity = scored
+            .iter()
+            .map(|(_, similarity)| *similarity)
+            .fold(f64::NEG_INFINITY, f64::max);
+        if best_similarity < self.similarity_threshold {
+            return Vec::new();
         }
+
+        let mut top: Vec<(Event, f64)> = scored
+            .into_iter()
+            .filter(|(_, similarity)| *similarity == best_similarity)
+            .map(|(candidate, similarity)| (candidate.clone(), similarity))
+            .collect();
+        top.sort_by(|a, b| a.0.event_id.cmp(&b.0.event_id));
+        top
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Platform;
+    use chrono::Duration;
+
+    const ASSETS: &[&str] = &["BTC", "ETH", "SOL", "XRP"];
+
+    /// Price-threshold markets phrased the way each venue does
+    fn events(platform: Platform, count: usize) -> Vec<Event> {
+        let now = Utc::now();
+        (0..count)
+            .map(|i| {
+                let asset = ASSETS[i % ASSETS.len()];
+                let threshold = 1_000 + (i / ASSETS.len()) * 250;
+                let title = match platform {
+                    Platform::Kalshi => format!("{} above ${} on close?", asset, threshold),
+                    _ => format!("Will {} be above ${}?", asset, threshold),
+                };
+                Event::new(platform, format!("{}-{}", platform, i), title, String::new())
+                    .with_category("Crypto".to_string())
+                    .with_resolution_date(now + Duration::hours(1 + (i % 48) as i64))
+            })
+            .collect()
+    }
+
+    /// (Polymarket ID, Kalshi ID, score) for every match, in result order
+    fn summarize(matches: Vec<(Event, Event, MatchConfidence)>) -> Vec<(String, String, f64)> {
+        matches
+            .into_iter()
+            .map(|(pm, kalshi, confidence)| (pm.event_id, kalshi.event_id, confidence.overall_score))
+            .collect()
+    }
+
+    #[test]
+    fn parallel_matching_agrees_with_a_serial_scan() {
+        let (pm, kalshi) = (events(Platform::Polymarket, 40), events(Platform::Kalshi, 40));
+        let matcher = EventMatcher::new(0.6).with_blocking(false);
+
+        let on_threads = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            summarize(pool.install(|| matcher.find_matches_with_confidence(&pm, &kalshi)))
+        };
+        let parallel = on_threads(4);
+        assert!(!parallel.is_empty());
+        assert_eq!(parallel, on_threads(1));
+
+        // Every pair scored one at a time, ordered the same way
+        let mut serial: Vec<(String, String, f64)> = pm
+            .iter()
+            .flat_map(|p| kalshi.iter().map(move |k| (p, k)))
+            .map(|(p, k)| (p.event_id.clone(), k.event_id.clone(), matcher.calculate_similarity(p, k)))
+            .filter(|(_, _, score)| *score >= 0.6)
+            .collect();
+        serial.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.0.cmp(&b.0)).then_with(|| a.1.cmp(&b.1)));
+        assert_eq!(parallel, serial);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::clients::{KalshiClient, ManifoldClient, PolymarketClient};
+use crate::event::{normalize_outcome, Event, MarketPrices, Platform};
+use crate::error::{BotError, BotResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Check an order's limit price and dollar size before sending it,
+/// returning the (fractional) share count it buys
+#[allow(clippy::neg_cmp_op_on_partial_ord)] // Negated comparisons reject NaN too
+pub fn validate_order(amount: f64, price: f64) -> BotResult<f64> {
+    if !(price > 0.0 && price < 1.0) {
+        return Err(BotError::InvalidPrice(format!(
+            "{} - must be between 0 and 1 (exclusive)",
+            price
+        )));
+    }
+    if !(amount > 0.0) {
+        return Err(BotError::InvalidOrder(format!(
+            "amount ${} - must be positive",
+            amount
+        )));
+    }
+    Ok(amount / price)
+}
+
+/// How long an order may rest on the book before the venue cancels it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeInForce {
+    /// Rest until filled or cancelled
+    Gtc,
+    /// Fill what's available now, cancel the rest
+    #[default]
+    Ioc,
+    /// Fill completely right now or not at all
+    Fok,
+    /// Rest until the given time
+    Gtd(DateTime<Utc>),
+}
+
+impl TimeInForce {
+    /// Expiry as a Unix timestamp, for good-till-date orders
+    pub fn expiration_ts(&self) -> Option<i64> {
+        match self {
+            TimeInForce::Gtd(expiry) => Some(expiry.timestamp()),
+            _ => None,
+        }
+    }
+}
+
+/// One execution against an order, as reported by the venue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fill {
+    pub shares: f64, // Contracts/tokens bought
+    pub price: f64,  // Price per share (0-1)
+    pub fee: f64,    // Fee charged in USD
+}
+
+impl Fill {
+    pub fn cost(&self) -> f64 {
+        self.shares * self.price
+    }
+}
+
+/// How a settled market resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Yes,
+    No,
+    /// No winning side (voided, cancelled or split evenly) - shares pay back
+    /// a refund or partial payout rather than $1 or nothing
+    Void,
+}
+
+impl Resolution {
+    /// Whether holding `outcome` ("YES"/"NO") won - `None` for a void market
+    pub fn won(&self, outcome: &str) -> Option<bool> {
+        let holds_yes = normalize_outcome(outcome) == "YES";
+        match self {
+            Resolution::Yes => Some(holds_yes),
+            Resolution::No => Some(!holds_yes),
+            Resolution::Void => None,
+        }
+    }
+}
+
+/// Common interface over trading venues, so executors and checkers can be
+/// written once and run against any client (or a mock)
+#[async_trait]
+pub trait Exchange: Send + Sync {
+    /// Which venue this client talks to
+    fn platform(&self) -> Platform;
+
+    /// Fetch active events
+    async fn fetch_events(&self) -> BotResult<Vec<Event>>;
+
+    /// Fetch current prices for an event/market
+    async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices>;
+
+    /// Place a buy order, returning the venue's order ID if one was issued
+    async fn place_order(
+        &self,
+        event_id: String,
+        outcome: String, // "YES" or "NO"
+        amount: f64,
+        price: f64,
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>>;
+
+    /// Sell `shares` of an outcome at no less than `min_price` each
+    async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>>;
+
+    /// Cancel whatever is still resting of an order
+    async fn cancel_order(&self, order_id: &str) -> BotResult<()>;
+
+    /// Venue-reported order status, lowercased
+    async fn get_order_status(&self, order_id: &str) -> BotResult<String>;
+
+    /// Executions so far against `order_id`
+    async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>>;
+
+    /// Check if an event is settled - `None` while it's still open
+    async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>>;
+
+    /// Get available balance in USD
+    async fn get_balance(&self) -> BotResult<f64>;
+
+    /// What the venue actually paid per share of `outcome` once an event
+    /// settled, for venues that report it (`None` if unknown or unsettled)
+    async fn settlement_payout_per_share(&self, _event_id: &str, _outcome: &str) -> BotResult<Option<f64>> {
+        Ok(None)
+    }
+
+    /// Native token balance for paying gas, for venues that need it
+    async fn get_gas_balance(&self) -> BotResult<Option<f64>> {
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl Exchange for PolymarketClient {
+    fn platform(&self) -> Platform {
+        Platform::Polymarket
+    }
+
+    async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        PolymarketClient::fetch_events(self).await
+    }
+
+    async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        PolymarketClient::fetch_prices(self, event_id).await
+    }
+
+    async fn place_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        amount: f64,
+        price: f64,
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        PolymarketClient::place_order(self, event_id, outcome, amount, price, time_in_force).await
+    }
+
+    async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>> {
+        PolymarketClient::sell_order(self, event_id, outcome, shares, min_price).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> BotResult<()> {
+        PolymarketClient::cancel_order(self, order_id).await
+    }
+
+    async fn get_order_status(&self, order_id: &str) -> BotResult<String> {
+        PolymarketClient::get_order_status(self, order_id).await
+    }
+
+    async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>> {
+        PolymarketClient::get_fills(self, order_id).await
+    }
+
+    async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>> {
+        PolymarketClient::check_settlement(self, event_id).await
+    }
+
+    async fn get_balance(&self) -> BotResult<f64> {
+        PolymarketClient::get_balance(self).await
+    }
+
+    async fn settlement_payout_per_share(&self, event_id: &str, outcome: &str) -> BotResult<Option<f64>> {
+        PolymarketClient::settlement_payout_per_share(self, event_id, outcome).await
+    }
+
+    async fn get_gas_balance(&self) -> BotResult<Option<f64>> {
+        PolymarketClient::get_native_balance(self).await.map(Some)
+    }
+}
+
+#[async_trait]
+impl Exchange for KalshiClient {
+    fn platform(&self) -> Platform {
+        Platform::Kalshi
+    }
+
+    async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        KalshiClient::fetch_events(self).await
+    }
+
+    async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        KalshiClient::fetch_prices(self, event_id).await
+    }
+
+    async fn place_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        amount: f64,
+        price: f64,
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        KalshiClient::place_order(self, event_id, outcome, amount, price, time_in_force).await
+    }
+
+    async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>> {
+        KalshiClient::sell_order(self, event_id, outcome, shares, min_price).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> BotResult<()> {
+        KalshiClient::cancel_order(self, order_id).await
+    }
+
+    async fn get_order_status(&self, order_id: &str) -> BotResult<String> {
+        KalshiClient::get_order_status(self, order_id).await
+    }
+
+    async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>> {
+        KalshiClient::get_fills(self, order_id).await
+    }
+
+    async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>> {
+        KalshiClient::check_settlement(self, event_id).await
+    }
+
+    async fn get_balance(&self) -> BotResult<f64> {
+        KalshiClient::get_balance(self).await
+    }
+
+    async fn settlement_payout_per_share(&self, event_id: &str, outcome: &str) -> BotResult<Option<f64>> {
+        KalshiClient::settlement_payout_per_share(self, event_id, outcome).await
+    }
+}
+
+#[async_trait]
+impl Exchange for ManifoldClient {
+    fn platform(&self) -> Platform {
+        Platform::Manifold
+    }
+
+    async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        ManifoldClient::fetch_events(self).await
+    }
+
+    async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        ManifoldClient::fetch_prices(self, event_id).await
+    }
+
+    async fn place_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        amount: f64,
+        price: f64,
+        time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        ManifoldClient::place_order(self, event_id, outcome, amount, price, time_in_force).await
+    }
+
+    async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>> {
+        ManifoldClient::sell_order(self, event_id, outcome, shares, min_price).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> BotResult<()> {
+        ManifoldClient::cancel_order(self, order_id).await
+    }
+
+    async fn get_order_status(&self, order_id: &str) -> BotResult<String> {
+        ManifoldClient::get_order_status(self, order_id).await
+    }
+
+    async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>> {
+        ManifoldClient::get_fills(self, order_id).await
+    }
+
+    async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>> {
+        ManifoldClient::check_settlement(self, event_id).await
+    }
+
+    async fn get_balance(&self) -> BotResult<f64> {
+        ManifoldClient::get_balance(self).await
+    }
+}
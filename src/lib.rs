@@ -1,21 +1,64 @@
 // Core modules
+#[cfg(feature = "api")]
+pub mod api;
+pub mod approval;
+pub mod config;
+pub mod error;
 pub mod event;
 pub mod event_matcher;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod exchange;
+pub mod order_book;
 pub mod arbitrage_detector;
+pub mod backtest;
 pub mod bot;
+pub mod circuit_breaker;
+pub mod clock;
 pub mod clients;
+pub mod notifier;
+pub mod opportunity_log;
 pub mod trade_executor;
 pub mod position_tracker;
+pub mod position_store;
 pub mod settlement_checker;
 pub mod polymarket_blockchain;
+pub mod price_oracle;
+pub mod mock;
+pub mod rebalance;
+pub mod replay;
+pub mod runner;
 
 // Re-exports
-pub use event::{Event, MarketPrices};
-pub use event_matcher::EventMatcher;
-pub use arbitrage_detector::{ArbitrageDetector, ArbitrageOpportunity};
-pub use bot::{ShortTermArbitrageBot, MarketFilters};
-pub use clients::{PolymarketClient, KalshiClient};
+pub use approval::{ApprovalPolicy, ApprovalQueue, PendingOpportunity};
+pub use config::Config;
+pub use error::{BotError, BotResult};
+pub use event::{dedupe_events, normalize_outcome, Event, MarketPrices, MarketQuote, Outcome, Platform};
+pub use event_matcher::{
+    default_aliases, EventMatcher, MatchConfidence, MatchWeights, NumberKind, NumberToken,
+};
+pub use order_book::{MarketDepth, OrderBook};
+pub use arbitrage_detector::{
+    ArbitrageDetector, ArbitrageOpportunity, BasketOpportunity, FeeKind, FeeModel, Fees, FlatFee, KalshiFee,
+    OrderAction,
+};
+pub use backtest::{BacktestRecord, BacktestReport, Backtester};
+pub use bot::{allocate, is_executable, MarketFilters, ScannedOpportunity, ShortTermArbitrageBot, TradeCooldown};
+pub use circuit_breaker::CircuitBreaker;
+pub use clock::{Clock, FixedClock, JitteredInterval, SystemClock};
+pub use clients::{Environment, EventCache, PolymarketClient, KalshiClient, ManifoldClient, RateLimiter, RetryPolicy};
+pub use exchange::{validate_order, Exchange, Fill, Resolution, TimeInForce};
+pub use notifier::{Notifier, Notifiers, TelegramNotifier, WebhookNotifier};
+pub use opportunity_log::{OpportunityLogger, OpportunityRecord};
 pub use trade_executor::{TradeExecutor, TradeResult};
-pub use position_tracker::{PositionTracker, Position, PositionStatus, PositionStatistics};
+pub use position_tracker::{EquityPoint, PositionTracker, Position, PositionStatus, PositionStatistics};
+pub use position_store::PositionStore;
+#[cfg(feature = "sqlite")]
+pub use position_store::SqlitePositionStore;
 pub use settlement_checker::SettlementChecker;
+pub use price_oracle::{CoingeckoOracle, MaticPriceSource, PriceOracle, StaticOracle};
+pub use rebalance::{rebalance_plan, RebalanceAction};
+pub use mock::{MockExchange, MockOrder};
+pub use replay::ReplayClient;
+pub use runner::BotRunner;
 
@@ -1,37 +1,130 @@
 use anyhow::Result;
+use clap::{Parser, ValueEnum};
 use polymarket_kalshi_arbitrage_bot::{
-    bot::{MarketFilters, ShortTermArbitrageBot},
-    clients::{KalshiClient, PolymarketClient},
-    event::MarketPrices,
+    arbitrage_detector::ArbitrageDetector,
+    backtest::Backtester,
+    bot::ShortTermArbitrageBot,
+    clients::{Environment, KalshiClient, ManifoldClient, PolymarketClient},
+    config::Config,
+    notifier::Notifiers,
+    opportunity_log::OpportunityLogger,
     position_tracker::PositionTracker,
+    runner::BotRunner,
     settlement_checker::SettlementChecker,
     trade_executor::TradeExecutor,
 };
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn, Level};
 
+/// Polymarket-Kalshi arbitrage bot
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Path to the TOML config file
+    #[arg(long, env = "CONFIG_FILE", default_value = "config.toml")]
+    config: String,
+
+    /// Find and log opportunities without placing orders
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Run a single scan + execute pass, then exit
+    #[arg(long)]
+    once: bool,
+
+    /// Seconds between scans (overrides the config file)
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    scan_interval: Option<u64>,
+
+    /// Hand opportunities to a separate executor task over a channel instead
+    /// of trading inline in the scan loop
+    #[arg(long)]
+    decoupled: bool,
+
+    /// Sell every open position (emergency exit), save positions and exit
+    #[arg(long)]
+    close_all: bool,
+
+    /// Replay recorded price snapshots (JSONL) through the detector and exit
+    #[arg(long, value_name = "PATH")]
+    backtest: Option<String>,
+
+    /// Log level (trace, debug, info, warn, error)
+    #[arg(long, default_value = "info")]
+    log_level: Level,
+
+    /// Log output format
+    #[arg(long, env = "LOG_FORMAT", value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+}
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line, for log aggregators
+    Json,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Load environment variables (before parsing, so CONFIG_FILE can come from .env)
+    dotenv::dotenv().ok();
+
+    let cli = Cli::parse();
+
     // Initialize logging
-    tracing_subscriber::fmt()
-        .with_max_level(Level::INFO)
-        .init();
+    match cli.log_format {
+        LogFormat::Text => tracing_subscriber::fmt()
+            .with_max_level(cli.log_level)
+            .init(),
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .with_max_level(cli.log_level)
+            .init(),
+    }
 
     info!("Starting Polymarket-Kalshi Arbitrage Bot");
+    if cli.dry_run {
+        info!("🧪 Dry run - no orders will be placed");
+    }
 
-    // Load environment variables
-    dotenv::dotenv().ok();
+    // Load settings once (config.toml, then env vars, then defaults)
+    let mut config = Config::load(&cli.config)?;
+    if let Some(secs) = cli.scan_interval {
+        config.scan_interval_secs = secs;
+    }
+
+    // Backtests need no credentials or network access
+    if let Some(path) = &cli.backtest {
+        let backtester = Backtester::new(
+            config.event_matcher(),
+            ArbitrageDetector::new(config.min_profit_threshold)
+                .with_min_roi_percent(config.min_roi_percent)
+                .with_fees(config.fees()),
+        );
+        backtester.run_file(path)?;
+        return Ok(());
+    }
+
+    // One cap on in-flight HTTP requests, shared by both clients
+    let request_limit = Arc::new(Semaphore::new(config.max_concurrent_requests));
 
     // Initialize clients with required credentials
-    let polygon_rpc = std::env::var("POLYGON_RPC_URL")
-        .unwrap_or_else(|_| "https://polygon-rpc.com".to_string());
     let wallet_key = std::env::var("POLYMARKET_WALLET_PRIVATE_KEY")
         .ok();
     
     let mut polymarket_client = PolymarketClient::new()
-        .with_rpc(polygon_rpc);
+        .with_rpc(config.polygon_rpc_url.clone())
+        .with_fallback_rpcs(config.polygon_fallback_rpc_urls.clone())
+        .with_event_cache_ttl(config.event_cache_ttl())
+        .with_request_limit(request_limit.clone());
+    if let Some(proxy_url) = &config.proxy_url {
+        polymarket_client = polymarket_client.with_proxy(proxy_url.clone())?;
+    }
     
     if let Some(key) = wallet_key {
         polymarket_client = polymarket_client.with_wallet(key);
@@ -55,162 +148,296 @@ async fn main() -> Result<()> {
         return Err(anyhow::anyhow!("Missing Kalshi API credentials"));
     }
     
-    let kalshi_client = KalshiClient::new(kalshi_api_key, kalshi_api_secret);
+    // KALSHI_ENV=demo trades against the sandbox instead of production
+    let kalshi_env: Environment = match std::env::var("KALSHI_ENV") {
+        Ok(env) => env.parse()?,
+        Err(_) => Environment::default(),
+    };
+    if kalshi_env == Environment::Demo {
+        info!("🧪 Using Kalshi demo environment");
+    }
+
+    let mut kalshi_client = KalshiClient::new(kalshi_api_key, kalshi_api_secret)
+        .with_environment(kalshi_env)
+        .with_event_cache_ttl(config.event_cache_ttl())
+        .with_request_limit(request_limit.clone());
+    if let Some(proxy_url) = &config.proxy_url {
+        info!("🌐 Routing venue API requests through proxy");
+        kalshi_client = kalshi_client.with_proxy(proxy_url.clone())?;
+    }
+
+    // Manifold is optional - only enabled when MANIFOLD_API_KEY is set
+    let manifold_client = match std::env::var("MANIFOLD_API_KEY") {
+        Ok(key) => {
+            info!("🪄 Manifold client enabled");
+            let mut client = ManifoldClient::new(key)
+                .with_event_cache_ttl(config.event_cache_ttl())
+                .with_request_limit(request_limit);
+            if let Some(proxy_url) = &config.proxy_url {
+                client = client.with_proxy(proxy_url.clone())?;
+            }
+            Some(Arc::new(client))
+        }
+        Err(_) => None,
+    };
 
     // Wrap clients in Arc for sharing
     let polymarket_client = Arc::new(polymarket_client);
     let kalshi_client = Arc::new(kalshi_client);
 
     // Create position tracker
-    let position_tracker = Arc::new(Mutex::new(PositionTracker::new()));
-
-    // Create trade executor with position tracker
-    let trade_executor = Arc::new(
-        TradeExecutor::new(
-            (*polymarket_client.clone()).clone(),
-            (*kalshi_client.clone()).clone(),
-        )
-        .with_position_tracker(position_tracker.clone()),
-    );
+    // Restore positions saved by a previous run
+    let positions_file = config.positions_file.clone();
+    let load_from_file = || {
+        PositionTracker::load_from_file(&positions_file).unwrap_or_else(|e| {
+            warn!("⚠️ Could not load saved positions ({}), starting fresh", e);
+            PositionTracker::new()
+        })
+    };
+
+    // With the sqlite feature, DATABASE_URL switches persistence to SQLite
+    #[cfg(feature = "sqlite")]
+    let tracker = match std::env::var("DATABASE_URL") {
+        Ok(url) => {
+            use polymarket_kalshi_arbitrage_bot::position_store::SqlitePositionStore;
+
+            let store = Arc::new(SqlitePositionStore::connect(&url).await?);
+            PositionTracker::load_from_store(store).await?
+        }
+        Err(_) => load_from_file(),
+    };
+    #[cfg(not(feature = "sqlite"))]
+    let tracker = load_from_file();
+    let tracker = match config.max_open_age() {
+        Some(max_open_age) => tracker.with_max_open_age(max_open_age),
+        None => tracker,
+    };
+
+    let position_tracker = Arc::new(Mutex::new(tracker));
+
+    // Optional alerts (NOTIFY_WEBHOOK_URL, TELEGRAM_BOT_TOKEN + TELEGRAM_CHAT_ID)
+    let notifiers = Notifiers::from_env();
+    if !notifiers.is_empty() {
+        info!("🔔 Notifications enabled");
+    }
 
     // Create settlement checker
-    let settlement_checker = Arc::new(SettlementChecker::new(
+    let mut settlement_checker = SettlementChecker::new(
         polymarket_client.clone(),
         kalshi_client.clone(),
         position_tracker.clone(),
-    ));
+    );
+    if let Some(manifold) = &manifold_client {
+        settlement_checker = settlement_checker.with_manifold_client(manifold.clone());
+    }
+    if let Some(threshold) = config.min_balance_alert {
+        settlement_checker = settlement_checker.with_low_balance_alert(threshold, notifiers.clone());
+    }
+    if let Some(target_ratio) = config.rebalance_target_ratio {
+        settlement_checker = settlement_checker
+            .with_rebalance_target(target_ratio)
+            .with_notifiers(notifiers.clone());
+    }
+    let settlement_checker = Arc::new(settlement_checker);
 
-    // Configure filters
-    let filters = MarketFilters {
-        categories: vec!["crypto".to_string(), "sports".to_string()],
-        max_hours_until_resolution: 24,
-        min_liquidity: 100.0,
-    };
+    // MATIC price used to convert Polygon gas into USD
+    let price_oracle = config.price_oracle();
+
+    // Platform fees
+    let min_profit_threshold = config.min_profit_threshold;
+    let fees = config.fees();
+    info!("Fees - Polymarket: {:.2}%, Kalshi: {:.2}%", fees.polymarket * 100.0, fees.kalshi * 100.0);
+
+    let arbitrage_detector = ArbitrageDetector::new(min_profit_threshold)
+        .with_min_roi_percent(config.min_roi_percent)
+        .with_fees(fees);
+
+    // Create trade executor with position tracker (re-checks prices before ordering)
+    let mut trade_executor = TradeExecutor::new(polymarket_client.clone(), kalshi_client.clone())
+        .with_position_tracker(position_tracker.clone())
+        .with_arbitrage_detector(arbitrage_detector.clone())
+        .with_min_gas_balance(config.min_matic_balance)
+        .with_dry_run(cli.dry_run);
+    if let Some(manifold) = &manifold_client {
+        trade_executor = trade_executor.with_manifold_client(manifold.clone());
+    }
+    let trade_executor = Arc::new(trade_executor);
+
+    if cli.close_all {
+        let results = trade_executor.close_all_open(&position_tracker).await;
+        let closed = results.iter().filter(|r| matches!(r, Ok(r) if r.success)).count();
+        info!("🚪 Closed {}/{} open positions", closed, results.len());
+
+        persist_positions(&position_tracker, &positions_file).await;
+        return Ok(());
+    }
 
     // Create bot
     let bot = ShortTermArbitrageBot::new(
-        filters,
-        0.80, // similarity threshold
-        0.02, // min profit threshold (2%)
-    );
+        config.filters(),
+        config.similarity_threshold,
+        min_profit_threshold,
+    )
+    .with_event_matcher(config.event_matcher())
+    .with_approval_policy(config.approval_policy)
+    .with_arbitrage_detector(arbitrage_detector)
+    .with_trade_cooldown(config.trade_cooldown())
+    .with_max_price_age(config.max_price_age())
+    .with_price_sum_tolerance(config.price_sum_tolerance);
 
-    // Fetch prices function
-    let fetch_prices = {
-        let pm = polymarket_client.clone();
-        let kalshi = kalshi_client.clone();
-        move |event_id: &str, platform: &str| {
-            let event_id = event_id.to_string();
-            let platform = platform.to_string();
-            let pm = pm.clone();
-            let kalshi = kalshi.clone();
-            async move {
-                match platform.as_str() {
-                    "polymarket" => pm.fetch_prices(&event_id).await.unwrap_or_default(),
-                    "kalshi" => kalshi.fetch_prices(&event_id).await.unwrap_or_default(),
-                    _ => MarketPrices::new(0.0, 0.0, 0.0),
-                }
-            }
+    info!("Starting continuous scanning (interval: {}s)", config.scan_interval_secs);
+    info!("Settlement checking (every {}s)", config.settlement_interval_secs);
+    
+    // Optional JSONL record of every detected opportunity
+    let opportunity_logger = match &config.opportunity_log_file {
+        Some(path) => {
+            let logger = OpportunityLogger::open(path)?;
+            info!("📝 Logging opportunities to {}", logger.path().display());
+            Some(logger)
         }
+        None => None,
     };
 
-    // Run continuous scanning (every 60 seconds)
-    info!("Starting continuous scanning (interval: 60s)");
-    info!("Settlement checking (every 5 minutes)");
-    
-    let mut scan_interval = tokio::time::interval(Duration::from_secs(60));
-    let mut settlement_interval = tokio::time::interval(Duration::from_secs(300)); // 5 minutes
-    
-    loop {
-        tokio::select! {
-            _ = scan_interval.tick() => {
-        
-        // Fetch events
-        let (pm_events, kalshi_events) = tokio::join!(
-            polymarket_client.fetch_events(),
-            kalshi_client.fetch_events()
-        );
-        
-        let pm_events = pm_events.unwrap_or_default();
-        let kalshi_events = kalshi_events.unwrap_or_default();
-        
-        // Scan for opportunities
-        let opportunities = bot.scan_for_opportunities(&pm_events, &kalshi_events, fetch_prices.clone()).await;
-        
-        // Execute trades for found opportunities
-        if !opportunities.is_empty() {
-            info!("Found {} arbitrage opportunities", opportunities.len());
-            
-            for (pm_event, kalshi_event, opp) in opportunities {
-                info!(
-                    "🚨 Arbitrage Opportunity: {} - Profit: ${:.4}, ROI: {:.2}%",
-                    pm_event.title,
-                    opp.net_profit,
-                    opp.roi_percent
-                );
-
-                // Execute trade (with default amount - you may want to make this configurable)
-                let trade_amount = 100.0; // $100 default
-                
-                match trade_executor
-                    .execute_arbitrage(&opp, &pm_event, &kalshi_event, trade_amount)
-                    .await
-                {
-                    Ok(result) => {
-                        if result.success {
-                            info!(
-                                "✅ Trade executed successfully! PM Order: {:?}, Kalshi Order: {:?}",
-                                result.polymarket_order_id, result.kalshi_order_id
-                            );
-                        } else {
-                            info!(
-                                "⚠️ Trade execution failed: {}",
-                                result.error.unwrap_or_default()
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        error!("Error executing trade: {}", e);
-                    }
-                }
-            }
-            }
-            _ = settlement_interval.tick() => {
-                // Check for settlements
-                info!("Checking for settled positions...");
-                match settlement_checker.check_settlements().await {
-                    Ok(count) => {
-                        if count > 0 {
-                            info!("✅ {} positions settled!", count);
-                            
-                            // Show statistics
-                            let stats = settlement_checker.get_statistics().await;
-                            info!(
-                                "📊 Statistics - Total: {}, Open: {}, Won: {}, Lost: {}, Total Profit: ${:.2}",
-                                stats.total_positions,
-                                stats.open_positions,
-                                stats.won_positions,
-                                stats.lost_positions,
-                                stats.total_profit
-                            );
-                            
-                            // Check balances
-                            if let Ok((pm_balance, kalshi_balance)) = settlement_checker.check_balances().await {
-                                info!(
-                                    "💰 Current Balances - Polymarket: ${:.2}, Kalshi: ${:.2}, Total: ${:.2}",
-                                    pm_balance,
-                                    kalshi_balance,
-                                    pm_balance + kalshi_balance
-                                );
-                            }
-                        } else {
-                            info!("No new settlements");
-                        }
-                    }
-                    Err(e) => {
-                        error!("Error checking settlements: {}", e);
-                    }
-                }
+    let mut runner = BotRunner::new(
+        bot,
+        polymarket_client.clone(),
+        kalshi_client,
+        trade_executor,
+        settlement_checker,
+        position_tracker.clone(),
+    )
+    .with_gas_pricing(polymarket_client, price_oracle, config.matic_usd_price)
+    .with_notifiers(notifiers)
+    .with_circuit_breaker(config.circuit_breaker())
+    // Jittered so restarts (or several bots) don't hit the APIs in lockstep
+    .with_intervals(config.scan_interval(), config.settlement_interval(), config.interval_jitter)
+    .with_dry_run(cli.dry_run)
+    .with_single_scan(cli.once)
+    .with_decoupled_execution(cli.decoupled);
+    if let Some(manifold) = manifold_client {
+        runner = runner.with_manifold_client(manifold);
+    }
+    if let Some(logger) = opportunity_logger {
+        runner = runner.with_opportunity_logger(logger);
+    }
+
+    // Prometheus metrics (only with the `metrics` feature)
+    #[cfg(feature = "metrics")]
+    {
+        let port: u16 = std::env::var("METRICS_PORT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(9090);
+        let metrics = Arc::new(polymarket_kalshi_arbitrage_bot::metrics::Metrics::new()?);
+        runner = runner.with_metrics(metrics).with_metrics_server(port);
+    }
+
+    // REST API (only with the `api` feature)
+    #[cfg(feature = "api")]
+    {
+        let port = std::env::var("API_PORT")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(8080);
+        // Routes that trade (close-all, approvals) stay disabled without API_TOKEN
+        let auth_token = std::env::var("API_TOKEN").ok();
+        if auth_token.is_none() {
+            warn!("⚠️ API_TOKEN not set - API routes that trade are disabled");
+        }
+        runner = runner.with_api_server(port, auth_token);
+    }
+
+    // Ctrl-C cancels the runner, which finishes any in-flight scan first and
+    // stops the servers and position store writer before returning
+    let cancel = CancellationToken::new();
+    {
+        let cancel = cancel.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                cancel.cancel();
             }
+        });
+    }
+    runner.run(cancel).await;
+
+    // Persist positions and report final state
+    persist_positions(&position_tracker, &positions_file).await;
+
+    let stats = position_tracker.lock().await.get_statistics();
+    info!(
+        "📊 Final Statistics - Total: {}, Open: {} ({} stale), Won: {}, Lost: {}, Total Profit: ${:.2}, Exposure: ${:.2}, Win Rate: {:.1}%, ROI: {:.2}%, Max Drawdown: ${:.2}",
+        stats.total_positions,
+        stats.open_positions,
+        stats.stale_positions,
+        stats.won_positions,
+        stats.lost_positions,
+        stats.total_profit,
+        stats.open_exposure,
+        stats.win_rate * 100.0,
+        stats.overall_roi_percent,
+        stats.max_drawdown
+    );
+
+    info!("👋 Bot stopped");
+    Ok(())
+}
+
+/// Save positions to `path` and wait for queued position store writes to land
+async fn persist_positions(position_tracker: &Mutex<PositionTracker>, path: &str) {
+    let writer = {
+        let mut tracker = position_tracker.lock().await;
+        if let Err(e) = tracker.save_to_file(path) {
+            error!("Failed to save positions: {}", e);
+        }
+        tracker.finish_store_writes()
+    };
+
+    if let Some(writer) = writer {
+        if let Err(e) = writer.await {
+            error!("Position store writer failed: {}", e);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("bot").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_flags_and_overrides() {
+        let cli = parse(&["--dry-run", "--once", "--scan-interval", "15", "--log-level", "debug"]).unwrap();
+        assert!(cli.dry_run);
+        assert!(cli.once);
+        assert!(!cli.decoupled);
+        assert_eq!(cli.scan_interval, Some(15));
+        assert_eq!(cli.log_level, Level::DEBUG);
+
+        let cli = parse(&["--backtest", "snapshots.jsonl", "--log-format", "json"]).unwrap();
+        assert_eq!(cli.backtest.as_deref(), Some("snapshots.jsonl"));
+        assert!(matches!(cli.log_format, LogFormat::Json));
+    }
+
+    #[test]
+    fn rejects_a_zero_scan_interval() {
+        let error = parse(&["--scan-interval", "0"]).unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+        assert!(parse(&["--scan-interval", "1"]).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_values() {
+        assert!(parse(&["--log-level", "loud"]).is_err());
+        assert!(parse(&["--log-format", "xml"]).is_err());
+        assert!(parse(&["--no-such-flag"]).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+// Prometheus metrics for the running bot (enabled with the `metrics` feature)
+
+use crate::position_tracker::PositionStatistics;
+use anyhow::{Context, Result};
+use axum::{routing::get, Router};
+use prometheus::{Encoder, Gauge, IntCounter, IntGauge, Registry, TextEncoder};
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+pub struct Metrics {
+    registry: Registry,
+    pub scans: IntCounter,
+    pub opportunities: IntCounter,
+    pub trades_executed: IntCounter,
+    pub trades_failed: IntCounter,
+    pub open_positions: IntGauge,
+    pub total_profit: Gauge,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+
+        let scans = IntCounter::new("arb_scans_total", "Scan cycles performed")?;
+        let opportunities = IntCounter::new("arb_opportunities_total", "Arbitrage opportunities found")?;
+        let trades_executed = IntCounter::new("arb_trades_executed_total", "Arbitrage trades executed on both legs")?;
+        let trades_failed = IntCounter::new("arb_trades_failed_total", "Arbitrage trades that failed")?;
+        let open_positions = IntGauge::new("arb_open_positions", "Positions awaiting settlement")?;
+        let total_profit = Gauge::new("arb_total_profit_usd", "Realized profit in USD")?;
+
+        registry.register(Box::new(scans.clone()))?;
+        registry.register(Box::new(opportunities.clone()))?;
+        registry.register(Box::new(trades_executed.clone()))?;
+        registry.register(Box::new(trades_failed.clone()))?;
+        registry.register(Box::new(open_positions.clone()))?;
+        registry.register(Box::new(total_profit.clone()))?;
+
+        Ok(Self {
+            registry,
+            scans,
+            opportunities,
+            trades_executed,
+            trades_failed,
+            open_positions,
+            total_profit,
+        })
+    }
+
+    /// Refresh position gauges from the tracker's statistics
+    pub fn update_positions(&self, stats: &PositionStatistics) {
+        self.open_positions.set(stats.open_positions as i64);
+        self.total_profit.set(stats.total_profit);
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::warn!("Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+/// Serve `/metrics` on `port` until `shutdown` fires
+pub async fn serve(metrics: Arc<Metrics>, port: u16, shutdown: CancellationToken) -> Result<()> {
+    let app = Router::new().route(
+        "/metrics",
+        get(move || {
+            let metrics = metrics.clone();
+            async move { metrics.render() }
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind(("0.0.0.0", port))
+        .await
+        .with_context(|| format!("Failed to bind metrics port {}", port))?;
+
+    info!("📈 Metrics available at http://0.0.0.0:{}/metrics", port);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown.cancelled_owned())
+        .await
+        .context("Metrics server failed")
+}
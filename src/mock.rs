@@ -0,0 +1,257 @@
+// Scriptable in-memory venue for exercising executors, runners and checkers without a network
+
+use crate::error::{BotError, BotResult};
+use crate::event::{Event, MarketPrices, Platform};
+use crate::exchange::{Exchange, Fill, Resolution, TimeInForce};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// An order the mock accepted
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockOrder {
+    pub order_id: String,
+    pub event_id: String,
+    pub outcome: String,
+    pub shares: f64, // Shares requested (buys: amount / price)
+    pub price: f64,  // Limit price (buys) or minimum price (sells)
+    pub sell: bool,
+}
+
+/// `Exchange` that serves canned events, prices and settlements and records
+/// every order placed against it. Orders fill `fill_ratio` of their size at
+/// their limit price.
+pub struct MockExchange {
+    platform: Platform,
+    events: Vec<Event>,
+    prices: HashMap<String, MarketPrices>,
+    settlements: HashMap<String, Resolution>,
+    payouts: Mutex<HashMap<String, f64>>, // Reported payout per share, by event
+    balance: f64,
+    gas_balance: Option<f64>, // None for venues that don't pay gas
+    fill_ratio: f64,
+    order_status: String,
+    reject_orders: bool,
+    latency: Duration, // Delay before answering each call
+    orders: Mutex<Vec<MockOrder>>,
+    calls: AtomicUsize,     // Every trait call made
+    in_flight: AtomicUsize, // Calls currently waiting out `latency`
+    max_in_flight: AtomicUsize,
+}
+
+impl MockExchange {
+    pub fn new(platform: Platform) -> Self {
+        Self {
+            platform,
+            events: Vec::new(),
+            prices: HashMap::new(),
+            settlements: HashMap::new(),
+            payouts: Mutex::new(HashMap::new()),
+            balance: 10_000.0,
+            gas_balance: None,
+            fill_ratio: 1.0,
+            order_status: "matched".to_string(),
+            reject_orders: false,
+            latency: Duration::ZERO,
+            orders: Mutex::new(Vec::new()),
+            calls: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+            max_in_flight: AtomicUsize::new(0),
+        }
+    }
+
+    /// Events returned by `fetch_events`
+    pub fn with_events(mut self, events: Vec<Event>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Prices returned for `event_id` (other events error)
+    pub fn with_prices(mut self, event_id: &str, prices: MarketPrices) -> Self {
+        self.prices.insert(event_id.to_string(), prices);
+        self
+    }
+
+    /// Resolve `event_id`
+    pub fn with_settlement(mut self, event_id: &str, resolution: Resolution) -> Self {
+        self.settlements.insert(event_id.to_string(), resolution);
+        self
+    }
+
+    pub fn with_balance(mut self, balance: f64) -> Self {
+        self.balance = balance;
+        self
+    }
+
+    /// Native token balance reported for gas
+    pub fn with_gas_balance(mut self, gas_balance: f64) -> Self {
+        self.gas_balance = Some(gas_balance);
+        self
+    }
+
+    /// Fraction (0-1) of each order that fills
+    pub fn with_fill_ratio(mut self, fill_ratio: f64) -> Self {
+        self.fill_ratio = fill_ratio;
+        self
+    }
+
+    /// Status reported for every order (default "matched")
+    pub fn with_order_status(mut self, status: &str) -> Self {
+        self.order_status = status.to_string();
+        self
+    }
+
+    /// Fail every buy and sell
+    pub fn with_rejected_orders(mut self) -> Self {
+        self.reject_orders = true;
+        self
+    }
+
+    /// Wait `latency` before answering each call
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Report `per_share` as what each share of `event_id` paid out, from now on
+    pub fn set_payout(&self, event_id: &str, per_share: f64) {
+        self.payouts.lock().unwrap().insert(event_id.to_string(), per_share);
+    }
+
+    /// Orders placed so far, oldest first
+    pub fn orders(&self) -> Vec<MockOrder> {
+        self.orders.lock().unwrap().clone()
+    }
+
+    /// Trait calls made so far
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// Most calls ever in flight at once
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Count the call and wait out the configured latency
+    async fn call(&self) {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        if !self.latency.is_zero() {
+            tokio::time::sleep(self.latency).await;
+        }
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    fn record_order(&self, event_id: String, outcome: String, shares: f64, price: f64, sell: bool) -> BotResult<Option<String>> {
+        if self.reject_orders {
+            return Err(BotError::Api {
+                status: 400,
+                message: format!("Order rejected by mock {}", self.platform),
+            });
+        }
+        let mut orders = self.orders.lock().unwrap();
+        let order_id = format!("{}-{}", self.platform, orders.len() + 1);
+        orders.push(MockOrder {
+            order_id: order_id.clone(),
+            event_id,
+            outcome,
+            shares,
+            price,
+            sell,
+        });
+        Ok(Some(order_id))
+    }
+}
+
+#[async_trait]
+impl Exchange for MockExchange {
+    fn platform(&self) -> Platform {
+        self.platform
+    }
+
+    async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        self.call().await;
+        Ok(self.events.clone())
+    }
+
+    async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        self.call().await;
+        Ok(self
+            .prices
+            .get(event_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("No mock prices for {}", event_id))?)
+    }
+
+    async fn place_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        amount: f64,
+        price: f64,
+        _time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        self.call().await;
+        self.record_order(event_id, outcome, amount / price, price, false)
+    }
+
+    async fn sell_order(
+        &self,
+        event_id: String,
+        outcome: String,
+        shares: f64,
+        min_price: f64,
+    ) -> BotResult<Option<String>> {
+        self.call().await;
+        self.record_order(event_id, outcome, shares, min_price, true)
+    }
+
+    async fn cancel_order(&self, _order_id: &str) -> BotResult<()> {
+        self.call().await;
+        Ok(())
+    }
+
+    async fn get_order_status(&self, _order_id: &str) -> BotResult<String> {
+        self.call().await;
+        Ok(self.order_status.clone())
+    }
+
+    async fn get_fills(&self, order_id: &str) -> BotResult<Vec<Fill>> {
+        self.call().await;
+        let orders = self.orders.lock().unwrap();
+        let order = orders
+            .iter()
+            .find(|order| order.order_id == order_id)
+            .ok_or_else(|| anyhow!("Unknown mock order {}", order_id))?;
+        Ok(vec![Fill {
+            shares: order.shares * self.fill_ratio,
+            price: order.price,
+            fee: 0.0,
+        }])
+    }
+
+    async fn check_settlement(&self, event_id: &str) -> BotResult<Option<Resolution>> {
+        self.call().await;
+        Ok(self.settlements.get(event_id).copied())
+    }
+
+    async fn get_balance(&self) -> BotResult<f64> {
+        self.call().await;
+        Ok(self.balance)
+    }
+
+    async fn get_gas_balance(&self) -> BotResult<Option<f64>> {
+        self.call().await;
+        Ok(self.gas_balance)
+    }
+
+    async fn settlement_payout_per_share(&self, event_id: &str, _outcome: &str) -> BotResult<Option<f64>> {
+        self.call().await;
+        Ok(self.payouts.lock().unwrap().get(event_id).copied())
+    }
+}
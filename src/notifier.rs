@@ -0,0 +1,289 @@
+// Alerts for found opportunities, executed trades and balance problems (webhook, Telegram)
+
+use crate::arbitrage_detector::ArbitrageOpportunity;
+use crate::event::{Event, Platform};
+use crate::rebalance::RebalanceAction;
+use crate::trade_executor::TradeResult;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// An arbitrage opportunity was found
+    async fn notify_opportunity(
+        &self,
+        pm_event: &Event,
+        kalshi_event: &Event,
+        opportunity: &ArbitrageOpportunity,
+        trade_size: f64,
+    ) -> Result<()>;
+
+    /// A trade was attempted
+    async fn notify_trade(&self, result: &TradeResult) -> Result<()>;
+
+    /// A venue's balance dropped below the alert threshold
+    async fn notify_low_balance(&self, platform: Platform, balance: f64, threshold: f64) -> Result<()>;
+
+    /// Balances drifted far enough apart that collateral should be moved
+    async fn notify_rebalance(&self, action: &RebalanceAction) -> Result<()>;
+}
+
+fn http_client() -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_else(|_| Client::new())
+}
+
+/// POSTs a JSON payload per event to a URL (e.g. a Discord/Slack-compatible relay)
+pub struct WebhookNotifier {
+    http_client: Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String) -> Self {
+        Self {
+            http_client: http_client(),
+            url,
+        }
+    }
+
+    async fn post(&self, payload: serde_json::Value) -> Result<()> {
+        let response = self
+            .http_client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .context("Failed to send webhook")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Webhook returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify_opportunity(
+        &self,
+        pm_event: &Event,
+        kalshi_event: &Event,
+        opportunity: &ArbitrageOpportunity,
+        trade_size: f64,
+    ) -> Result<()> {
+        self.post(serde_json::json!({
+            "type": "opportunity",
+            "polymarket_event": { "id": pm_event.event_id, "title": pm_event.title },
+            "kalshi_event": { "id": kalshi_event.event_id, "title": kalshi_event.title },
+            "strategy": opportunity.strategy,
+            "net_profit": opportunity.net_profit,
+            "roi_percent": opportunity.roi_percent,
+            "trade_size": trade_size,
+        }))
+        .await
+    }
+
+    async fn notify_trade(&self, result: &TradeResult) -> Result<()> {
+        self.post(serde_json::json!({
+            "type": "trade",
+            "success": result.success,
+            "polymarket_order_id": result.polymarket_order_id,
+            "kalshi_order_id": result.kalshi_order_id,
+            "error": result.error,
+        }))
+        .await
+    }
+
+    async fn notify_low_balance(&self, platform: Platform, balance: f64, threshold: f64) -> Result<()> {
+        self.post(serde_json::json!({
+            "type": "low_balance",
+            "platform": platform,
+            "balance": balance,
+            "threshold": threshold,
+        }))
+        .await
+    }
+
+    async fn notify_rebalance(&self, action: &RebalanceAction) -> Result<()> {
+        self.post(serde_json::json!({
+            "type": "rebalance",
+            "from": action.from,
+            "to": action.to,
+            "amount": action.amount,
+        }))
+        .await
+    }
+}
+
+/// Sends messages to a Telegram chat through a bot
+pub struct TelegramNotifier {
+    http_client: Client,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramNotifier {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        Self {
+            http_client: http_client(),
+            bot_token,
+            chat_id,
+        }
+    }
+
+    async fn send_message(&self, text: String) -> Result<()> {
+        let response = self
+            .http_client
+            .post(format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token))
+            .json(&serde_json::json!({ "chat_id": self.chat_id, "text": text }))
+            .send()
+            .await
+            .context("Failed to send Telegram message")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Telegram returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    async fn notify_opportunity(
+        &self,
+        pm_event: &Event,
+        _kalshi_event: &Event,
+        opportunity: &ArbitrageOpportunity,
+        trade_size: f64,
+    ) -> Result<()> {
+        self.send_message(format!(
+            "🚨 Arbitrage: {}\n{}\nSize ${:.2}",
+            pm_event.title, opportunity, trade_size
+        ))
+        .await
+    }
+
+    async fn notify_trade(&self, result: &TradeResult) -> Result<()> {
+        let text = if result.success {
+            format!(
+                "✅ Trade executed - PM: {}, Kalshi: {}",
+                result.polymarket_order_id.as_deref().unwrap_or("-"),
+                result.kalshi_order_id.as_deref().unwrap_or("-")
+            )
+        } else {
+            format!("⚠️ Trade failed: {}", result.error.as_deref().unwrap_or("unknown error"))
+        };
+        self.send_message(text).await
+    }
+
+    async fn notify_low_balance(&self, platform: Platform, balance: f64, threshold: f64) -> Result<()> {
+        self.send_message(format!(
+            "🪫 Low {} balance: ${:.2} (alert below ${:.2})",
+            platform, balance, threshold
+        ))
+        .await
+    }
+
+    async fn notify_rebalance(&self, action: &RebalanceAction) -> Result<()> {
+        self.send_message(format!("⚖️ Rebalance suggested: {}", action)).await
+    }
+}
+
+/// Fans notifications out to every configured notifier in background tasks,
+/// so a slow or failing endpoint never holds up trading
+#[derive(Clone, Default)]
+pub struct Notifiers {
+    notifiers: Vec<Arc<dyn Notifier>>,
+}
+
+impl Notifiers {
+    pub fn new(notifiers: Vec<Arc<dyn Notifier>>) -> Self {
+        Self { notifiers }
+    }
+
+    /// `NOTIFY_WEBHOOK_URL` enables the webhook notifier; `TELEGRAM_BOT_TOKEN`
+    /// and `TELEGRAM_CHAT_ID` together enable Telegram
+    pub fn from_env() -> Self {
+        let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+
+        if let Ok(url) = std::env::var("NOTIFY_WEBHOOK_URL") {
+            notifiers.push(Arc::new(WebhookNotifier::new(url)));
+        }
+        if let (Ok(token), Ok(chat_id)) = (
+            std::env::var("TELEGRAM_BOT_TOKEN"),
+            std::env::var("TELEGRAM_CHAT_ID"),
+        ) {
+            notifiers.push(Arc::new(TelegramNotifier::new(token, chat_id)));
+        }
+
+        Self::new(notifiers)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notifiers.is_empty()
+    }
+
+    pub fn opportunity(
+        &self,
+        pm_event: &Event,
+        kalshi_event: &Event,
+        opportunity: &ArbitrageOpportunity,
+        trade_size: f64,
+    ) {
+        for notifier in &self.notifiers {
+            let notifier = notifier.clone();
+            let (pm_event, kalshi_event, opportunity) =
+                (pm_event.clone(), kalshi_event.clone(), opportunity.clone());
+            tokio::spawn(async move {
+                if let Err(e) = notifier
+                    .notify_opportunity(&pm_event, &kalshi_event, &opportunity, trade_size)
+                    .await
+                {
+                    warn!("Failed to send opportunity notification: {}", e);
+                }
+            });
+        }
+    }
+
+    pub fn trade(&self, result: &TradeResult) {
+        for notifier in &self.notifiers {
+            let notifier = notifier.clone();
+            let result = result.clone();
+            tokio::spawn(async move {
+                if let Err(e) = notifier.notify_trade(&result).await {
+                    warn!("Failed to send trade notification: {}", e);
+                }
+            });
+        }
+    }
+
+    pub fn low_balance(&self, platform: Platform, balance: f64, threshold: f64) {
+        for notifier in &self.notifiers {
+            let notifier = notifier.clone();
+            tokio::spawn(async move {
+                if let Err(e) = notifier.notify_low_balance(platform, balance, threshold).await {
+                    warn!("Failed to send low balance notification: {}", e);
+                }
+            });
+        }
+    }
+
+    pub fn rebalance(&self, action: &RebalanceAction) {
+        for notifier in &self.notifiers {
+            let notifier = notifier.clone();
+            let action = *action;
+            tokio::spawn(async move {
+                if let Err(e) = notifier.notify_rebalance(&action).await {
+                    warn!("Failed to send rebalance notification: {}", e);
+                }
+            });
+        }
+    }
+}
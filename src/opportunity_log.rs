@@ -0,0 +1,98 @@
+// Append-only JSONL record of every opportunity the bot detected
+
+use crate::arbitrage_detector::ArbitrageOpportunity;
+use crate::event::Event;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One detected opportunity. Leg prices, profit and ROI are in `opportunity`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpportunityRecord {
+    pub timestamp: DateTime<Utc>,
+    pub pm_event: Event,
+    pub kalshi_event: Event,
+    pub opportunity: ArbitrageOpportunity,
+    pub trade_size: f64,
+    pub traded: bool, // Whether the trade was executed successfully
+}
+
+impl OpportunityRecord {
+    pub fn new(
+        pm_event: &Event,
+        kalshi_event: &Event,
+        opportunity: &ArbitrageOpportunity,
+        trade_size: f64,
+        traded: bool,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            pm_event: pm_event.clone(),
+            kalshi_event: kalshi_event.clone(),
+            opportunity: opportunity.clone(),
+            trade_size,
+            traded,
+        }
+    }
+}
+
+/// Appends one JSON line per opportunity, flushed as it is written so the
+/// file survives a crash
+pub struct OpportunityLogger {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl OpportunityLogger {
+    /// Open `path` for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open opportunity log {}", path.display()))?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `record` as a single line
+    pub fn log(&self, record: &OpportunityRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.flush())
+            .with_context(|| format!("Failed to write opportunity log {}", self.path.display()))
+    }
+
+    /// Read back every record in a log file, oldest first
+    pub fn read(path: impl AsRef<Path>) -> Result<Vec<OpportunityRecord>> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open opportunity log {}", path.display()))?;
+
+        BufReader::new(file)
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+            .map(|(i, line)| {
+                let line = line?;
+                serde_json::from_str(&line)
+                    .with_context(|| format!("Bad record on line {} of {}", i + 1, path.display()))
+            })
+            .collect()
+    }
+}
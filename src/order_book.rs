@@ -0,0 +1,198 @@
+use crate::event::{MarketPrices, MarketQuote, Outcome};
+
+/// One side of an order book as (price, size) levels, best price first
+#[derive(Debug, Clone, Default)]
+pub struct MarketDepth {
+    pub levels: Vec<(f64, f64)>,
+}
+
+impl MarketDepth {
+    pub fn new(levels: Vec<(f64, f64)>) -> Self {
+        Self { levels }
+    }
+
+    pub fn best_price(&self) -> Option<f64> {
+        self.levels.first().map(|(price, _)| *price)
+    }
+
+    /// Total USDC notional across all levels
+    pub fn total_notional(&self) -> f64 {
+        self.levels.iter().map(|(price, size)| price * size).sum()
+    }
+
+    /// USDC notional resting within `range` (in dollars) of the best price
+    pub fn notional_within(&self, range: f64) -> f64 {
+        let Some(best) = self.best_price() else {
+            return 0.0;
+        };
+
+        self.levels
+            .iter()
+            .take_while(|(price, _)| (price - best).abs() <= range + f64::EPSILON)
+            .map(|(price, size)| price * size)
+            .sum()
+    }
+
+    /// Volume-weighted average price for filling `notional` USDC against these levels.
+    /// Returns `None` if the levels don't hold enough depth.
+    pub fn vwap_for_notional(&self, notional: f64) -> Option<f64> {
+        if notional <= 0.0 {
+            return None;
+        }
+
+        let mut remaining = notional;
+        let mut shares = 0.0;
+
+        for (price, size) in &self.levels {
+            let spend = remaining.min(price * size);
+            shares += spend / price;
+            remaining -= spend;
+
+            if remaining <= 1e-9 {
+                return Some(notional / shares);
+            }
+        }
+
+        None
+    }
+}
+
+/// Order book for a binary market, expressed in YES terms.
+/// A NO bid at p is equivalent to a YES ask at 1 - p, so one book covers both outcomes.
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    pub bids: MarketDepth, // Highest first
+    pub asks: MarketDepth, // Lowest first
+}
+
+impl OrderBook {
+    /// Build a book from unsorted levels, sorting each side best-first
+    pub fn new(mut bids: Vec<(f64, f64)>, mut asks: Vec<(f64, f64)>) -> Self {
+        bids.retain(|(price, size)| *price > 0.0 && *size > 0.0);
+        asks.retain(|(price, size)| *price > 0.0 && *size > 0.0);
+        bids.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        asks.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        Self {
+            bids: MarketDepth::new(bids),
+            asks: MarketDepth::new(asks),
+        }
+    }
+
+    /// The same book seen from the NO side
+    pub fn complement(&self) -> Self {
+        let flip = |depth: &MarketDepth| {
+            MarketDepth::new(
+                depth
+                    .levels
+                    .iter()
+                    .map(|(price, size)| (1.0 - price, *size))
+                    .collect(),
+            )
+        };
+
+        Self {
+            bids: flip(&self.asks),
+            asks: flip(&self.bids),
+        }
+    }
+
+    /// Average YES price when buying `size_usdc` worth, walking the asks.
+    /// Use `complement()` first to price the NO side.
+    pub fn vwap_for_size(&self, size_usdc: f64) -> Option<f64> {
+        self.asks.vwap_for_notional(size_usdc)
+    }
+
+    /// USDC notional available within `range` of the best price on both sides
+    pub fn liquidity_within(&self, range: f64) -> f64 {
+        self.bids.notional_within(range) + self.asks.notional_within(range)
+    }
+
+    /// Quote both outcomes at their best bids (a NO bid is a YES ask), with
+    /// depth-based liquidity and this book attached for bid/ask and VWAP pricing
+    pub fn to_market_quote(&self, depth_range: f64) -> MarketQuote {
+        let yes = self.bids.best_price().unwrap_or(0.0);
+        let no = self.asks.best_price().map(|ask| 1.0 - ask).unwrap_or(0.0);
+
+        MarketQuote::new(
+            vec![Outcome::new("YES", yes), Outcome::new("NO", no)],
+            self.liquidity_within(depth_range),
+        )
+        .with_book(self.clone())
+    }
+
+    /// Binary view of `to_market_quote`
+    pub fn to_market_prices(&self, depth_range: f64) -> MarketPrices {
+        self.to_market_quote(depth_range)
+            .binary_prices()
+            .expect("order book quotes are binary")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::{parse_clob_book, parse_kalshi_book};
+
+    #[test]
+    fn clob_book_sorts_levels_best_first() {
+        let data = serde_json::json!({
+            "bids": [{"price": "0.38", "size": "50"}, {"price": "0.40", "size": "100"}, {"price": "0.39", "size": "0"}],
+            "asks": [{"price": 0.47, "size": 10}, {"price": "0.45", "size": "200"}],
+        });
+
+        let book = parse_clob_book(&data);
+
+        assert_eq!(book.bids.levels, [(0.40, 100.0), (0.38, 50.0)]);
+        assert_eq!(book.asks.levels, [(0.45, 200.0), (0.47, 10.0)]);
+    }
+
+    #[test]
+    fn kalshi_no_bids_become_yes_asks() {
+        let data = serde_json::json!({"orderbook": {"yes": [[38, 50], [40, 100]], "no": [[55, 200]]}});
+
+        let book = parse_kalshi_book(&data);
+
+        assert_eq!(book.bids.levels, [(0.40, 100.0), (0.38, 50.0)]);
+        assert_eq!(book.asks.levels.len(), 1);
+        assert!((book.asks.levels[0].0 - 0.45).abs() < 1e-9);
+        assert_eq!(book.asks.levels[0].1, 200.0);
+    }
+
+    #[test]
+    fn liquidity_counts_levels_within_range_of_the_best() {
+        let book = OrderBook::new(vec![(0.40, 100.0), (0.39, 100.0), (0.30, 100.0)], vec![(0.45, 100.0)]);
+
+        assert!((book.liquidity_within(0.01) - (40.0 + 39.0 + 45.0)).abs() < 1e-9);
+        assert!((book.bids.total_notional() - 109.0).abs() < 1e-9);
+        assert_eq!(OrderBook::default().liquidity_within(0.05), 0.0);
+    }
+
+    #[test]
+    fn vwap_walks_into_the_next_level() {
+        let book = OrderBook::new(Vec::new(), vec![(0.50, 100.0), (0.40, 100.0)]);
+
+        // $40 buys the 0.40 level whole, the other $20 buys 40 shares at 0.50
+        let vwap = book.vwap_for_size(60.0).unwrap();
+        assert!((vwap - 60.0 / 140.0).abs() < 1e-9);
+        assert_eq!(book.vwap_for_size(40.0), Some(0.40));
+    }
+
+    #[test]
+    fn vwap_is_none_without_enough_depth() {
+        let book = OrderBook::new(Vec::new(), vec![(0.40, 100.0), (0.50, 100.0)]);
+
+        assert_eq!(book.vwap_for_size(90.5), None);
+        assert_eq!(book.vwap_for_size(0.0), None);
+        assert_eq!(OrderBook::default().vwap_for_size(1.0), None);
+    }
+
+    #[test]
+    fn complement_prices_the_no_side() {
+        // YES bids at 0.60 are NO asks at 0.40
+        let book = OrderBook::new(vec![(0.60, 100.0), (0.55, 100.0)], Vec::new());
+
+        let vwap = book.complement().vwap_for_size(85.0).unwrap();
+        assert!((vwap - 85.0 / 200.0).abs() < 1e-9);
+    }
+}
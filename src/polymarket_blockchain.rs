@@ -1,32 +1,353 @@
 // Polymarket blockchain integration using ethers-rs
 // Handles Polygon blockchain interactions for Polymarket trading
 
+use crate::error::BotError;
+use crate::exchange::{Fill, Resolution, TimeInForce};
 use anyhow::{Context, Result};
-use ethers::providers::{Provider, Http, Middleware};
+use async_trait::async_trait;
+use ethers::providers::{
+    Http, JsonRpcClient, Middleware, MiddlewareError, PendingTransaction, Provider,
+    RpcError,
+};
 use ethers::signers::{LocalWallet, Signer};
 use ethers::middleware::SignerMiddleware;
-use ethers::types::{Address, U256, H256, TransactionRequest};
+use ethers::types::{Address, BlockNumber, U256, H256, TransactionRequest, Eip1559TransactionRequest};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::transaction::eip712::TypedData;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::future::Future;
 use std::str::FromStr;
-use tracing::{info, warn, error};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Semaphore};
+use tracing::{info, warn};
 
-/// Polymarket blockchain client for Polygon network
-pub struct PolymarketBlockchain {
-    provider: Provider<Http>,
+/// Polymarket CTF Exchange contract on Polygon (EIP-712 verifying contract for CLOB orders)
+const CTF_EXCHANGE_ADDRESS: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+
+/// USDC (PoS) token on Polygon - 6 decimals
+const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+
+/// Gnosis ConditionalTokens contract on Polygon (holds market payouts once the UMA oracle reports)
+const CONDITIONAL_TOKENS_ADDRESS: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+
+/// Polymarket CLOB REST API
+pub const DEFAULT_CLOB_URL: &str = "https://clob.polymarket.com";
+
+/// Typical gas used by a single order transaction on Polygon
+const ORDER_GAS_LIMIT: u64 = 200_000;
+
+const CLOB_SIDE_BUY: u8 = 0;
+const CLOB_SIDE_SELL: u8 = 1;
+const CLOB_SIGNATURE_TYPE_EOA: u8 = 0;
+
+/// L2 API credentials for the Polymarket CLOB
+#[derive(Clone)]
+pub struct ClobCredentials {
+    pub api_key: String,
+    pub api_secret: String, // base64 (URL-safe) encoded HMAC secret
+    pub passphrase: String,
+}
+
+// Manual impl so logging a client never prints the secret or passphrase
+impl std::fmt::Debug for ClobCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClobCredentials")
+            .field("api_key", &self.api_key)
+            .field("api_secret", &"<redacted>")
+            .field("passphrase", &"<redacted>")
+            .finish()
+    }
+}
+
+impl ClobCredentials {
+    /// Build the POLY_* authentication headers for a CLOB request
+    fn auth_headers(
+        &self,
+        address: Address,
+        method: &str,
+        path: &str,
+        body: &str,
+    ) -> Result<reqwest::header::HeaderMap> {
+        use base64::{engine::general_purpose, Engine as _};
+        use hmac::{Hmac, Mac};
+        use reqwest::header::{HeaderMap, HeaderValue};
+        use sha2::Sha256;
+
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let secret = general_purpose::URL_SAFE
+            .decode(&self.api_secret)
+            .context("CLOB API secret is not valid base64")?;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(&secret)
+            .context("Invalid CLOB API secret")?;
+        mac.update(format!("{}{}{}{}", timestamp, method, path, body).as_bytes());
+        let signature = general_purpose::URL_SAFE.encode(mac.finalize().into_bytes());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("POLY_ADDRESS", HeaderValue::from_str(&format!("{:?}", address))?);
+        headers.insert("POLY_API_KEY", HeaderValue::from_str(&self.api_key)?);
+        headers.insert("POLY_PASSPHRASE", HeaderValue::from_str(&self.passphrase)?);
+        headers.insert("POLY_TIMESTAMP", HeaderValue::from_str(&timestamp)?);
+        headers.insert("POLY_SIGNATURE", HeaderValue::from_str(&signature)?);
+
+        Ok(headers)
+    }
+}
+
+/// CLOB order as signed under the CTF Exchange EIP-712 domain
+#[derive(Debug, Clone)]
+pub struct ClobOrder {
+    pub salt: U256,
+    pub maker: Address,
+    pub signer: Address,
+    pub taker: Address,
+    pub token_id: U256,
+    pub maker_amount: U256,
+    pub taker_amount: U256,
+    pub expiration: U256,
+    pub nonce: U256,
+    pub fee_rate_bps: U256,
+    pub side: u8,
+    pub signature_type: u8,
+}
+
+impl ClobOrder {
+    /// JSON form shared by the EIP-712 message and the API payload
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "salt": self.salt.to_string(),
+            "maker": format!("{:?}", self.maker),
+            "signer": format!("{:?}", self.signer),
+            "taker": format!("{:?}", self.taker),
+            "tokenId": self.token_id.to_string(),
+            "makerAmount": self.maker_amount.to_string(),
+            "takerAmount": self.taker_amount.to_string(),
+            "expiration": self.expiration.to_string(),
+            "nonce": self.nonce.to_string(),
+            "feeRateBps": self.fee_rate_bps.to_string(),
+            "side": self.side,
+            "signatureType": self.signature_type,
+        })
+    }
+}
+
+/// Fees for a Polygon transaction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GasFees {
+    Eip1559 {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+    Legacy {
+        gas_price: U256,
+    }, // RPCs without EIP-1559 support
+}
+
+impl GasFees {
+    /// Highest price per gas unit the transaction may pay
+    pub fn max_price_per_gas(&self) -> U256 {
+        match self {
+            GasFees::Eip1559 { max_fee_per_gas, .. } => *max_fee_per_gas,
+            GasFees::Legacy { gas_price } => *gas_price,
+        }
+    }
+
+    /// Scale the tip by `multiplier`, raising the fee cap by the same amount
+    /// so it always covers the tip
+    fn with_multiplier(self, multiplier: f64) -> Self {
+        let scale = |value: U256| {
+            // Work in basis points to keep U256 math integral
+            value * U256::from((multiplier * 10_000.0).round().max(0.0) as u64) / U256::from(10_000u64)
+        };
+
+        match self {
+            GasFees::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas } => {
+                let bumped = scale(max_priority_fee_per_gas);
+                GasFees::Eip1559 {
+                    max_fee_per_gas: max_fee_per_gas.saturating_sub(max_priority_fee_per_gas) + bumped,
+                    max_priority_fee_per_gas: bumped,
+                }
+            }
+            GasFees::Legacy { gas_price } => GasFees::Legacy { gas_price: scale(gas_price) },
+        }
+    }
+}
+
+/// JSON-RPC transport over several endpoints. Requests go to the endpoint
+/// that last succeeded; a transport failure (timeout, 5xx, garbled response)
+/// moves on to the next one. RPC-level errors such as reverts are returned as-is.
+#[derive(Debug, Clone)]
+pub struct Failover<C> {
+    endpoints: Arc<Vec<C>>,
+    current: Arc<AtomicUsize>,
+}
+
+/// Failover between HTTP RPC URLs
+pub type FailoverHttp = Failover<Http>;
+
+impl Failover<Http> {
+    pub fn new(rpc_urls: &[String]) -> Result<Self> {
+        let endpoints = rpc_urls
+            .iter()
+            .map(|url| Http::from_str(url).with_context(|| format!("Invalid RPC URL: {}", url)))
+            .collect::<Result<Vec<_>>>()?;
+
+        Self::with_endpoints(endpoints)
+    }
+}
+
+impl<C> Failover<C> {
+    /// Fail over between already-built transports, trying them in order
+    pub fn with_endpoints(endpoints: Vec<C>) -> Result<Self> {
+        if endpoints.is_empty() {
+            return Err(anyhow::anyhow!("At least one Polygon RPC URL is required"));
+        }
+
+        Ok(Self {
+            endpoints: Arc::new(endpoints),
+            current: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+}
+
+#[async_trait]
+impl<C: JsonRpcClient> JsonRpcClient for Failover<C> {
+    type Error = C::Error;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let start = self.current.load(Ordering::Relaxed);
+        let mut last_error = None;
+
+        for offset in 0..self.endpoints.len() {
+            let index = (start + offset) % self.endpoints.len();
+            // Borrowed so the same params can be re-sent to each endpoint
+            match self.endpoints[index].request(method, &params).await {
+                Ok(response) => {
+                    self.current.store(index, Ordering::Relaxed);
+                    return Ok(response);
+                }
+                Err(e) if e.as_error_response().is_some() => return Err(e),
+                Err(e) => {
+                    warn!("RPC endpoint #{} failed for {}: {}", index, method, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error.expect("Failover always has at least one endpoint"))
+    }
+}
+
+/// Hands out sequential nonces for one account so transactions sent close
+/// together (e.g. an approval and an order) never reuse a nonce
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    next: Mutex<Option<U256>>, // None until fetched, or after a resync
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Next nonce to use, calling `fetch_pending` for the account's pending
+    /// nonce on first use (or after `resync`)
+    pub async fn next_nonce<F, Fut>(&self, fetch_pending: F) -> Result<U256>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<U256>>,
+    {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => fetch_pending().await?,
+        };
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Forget the cached nonce so the next call re-reads it from the chain
+    pub async fn resync(&self) {
+        *self.next.lock().await = None;
+    }
+}
+
+/// Polymarket blockchain client for Polygon network, over any JSON-RPC
+/// transport (failover HTTP unless built with `with_provider`)
+pub struct PolymarketBlockchain<P = FailoverHttp> {
+    provider: Provider<P>,
     wallet: Option<LocalWallet>,
     chain_id: u64,
+    clob_credentials: Option<ClobCredentials>,
+    gas_multiplier: f64, // Applied to the priority fee (or legacy gas price)
+    nonce_manager: Arc<NonceManager>,
+    clob_base_url: String,
+    request_limit: Option<Arc<Semaphore>>, // Shared cap on in-flight HTTP requests
 }
 
 impl PolymarketBlockchain {
     /// Create a new blockchain client
     pub fn new(rpc_url: &str) -> Result<Self> {
-        let provider = Provider::<Http>::try_from(rpc_url)
-            .context("Failed to create Polygon provider")?;
-        
-        Ok(Self {
+        Self::with_rpcs(&[rpc_url.to_string()])
+    }
+
+    /// Create a client that fails over between `rpc_urls`, in order
+    pub fn with_rpcs(rpc_urls: &[String]) -> Result<Self> {
+        let provider = Provider::new(
+            FailoverHttp::new(rpc_urls).context("Failed to create Polygon provider")?,
+        );
+        Ok(Self::with_provider(provider))
+    }
+}
+
+impl<P: JsonRpcClient + Clone + 'static> PolymarketBlockchain<P> {
+    /// Create a client on an existing provider
+    pub fn with_provider(provider: Provider<P>) -> Self {
+        Self {
             provider,
             wallet: None,
             chain_id: 137, // Polygon mainnet chain ID
-        })
+            clob_credentials: None,
+            gas_multiplier: 1.0,
+            nonce_manager: Arc::new(NonceManager::new()),
+            clob_base_url: DEFAULT_CLOB_URL.to_string(),
+            request_limit: None,
+        }
+    }
+
+    /// Hold a permit from `request_limit` for every CLOB request
+    pub fn with_request_limit(mut self, request_limit: Arc<Semaphore>) -> Self {
+        self.request_limit = Some(request_limit);
+        self
+    }
+
+    /// Send a CLOB request within the request limit. Never retried - an order
+    /// may have been placed even if the response was lost.
+    async fn send_clob(&self, request: reqwest::RequestBuilder) -> reqwest::Result<reqwest::Response> {
+        // Never closed, so acquiring only fails if the semaphore is dropped
+        let _permit = match &self.request_limit {
+            Some(semaphore) => semaphore.acquire().await.ok(),
+            None => None,
+        };
+        request.send().await
+    }
+
+    /// Send CLOB requests to `base_url` instead of the production API
+    pub fn with_clob_base_url(mut self, base_url: String) -> Self {
+        self.clob_base_url = base_url;
+        self
+    }
+
+    /// Bump the priority fee by `multiplier` (e.g. 1.25) to get included faster
+    pub fn with_gas_multiplier(mut self, multiplier: f64) -> Self {
+        self.gas_multiplier = multiplier.max(1.0);
+        self
     }
 
     /// Load wallet from private key
@@ -52,7 +373,7 @@ impl PolymarketBlockchain {
     /// USDC has 6 decimals (not 18!)
     pub async fn get_usdc_balance(&self) -> Result<f64> {
         let address = self.address()?;
-        let usdc_address: Address = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174"
+        let usdc_address: Address = USDC_ADDRESS
             .parse()
             .context("Invalid USDC contract address")?;
 
@@ -67,14 +388,15 @@ impl PolymarketBlockchain {
         
         // Pad address to 32 bytes
         let mut address_bytes = [0u8; 32];
-        address_bytes[12..].copy_from_slice(&address.as_bytes());
+        address_bytes[12..].copy_from_slice(address.as_bytes());
         data.extend_from_slice(&address_bytes);
 
         // Call the contract
         let result = self.provider.call(
             &TransactionRequest::new()
                 .to(usdc_address)
-                .data(data.into()),
+                .data(data)
+                .into(),
             None,
         ).await
         .context("Failed to call USDC balanceOf")?;
@@ -90,45 +412,344 @@ impl PolymarketBlockchain {
         }
     }
 
+    /// USDC `spender` may move on behalf of the wallet (6 decimals)
+    pub async fn usdc_allowance(&self, spender: Address) -> Result<U256> {
+        let owner = self.address()?;
+        let usdc_address: Address = USDC_ADDRESS
+            .parse()
+            .context("Invalid USDC contract address")?;
+
+        // allowance(address owner, address spender) -> uint256
+        let mut data = ethers::utils::id("allowance(address,address)").to_vec();
+        data.extend_from_slice(H256::from(owner).as_bytes());
+        data.extend_from_slice(H256::from(spender).as_bytes());
+
+        let result = self.provider.call(
+            &TransactionRequest::new()
+                .to(usdc_address)
+                .data(data)
+                .into(),
+            None,
+        ).await
+        .context("Failed to call USDC allowance")?;
+
+        if result.len() < 32 {
+            return Err(anyhow::anyhow!("Invalid allowance response from USDC contract"));
+        }
+        Ok(U256::from_big_endian(&result[..32]))
+    }
+
+    /// Approve `spender` to move `amount` USDC and wait for the transaction to be mined
+    pub async fn approve_usdc(&self, spender: Address, amount: U256) -> Result<H256> {
+        let usdc_address: Address = USDC_ADDRESS
+            .parse()
+            .context("Invalid USDC contract address")?;
+
+        // approve(address spender, uint256 amount)
+        let mut data = ethers::utils::id("approve(address,uint256)").to_vec();
+        data.extend_from_slice(H256::from(spender).as_bytes());
+        let mut amount_bytes = [0u8; 32];
+        amount.to_big_endian(&mut amount_bytes);
+        data.extend_from_slice(&amount_bytes);
+
+        let tx_hash = self.send_transaction(usdc_address, data).await
+            .context("USDC approval failed")?;
+        info!("Sent USDC approval for {:?}: {:?}", spender, tx_hash);
+
+        let receipt = PendingTransaction::new(tx_hash, &self.provider).await
+            .context("Failed waiting for USDC approval")?
+            .context("USDC approval dropped from mempool")?;
+        if receipt.status != Some(1.into()) {
+            let reason = self
+                .transaction_failure_reason(&format!("{:?}", tx_hash))
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| "unknown reason".to_string());
+            return Err(BotError::Blockchain(format!(
+                "USDC approval reverted (tx {:?}): {}",
+                tx_hash, reason
+            ))
+            .into());
+        }
+
+        Ok(tx_hash)
+    }
+
+    /// Make sure the CTF exchange can spend at least `amount` USDC, approving an
+    /// unlimited allowance once if it can't. Returns the approval hash if one was sent.
+    pub async fn ensure_exchange_allowance(&self, amount: f64) -> Result<Option<H256>> {
+        let exchange: Address = CTF_EXCHANGE_ADDRESS
+            .parse()
+            .context("Invalid CTF exchange address")?;
+        let required = U256::from((amount * 1_000_000.0).ceil() as u128);
+
+        if self.usdc_allowance(exchange).await? >= required {
+            return Ok(None);
+        }
+
+        info!("🔓 USDC allowance for the exchange is too low, approving");
+        self.approve_usdc(exchange, U256::MAX).await.map(Some)
+    }
+
+    /// Native MATIC balance, used to pay gas (18 decimals)
+    pub async fn get_native_balance(&self) -> Result<f64> {
+        let address = self.address()?;
+        let balance = self.provider.get_balance(address, None).await
+            .context("Failed to get MATIC balance")?;
+        Ok(balance.as_u128() as f64 / 1e18)
+    }
+
+    /// Attach CLOB API credentials (required for authenticated order placement)
+    pub fn with_clob_credentials(mut self, credentials: ClobCredentials) -> Self {
+        self.clob_credentials = Some(credentials);
+        self
+    }
+
+    /// Build a BUY order for the CLOB from a dollar amount and limit price.
+    /// USDC and conditional tokens both use 6 decimals.
+    pub fn build_clob_order(&self, token_id: &str, amount: f64, price: f64) -> Result<ClobOrder> {
+        let maker = self.address()?;
+        let token_id = U256::from_dec_str(token_id)
+            .context("CLOB token ID must be a decimal uint256")?;
+
+        if price <= 0.0 {
+            return Err(BotError::InvalidPrice(price.to_string()).into());
+        }
+
+        Ok(ClobOrder {
+            salt: U256::from(rand::random::<u64>()),
+            maker,
+            signer: maker,
+            taker: Address::zero(),
+            token_id,
+            maker_amount: U256::from((amount * 1_000_000.0).round() as u128),
+            taker_amount: U256::from((amount / price * 1_000_000.0).round() as u128),
+            expiration: U256::zero(),
+            nonce: U256::zero(),
+            fee_rate_bps: U256::zero(),
+            side: CLOB_SIDE_BUY,
+            signature_type: CLOB_SIGNATURE_TYPE_EOA,
+        })
+    }
+
+    /// Build a SELL order for `shares` tokens at a minimum price per share
+    pub fn build_clob_sell_order(&self, token_id: &str, shares: f64, min_price: f64) -> Result<ClobOrder> {
+        let mut order = self.build_clob_order(token_id, shares * min_price, min_price)?;
+        // Selling swaps the sides: we give tokens and receive USDC
+        std::mem::swap(&mut order.maker_amount, &mut order.taker_amount);
+        order.side = CLOB_SIDE_SELL;
+        Ok(order)
+    }
+
+    /// Build the EIP-712 typed data for a CLOB order (CTF Exchange domain on Polygon)
+    pub fn clob_order_typed_data(&self, order: &ClobOrder) -> Result<TypedData> {
+        let typed_data = serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "Order": [
+                    { "name": "salt", "type": "uint256" },
+                    { "name": "maker", "type": "address" },
+                    { "name": "signer", "type": "address" },
+                    { "name": "taker", "type": "address" },
+                    { "name": "tokenId", "type": "uint256" },
+                    { "name": "makerAmount", "type": "uint256" },
+                    { "name": "takerAmount", "type": "uint256" },
+                    { "name": "expiration", "type": "uint256" },
+                    { "name": "nonce", "type": "uint256" },
+                    { "name": "feeRateBps", "type": "uint256" },
+                    { "name": "side", "type": "uint8" },
+                    { "name": "signatureType", "type": "uint8" },
+                ],
+            },
+            "primaryType": "Order",
+            "domain": {
+                "name": "Polymarket CTF Exchange",
+                "version": "1",
+                "chainId": self.chain_id,
+                "verifyingContract": CTF_EXCHANGE_ADDRESS,
+            },
+            "message": order.to_json(),
+        });
+
+        serde_json::from_value(typed_data).context("Failed to build CLOB order typed data")
+    }
+
     /// Place order via Polymarket CLOB API (recommended method)
     /// This uses Polymarket's centralized order book API which handles blockchain interaction
     pub async fn place_order_via_clob(
         &self,
-        _http_client: &reqwest::Client,
+        http_client: &reqwest::Client,
         market_id: &str,
         outcome: &str, // "YES" or "NO"
         amount: f64,
         price: f64,
+        time_in_force: TimeInForce,
+    ) -> Result<Option<String>> {
+        info!(
+            "Submitting CLOB order: {} {} @ ${:.4} (amount: ${:.2}, {:?})",
+            market_id, outcome, price, amount, time_in_force
+        );
+
+        let order = self.build_clob_order(market_id, amount, price)?;
+        self.submit_clob_order(http_client, order, "BUY", time_in_force).await
+    }
+
+    /// Sell `shares` tokens on the CLOB at no less than `min_price` each
+    pub async fn place_sell_order_via_clob(
+        &self,
+        http_client: &reqwest::Client,
+        market_id: &str,
+        shares: f64,
+        min_price: f64,
+    ) -> Result<Option<String>> {
+        info!(
+            "Submitting CLOB sell order: {} {:.2} shares @ ${:.4}",
+            market_id, shares, min_price
+        );
+
+        let order = self.build_clob_sell_order(market_id, shares, min_price)?;
+        self.submit_clob_order(http_client, order, "SELL", TimeInForce::Ioc).await
+    }
+
+    /// Sign `order` and post it to the CLOB, returning the order ID
+    async fn submit_clob_order(
+        &self,
+        http_client: &reqwest::Client,
+        mut order: ClobOrder,
+        side: &str,
+        time_in_force: TimeInForce,
     ) -> Result<Option<String>> {
         // Polymarket CLOB API endpoint
-        let url = "https://clob.polymarket.com/orders";
-        
-        // Create order payload
-        // Note: This requires proper authentication and signature
-        // Polymarket CLOB uses wallet signature for authentication
+        let url = format!("{}/orders", self.clob_base_url);
+
         let wallet = self.wallet.as_ref()
             .context("Wallet required for CLOB orders")?;
+        let credentials = self.clob_credentials.as_ref()
+            .context("CLOB API credentials required. Set POLYMARKET_API_KEY, POLYMARKET_API_SECRET and POLYMARKET_API_PASSPHRASE")?;
 
-        // Create order message to sign
-        let timestamp = chrono::Utc::now().timestamp();
-        let order_data = serde_json::json!({
-            "market": market_id,
-            "side": "buy",
-            "outcome": outcome,
-            "amount": amount,
-            "price": price,
-            "timestamp": timestamp,
-        });
+        // GTD expiry is part of the signed order
+        if let Some(expiration) = time_in_force.expiration_ts() {
+            order.expiration = U256::from(expiration.max(0) as u64);
+        }
 
         // Sign the order (Polymarket uses EIP-712 signing)
-        // This is a simplified version - actual implementation needs EIP-712
-        warn!("CLOB API order placement requires EIP-712 signing. Using placeholder.");
-        
-        // For now, return error indicating need for EIP-712 implementation
-        Err(anyhow::anyhow!(
-            "Polymarket CLOB API requires EIP-712 signature. \
-            Use place_order_via_blockchain for direct contract interaction."
-        ))
+        let typed_data = self.clob_order_typed_data(&order)?;
+        let signature = wallet.sign_typed_data(&typed_data).await
+            .context("Failed to sign CLOB order")?;
+
+        let mut signed_order = order.to_json();
+        signed_order["side"] = serde_json::json!(side);
+        signed_order["signature"] = serde_json::json!(format!("0x{}", signature));
+
+        let payload = serde_json::json!({
+            "order": signed_order,
+            "owner": credentials.api_key,
+            "orderType": clob_order_type(time_in_force),
+        });
+        let body = serde_json::to_string(&payload)?;
+
+        let request = http_client
+            .post(&url)
+            .headers(credentials.auth_headers(wallet.address(), "POST", "/orders", &body)?)
+            .header("Content-Type", "application/json")
+            .body(body);
+        let response = self
+            .send_clob(request)
+            .await
+            .context("Failed to submit CLOB order")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::from_status(status, format!("CLOB order failed: {}", error_text)).into());
+        }
+
+        let data: serde_json::Value = response
+            .json()
+            .await
+            .context("Failed to parse CLOB order response")?;
+
+        if data["success"].as_bool() == Some(false) {
+            return Err(anyhow::anyhow!(
+                "CLOB order rejected: {}",
+                data["errorMsg"].as_str().unwrap_or("unknown error")
+            ));
+        }
+
+        Ok(data["orderID"].as_str().map(|s| s.to_string()))
+    }
+
+    /// Fetch an order from the CLOB (`GET /data/order/{id}`)
+    pub async fn get_clob_order(
+        &self,
+        http_client: &reqwest::Client,
+        order_id: &str,
+    ) -> Result<serde_json::Value> {
+        let wallet = self.wallet.as_ref()
+            .context("Wallet required for CLOB requests")?;
+        let credentials = self.clob_credentials.as_ref()
+            .context("CLOB API credentials required to look up orders")?;
+
+        let path = format!("/data/order/{}", order_id);
+        let request = http_client
+            .get(format!("{}{}", self.clob_base_url, path))
+            .headers(credentials.auth_headers(wallet.address(), "GET", &path, "")?);
+        let response = self
+            .send_clob(request)
+            .await
+            .context("Failed to fetch CLOB order")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::from_status(status, format!("CLOB order lookup failed: {}", error_text)).into());
+        }
+
+        response.json().await.context("Failed to parse CLOB order")
+    }
+
+    /// Cancel a resting CLOB order (`DELETE /order`)
+    pub async fn cancel_clob_order(&self, http_client: &reqwest::Client, order_id: &str) -> Result<()> {
+        let wallet = self.wallet.as_ref()
+            .context("Wallet required for CLOB requests")?;
+        let credentials = self.clob_credentials.as_ref()
+            .context("CLOB API credentials required to cancel orders")?;
+
+        let body = serde_json::json!({ "orderID": order_id }).to_string();
+        let request = http_client
+            .delete(format!("{}/order", self.clob_base_url))
+            .headers(credentials.auth_headers(wallet.address(), "DELETE", "/order", &body)?)
+            .header("Content-Type", "application/json")
+            .body(body);
+        let response = self
+            .send_clob(request)
+            .await
+            .context("Failed to cancel CLOB order")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::from_status(status, format!("CLOB cancel failed: {}", error_text)).into());
+        }
+
+        info!("Cancelled CLOB order {}", order_id);
+        Ok(())
+    }
+
+    /// Matched size of a CLOB order as a single fill at the order's price
+    pub async fn get_clob_fills(
+        &self,
+        http_client: &reqwest::Client,
+        order_id: &str,
+    ) -> Result<Vec<Fill>> {
+        let order = self.get_clob_order(http_client, order_id).await?;
+        Ok(clob_order_fills(&order))
     }
 
     /// Place order via direct blockchain contract interaction
@@ -144,7 +765,7 @@ impl PolymarketBlockchain {
             .context("Wallet required for blockchain orders")?;
 
         // Create signer middleware
-        let client = SignerMiddleware::new(self.provider.clone(), wallet.clone());
+        let _client = SignerMiddleware::new(self.provider.clone(), wallet.clone());
 
         // NOTE: These contract addresses need to be found from Polymarket documentation
         // or by inspecting the network requests on polymarket.com
@@ -203,16 +824,683 @@ impl PolymarketBlockchain {
             .context("Failed to get transaction receipt")?;
         
         if let Some(receipt) = receipt {
-            Ok(receipt.status == Some(1.into()))
+            let success = receipt.status == Some(1.into());
+            if !success {
+                if let Ok(Some(reason)) = self.transaction_failure_reason(tx_hash).await {
+                    warn!("Transaction {} reverted: {}", tx_hash, reason);
+                }
+            }
+            Ok(success)
         } else {
             Ok(false)
         }
     }
 
+    /// Why a mined transaction reverted: replays it with `eth_call` at its block
+    /// and decodes the `Error(string)` message. `None` if it succeeded, is still
+    /// pending, or reverted without a message.
+    pub async fn transaction_failure_reason(&self, tx_hash: &str) -> Result<Option<String>> {
+        let hash = H256::from_str(tx_hash)
+            .context("Invalid transaction hash")?;
+
+        let Some(receipt) = self.provider.get_transaction_receipt(hash).await
+            .context("Failed to get transaction receipt")? else {
+            return Ok(None);
+        };
+        if receipt.status == Some(1.into()) {
+            return Ok(None);
+        }
+
+        let tx = self.provider.get_transaction(hash).await
+            .context("Failed to get transaction")?
+            .context("Transaction not found")?;
+
+        let call: TypedTransaction = TransactionRequest::new()
+            .from(tx.from)
+            .to(tx.to.context("Transaction has no recipient")?)
+            .data(tx.input.clone())
+            .value(tx.value)
+            .gas(tx.gas)
+            .into();
+
+        // Replay against the state the transaction actually ran on
+        let block = receipt.block_number.map(|number| number.into());
+        match self.provider.call(&call, block).await {
+            Ok(_) => Ok(None),
+            Err(e) => Ok(RpcError::as_error_response(&e)
+                .and_then(|response| response.as_revert_data())
+                .and_then(|data| decode_revert_reason(&data))
+                .or_else(|| Some(e.to_string()))),
+        }
+    }
+
+    /// Read a market's on-chain resolution from the ConditionalTokens payouts.
+    /// Returns `None` while unresolved; YES is outcome index 0.
+    pub async fn check_resolution(&self, condition_id: &str) -> Result<Option<Resolution>> {
+        let condition = H256::from_str(condition_id)
+            .context("Invalid condition ID")?;
+
+        // payoutDenominator(bytes32) stays 0 until the oracle reports
+        let denominator = self
+            .call_conditional_tokens("payoutDenominator(bytes32)", condition, None)
+            .await?;
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+
+        // payoutNumerators(bytes32, uint256) for the YES (0) and NO (1) slots
+        let yes_payout = self
+            .call_conditional_tokens("payoutNumerators(bytes32,uint256)", condition, Some(0))
+            .await?;
+        let no_payout = self
+            .call_conditional_tokens("payoutNumerators(bytes32,uint256)", condition, Some(1))
+            .await?;
+
+        Ok(Some(resolution_from_payouts(yes_payout, no_payout)))
+    }
+
+    /// Fraction of $1 each share of outcome `index` (0 = YES, 1 = NO) redeems
+    /// for, from the ConditionalTokens payouts. `None` while unresolved.
+    pub async fn payout_per_share(&self, condition_id: &str, index: u64) -> Result<Option<f64>> {
+        let condition = H256::from_str(condition_id)
+            .context("Invalid condition ID")?;
+
+        let denominator = self
+            .call_conditional_tokens("payoutDenominator(bytes32)", condition, None)
+            .await?;
+        if denominator.is_zero() {
+            return Ok(None);
+        }
+
+        let numerator = self
+            .call_conditional_tokens("payoutNumerators(bytes32,uint256)", condition, Some(index))
+            .await?;
+        Ok(Some(numerator.as_u128() as f64 / denominator.as_u128() as f64))
+    }
+
+    /// Call a `uint256`-returning ConditionalTokens view taking a condition ID
+    /// and, optionally, an outcome index
+    async fn call_conditional_tokens(
+        &self,
+        signature: &str,
+        condition: H256,
+        index: Option<u64>,
+    ) -> Result<U256> {
+        let contract: Address = CONDITIONAL_TOKENS_ADDRESS
+            .parse()
+            .context("Invalid ConditionalTokens contract address")?;
+
+        let mut data = ethers::utils::id(signature).to_vec();
+        data.extend_from_slice(condition.as_bytes());
+        if let Some(index) = index {
+            let mut index_bytes = [0u8; 32];
+            U256::from(index).to_big_endian(&mut index_bytes);
+            data.extend_from_slice(&index_bytes);
+        }
+
+        let result = self.provider.call(
+            &TransactionRequest::new()
+                .to(contract)
+                .data(data)
+                .into(),
+            None,
+        ).await
+        .with_context(|| format!("Failed to call ConditionalTokens {}", signature))?;
+
+        if result.len() < 32 {
+            return Err(anyhow::anyhow!("Invalid {} response from ConditionalTokens", signature));
+        }
+        Ok(U256::from_big_endian(&result[..32]))
+    }
+
     /// Get current gas price
     pub async fn get_gas_price(&self) -> Result<U256> {
         self.provider.get_gas_price().await
             .context("Failed to get gas price")
     }
+
+    /// Current fees, preferring EIP-1559 and falling back to the legacy gas
+    /// price when the RPC doesn't support it. The gas multiplier is applied.
+    pub async fn estimate_gas_fees(&self) -> Result<GasFees> {
+        let fees = match self.provider.estimate_eip1559_fees(None).await {
+            Ok((max_fee_per_gas, max_priority_fee_per_gas)) => GasFees::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            },
+            Err(e) => {
+                warn!("EIP-1559 fee estimation failed, using legacy gas price: {}", e);
+                GasFees::Legacy {
+                    gas_price: self.get_gas_price().await?,
+                }
+            }
+        };
+
+        Ok(fees.with_multiplier(self.gas_multiplier))
+    }
+
+    /// Build a transaction to `to` with `data`, priced with `estimate_gas_fees`
+    /// and given the next nonce from the nonce manager. The nonce is reserved
+    /// last, so a failed fee estimate can't leave a gap in the sequence.
+    pub async fn build_transaction(&self, to: Address, data: Vec<u8>) -> Result<TypedTransaction> {
+        let from = self.address()?;
+        let fees = self.estimate_gas_fees().await?;
+        let nonce = self
+            .nonce_manager
+            .next_nonce(|| async {
+                self.provider
+                    .get_transaction_count(from, Some(BlockNumber::Pending.into()))
+                    .await
+                    .context("Failed to fetch pending nonce")
+            })
+            .await?;
+
+        let tx: TypedTransaction = match fees {
+            GasFees::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas } => {
+                Eip1559TransactionRequest::new()
+                    .from(from)
+                    .to(to)
+                    .data(data)
+                    .chain_id(self.chain_id)
+                    .max_fee_per_gas(max_fee_per_gas)
+                    .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                    .nonce(nonce)
+                    .into()
+            }
+            GasFees::Legacy { gas_price } => TransactionRequest::new()
+                .from(from)
+                .to(to)
+                .data(data)
+                .chain_id(self.chain_id)
+                .gas_price(gas_price)
+                .nonce(nonce)
+                .into(),
+        };
+
+        Ok(tx)
+    }
+
+    /// Sign and broadcast a transaction, returning its hash
+    pub async fn send_transaction(&self, to: Address, data: Vec<u8>) -> Result<H256> {
+        let wallet = self.wallet.as_ref()
+            .context("Wallet required to send transactions")?;
+        let client = SignerMiddleware::new(self.provider.clone(), wallet.clone());
+
+        let tx = self.build_transaction(to, data).await?;
+        let sent = client.send_transaction(tx, None).await;
+        match sent {
+            Ok(pending) => Ok(pending.tx_hash()),
+            Err(e) => {
+                // The nonce may not have been consumed - re-read it next time
+                self.nonce_manager.resync().await;
+
+                // Gas estimation replays the call, so a revert shows up here
+                let reason = e
+                    .as_error_response()
+                    .and_then(|response| response.as_revert_data())
+                    .and_then(|data| decode_revert_reason(&data));
+                match reason {
+                    Some(reason) => {
+                        Err(BotError::Blockchain(format!("Transaction reverted: {}", reason)).into())
+                    }
+                    None => Err(anyhow::Error::new(e).context("Failed to send transaction")),
+                }
+            }
+        }
+    }
+
+    /// Estimate the USD gas cost of one order at the current fees
+    pub async fn estimate_order_gas_cost_usd(&self, matic_usd: f64) -> Result<f64> {
+        let gas_price = self.estimate_gas_fees().await?.max_price_per_gas();
+        let cost_wei = gas_price.saturating_mul(U256::from(ORDER_GAS_LIMIT));
+        let cost_matic = cost_wei.as_u128() as f64 / 1e18;
+        Ok(cost_matic * matic_usd)
+    }
+}
+
+/// Map a binary market's payout numerators to the winning side
+fn resolution_from_payouts(yes_payout: U256, no_payout: U256) -> Resolution {
+    match yes_payout.cmp(&no_payout) {
+        std::cmp::Ordering::Greater => Resolution::Yes,
+        std::cmp::Ordering::Less => Resolution::No,
+        // A 50/50 split (e.g. a voided market) has no winning side
+        std::cmp::Ordering::Equal => Resolution::Void,
+    }
+}
+
+/// Decode a Solidity `Error(string)` revert payload
+fn decode_revert_reason(data: &[u8]) -> Option<String> {
+    // Selector of Error(string)
+    const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+    if data.len() < 4 || data[..4] != ERROR_SELECTOR {
+        return None;
+    }
+
+    ethers::abi::decode(&[ethers::abi::ParamType::String], &data[4..])
+        .ok()?
+        .into_iter()
+        .next()?
+        .into_string()
+}
+
+/// CLOB `orderType` for a time in force (FAK is the CLOB's immediate-or-cancel)
+fn clob_order_type(time_in_force: TimeInForce) -> &'static str {
+    match time_in_force {
+        TimeInForce::Gtc => "GTC",
+        TimeInForce::Ioc => "FAK",
+        TimeInForce::Fok => "FOK",
+        TimeInForce::Gtd(_) => "GTD",
+    }
+}
+
+/// Read a CLOB number field, which may be sent as a string
+fn clob_number(value: &serde_json::Value) -> Option<f64> {
+    value.as_f64().or_else(|| value.as_str()?.parse().ok())
 }
 
+/// Turn a CLOB order's matched size into a fill (empty if nothing matched yet)
+fn clob_order_fills(order: &serde_json::Value) -> Vec<Fill> {
+    let shares = clob_number(&order["size_matched"]).unwrap_or(0.0);
+    let Some(price) = clob_number(&order["price"]) else {
+        return Vec::new();
+    };
+    if shares <= 0.0 {
+        return Vec::new();
+    }
+
+    let fee_rate = clob_number(&order["fee_rate_bps"]).unwrap_or(0.0) / 10_000.0;
+    vec![Fill {
+        shares,
+        price,
+        fee: shares * price * fee_rate,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::{MockProvider, MockResponse};
+    use ethers::types::{Block, FeeHistory};
+    use serde_json::json;
+    use std::time::Duration;
+
+    // Well-known test key (Hardhat account 0)
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn gwei(amount: f64) -> U256 {
+        U256::from((amount * 1e9) as u64)
+    }
+
+    fn ok(value: impl Serialize) -> MockResponse {
+        MockResponse::Value(serde_json::to_value(value).unwrap())
+    }
+
+    /// Latest block, with a base fee on EIP-1559 chains
+    fn latest_block(base_fee_per_gas: Option<U256>) -> MockResponse {
+        ok(Block::<H256> { base_fee_per_gas, ..Default::default() })
+    }
+
+    /// Fee history with no rewards, so ethers falls back to its default tip
+    fn fee_history() -> MockResponse {
+        ok(FeeHistory {
+            base_fee_per_gas: Vec::new(),
+            gas_used_ratio: Vec::new(),
+            oldest_block: U256::zero(),
+            reward: Vec::new(),
+        })
+    }
+
+    /// ABI-encoded `uint256` return value
+    fn uint(value: U256) -> MockResponse {
+        let mut word = [0u8; 32];
+        value.to_big_endian(&mut word);
+        ok(ethers::types::Bytes::from(word.to_vec()))
+    }
+
+    /// `eth_call` failing with an `Error(string)` revert
+    fn reverted(reason: &str) -> MockResponse {
+        let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+        data.extend(ethers::abi::encode(&[ethers::abi::Token::String(reason.to_string())]));
+        MockResponse::Error(ethers::providers::JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_string(),
+            data: Some(json!(ethers::types::Bytes::from(data))),
+        })
+    }
+
+    fn receipt(tx_hash: H256, status: u64) -> MockResponse {
+        ok(ethers::types::TransactionReceipt {
+            transaction_hash: tx_hash,
+            block_number: Some(100.into()),
+            status: Some(status.into()),
+            ..Default::default()
+        })
+    }
+
+    fn mined_transaction(tx_hash: H256) -> MockResponse {
+        ok(ethers::types::Transaction {
+            hash: tx_hash,
+            block_number: Some(100.into()),
+            to: Some(USDC_ADDRESS.parse().unwrap()),
+            ..Default::default()
+        })
+    }
+
+    /// `eth_call` params reading `spender`'s USDC allowance
+    fn allowance_call(blockchain: &PolymarketBlockchain<MockProvider>, spender: Address) -> serde_json::Value {
+        let mut data = ethers::utils::id("allowance(address,address)").to_vec();
+        data.extend_from_slice(H256::from(blockchain.address().unwrap()).as_bytes());
+        data.extend_from_slice(H256::from(spender).as_bytes());
+        let call: TypedTransaction = TransactionRequest::new()
+            .to(USDC_ADDRESS.parse::<Address>().unwrap())
+            .data(data)
+            .into();
+        json!([call, "latest"])
+    }
+
+    /// An allowance check coming up short, then an approval sent as
+    /// `tx_hash` and mined (its receipt is left to the caller)
+    fn approval_responses(tx_hash: H256) -> Vec<MockResponse> {
+        vec![
+            uint(U256::from(9_999_999)),
+            latest_block(None),
+            ok(gwei(40.0)),
+            ok(U256::from(0)),
+            ok(U256::from(60_000)), // eth_estimateGas
+            ok(tx_hash),
+            mined_transaction(tx_hash),
+        ]
+    }
+
+    /// Wallet-backed client whose RPC answers with `responses`, in order
+    fn mocked(responses: Vec<MockResponse>) -> (PolymarketBlockchain<MockProvider>, MockProvider) {
+        let mock = MockProvider::new();
+        // MockProvider answers last-pushed first
+        for response in responses.into_iter().rev() {
+            mock.push_response(response);
+        }
+        let provider = Provider::new(mock.clone()).interval(Duration::from_millis(10));
+        let blockchain = PolymarketBlockchain::with_provider(provider).with_wallet(TEST_KEY).unwrap();
+        (blockchain, mock)
+    }
+
+    #[test]
+    fn clob_credentials_debug_redacts_secrets() {
+        let credentials = ClobCredentials {
+            api_key: "key-123".to_string(),
+            api_secret: "c2VjcmV0LXZhbHVl".to_string(),
+            passphrase: "hunter2".to_string(),
+        };
+
+        let debug = format!("{:?}", credentials);
+        assert!(debug.contains("key-123"));
+        assert!(!debug.contains("c2VjcmV0LXZhbHVl"));
+        assert!(!debug.contains("hunter2"));
+    }
+
+    #[tokio::test]
+    async fn signed_clob_order_recovers_to_the_wallet() {
+        use ethers::types::transaction::eip712::Eip712;
+
+        let blockchain = PolymarketBlockchain::new("http://127.0.0.1:8545")
+            .unwrap()
+            .with_wallet(TEST_KEY)
+            .unwrap();
+        let address = blockchain.address().unwrap();
+        let wallet = blockchain.wallet.as_ref().unwrap();
+
+        let order = blockchain.build_clob_order("123456789", 10.0, 0.40).unwrap();
+        assert_eq!(order.maker, address);
+        assert_eq!(order.maker_amount, U256::from(10_000_000u64));
+        assert_eq!(order.taker_amount, U256::from(25_000_000u64));
+
+        let typed_data = blockchain.clob_order_typed_data(&order).unwrap();
+        let signature = wallet.sign_typed_data(&typed_data).await.unwrap();
+        let digest = H256::from(typed_data.encode_eip712().unwrap());
+        assert_eq!(signature.recover(digest).unwrap(), address);
+
+        // Any change to the order breaks the signature
+        let mut tampered = order.clone();
+        tampered.taker_amount = U256::from(30_000_000u64);
+        let tampered_digest = H256::from(blockchain.clob_order_typed_data(&tampered).unwrap().encode_eip712().unwrap());
+        assert_ne!(signature.recover(tampered_digest).unwrap(), address);
+    }
+
+    #[tokio::test]
+    async fn eip1559_fees_bump_the_tip_by_the_multiplier() {
+        // A 30 gwei base fee gets ethers' default 3 gwei tip and a 60 gwei cap
+        let (blockchain, _) = mocked(vec![latest_block(Some(gwei(30.0))), fee_history()]);
+        let fees = blockchain.with_gas_multiplier(1.5).estimate_gas_fees().await.unwrap();
+
+        assert_eq!(
+            fees,
+            GasFees::Eip1559 {
+                max_fee_per_gas: gwei(61.5),
+                max_priority_fee_per_gas: gwei(4.5),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_legacy_gas_price() {
+        // No base fee on the latest block means no EIP-1559
+        let (blockchain, mock) = mocked(vec![latest_block(None), ok(gwei(40.0))]);
+        let fees = blockchain.with_gas_multiplier(1.25).estimate_gas_fees().await.unwrap();
+
+        assert_eq!(fees, GasFees::Legacy { gas_price: gwei(50.0) });
+        mock.assert_request("eth_getBlockByNumber", json!(["latest", false])).unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn transactions_carry_the_estimated_fees() {
+        let (blockchain, _) = mocked(vec![latest_block(Some(gwei(30.0))), fee_history(), ok(U256::from(7))]);
+        let to = Address::repeat_byte(0x11);
+        let tx = blockchain.build_transaction(to, vec![0xab]).await.unwrap();
+
+        let TypedTransaction::Eip1559(tx) = tx else {
+            panic!("expected an EIP-1559 transaction, got {:?}", tx);
+        };
+        assert_eq!(tx.max_fee_per_gas, Some(gwei(60.0)));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(gwei(3.0)));
+        assert_eq!(tx.nonce, Some(U256::from(7)));
+        assert_eq!(tx.chain_id, Some(137.into()));
+        assert_eq!(tx.from, Some(blockchain.address().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn concurrent_callers_get_sequential_nonces() {
+        let manager = Arc::new(NonceManager::new());
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let manager = manager.clone();
+                let fetches = fetches.clone();
+                tokio::spawn(async move {
+                    manager
+                        .next_nonce(|| async {
+                            fetches.fetch_add(1, Ordering::SeqCst);
+                            tokio::task::yield_now().await;
+                            Ok(U256::from(5))
+                        })
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+        let mut nonces = Vec::new();
+        for task in tasks {
+            nonces.push(task.await.unwrap().as_u64());
+        }
+        nonces.sort();
+
+        assert_eq!(nonces, (5..13).collect::<Vec<_>>());
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn resync_rereads_the_pending_nonce() {
+        let manager = NonceManager::new();
+        assert_eq!(manager.next_nonce(|| async { Ok(U256::from(3)) }).await.unwrap(), U256::from(3));
+        assert_eq!(manager.next_nonce(|| async { Ok(U256::from(99)) }).await.unwrap(), U256::from(4));
+
+        manager.resync().await;
+        assert_eq!(manager.next_nonce(|| async { Ok(U256::from(10)) }).await.unwrap(), U256::from(10));
+
+        // A failed fetch hands out nothing, so the next caller fetches again
+        manager.resync().await;
+        assert!(manager.next_nonce(|| async { Err(anyhow::anyhow!("RPC down")) }).await.is_err());
+        assert_eq!(manager.next_nonce(|| async { Ok(U256::from(11)) }).await.unwrap(), U256::from(11));
+    }
+
+    #[tokio::test]
+    async fn concurrent_transactions_fetch_the_nonce_once() {
+        let (blockchain, mock) = mocked(vec![
+            latest_block(None),
+            ok(gwei(40.0)),
+            ok(U256::from(7)),
+            latest_block(None),
+            ok(gwei(40.0)),
+        ]);
+        let to = Address::repeat_byte(0x11);
+
+        let (first, second) = tokio::join!(
+            blockchain.build_transaction(to, vec![0x01]),
+            blockchain.build_transaction(to, vec![0x02]),
+        );
+        let mut nonces = [first.unwrap(), second.unwrap()].map(|tx| tx.nonce().copied().unwrap());
+        nonces.sort();
+        assert_eq!(nonces, [U256::from(7), U256::from(8)]);
+
+        let address = blockchain.address().unwrap();
+        mock.assert_request("eth_getBlockByNumber", json!(["latest", false])).unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+        mock.assert_request("eth_getTransactionCount", json!([address, "pending"])).unwrap();
+        // The second transaction reuses the cached nonce
+        mock.assert_request("eth_getBlockByNumber", json!(["latest", false])).unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn usdc_allowance_reads_the_token_contract() {
+        let (blockchain, mock) = mocked(vec![uint(U256::from(5_000_000))]);
+        let spender: Address = CTF_EXCHANGE_ADDRESS.parse().unwrap();
+
+        assert_eq!(blockchain.usdc_allowance(spender).await.unwrap(), U256::from(5_000_000));
+        mock.assert_request("eth_call", allowance_call(&blockchain, spender)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn sufficient_allowance_skips_the_approval() {
+        // $10 needs 10_000_000 base units
+        let (blockchain, mock) = mocked(vec![uint(U256::from(10_000_000))]);
+
+        assert_eq!(blockchain.ensure_exchange_allowance(10.0).await.unwrap(), None);
+        let exchange: Address = CTF_EXCHANGE_ADDRESS.parse().unwrap();
+        mock.assert_request("eth_call", allowance_call(&blockchain, exchange)).unwrap();
+        // Nothing was sent after the allowance check
+        assert!(matches!(
+            mock.assert_request("eth_sendRawTransaction", ()),
+            Err(ethers::providers::MockError::EmptyRequests)
+        ));
+    }
+
+    #[tokio::test]
+    async fn low_allowance_sends_an_approval() {
+        let tx_hash = H256::repeat_byte(0xaa);
+        let (blockchain, _) = mocked([approval_responses(tx_hash), vec![receipt(tx_hash, 1)]].concat());
+
+        assert_eq!(blockchain.ensure_exchange_allowance(10.0).await.unwrap(), Some(tx_hash));
+    }
+
+    #[tokio::test]
+    async fn failure_reason_decodes_the_revert_message() {
+        let tx_hash = H256::repeat_byte(0xbb);
+        let (blockchain, _) = mocked(vec![
+            receipt(tx_hash, 0),
+            mined_transaction(tx_hash),
+            reverted("ERC20: transfer amount exceeds balance"),
+        ]);
+
+        let reason = blockchain.transaction_failure_reason(&format!("{:?}", tx_hash)).await.unwrap();
+        assert_eq!(reason.as_deref(), Some("ERC20: transfer amount exceeds balance"));
+    }
+
+    #[tokio::test]
+    async fn successful_transactions_have_no_failure_reason() {
+        let tx_hash = H256::repeat_byte(0xbb);
+        let (blockchain, _) = mocked(vec![receipt(tx_hash, 1)]);
+
+        assert_eq!(blockchain.transaction_failure_reason(&format!("{:?}", tx_hash)).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn reverted_approval_reports_the_reason() {
+        let tx_hash = H256::repeat_byte(0xaa);
+        let (blockchain, _) = mocked(
+            [
+                approval_responses(tx_hash),
+                vec![
+                    receipt(tx_hash, 0),
+                    // Replayed by transaction_failure_reason
+                    receipt(tx_hash, 0),
+                    mined_transaction(tx_hash),
+                    reverted("approve from the zero address"),
+                ],
+            ]
+            .concat(),
+        );
+
+        let error = blockchain.ensure_exchange_allowance(10.0).await.unwrap_err();
+        assert!(error.to_string().contains("approve from the zero address"), "{}", error);
+    }
+
+    /// Client failing over between `endpoints`, in order
+    fn failover(endpoints: Vec<MockProvider>) -> PolymarketBlockchain<Failover<MockProvider>> {
+        PolymarketBlockchain::with_provider(Provider::new(Failover::with_endpoints(endpoints).unwrap()))
+    }
+
+    #[tokio::test]
+    async fn failover_moves_on_from_a_failing_endpoint() {
+        // An endpoint with nothing queued fails like an unreachable one
+        let (down, up) = (MockProvider::new(), MockProvider::new());
+        up.push(gwei(40.0)).unwrap();
+        let blockchain = failover(vec![down.clone(), up.clone()]);
+
+        assert_eq!(blockchain.get_gas_price().await.unwrap(), gwei(40.0));
+
+        // The endpoint that answered stays first in line
+        down.push(gwei(99.0)).unwrap();
+        up.push(gwei(41.0)).unwrap();
+        assert_eq!(blockchain.get_gas_price().await.unwrap(), gwei(41.0));
+    }
+
+    #[tokio::test]
+    async fn rpc_errors_are_not_retried_on_other_endpoints() {
+        let (reverting, spare) = (MockProvider::new(), MockProvider::new());
+        reverting.push_response(reverted("paused"));
+        spare.push(gwei(40.0)).unwrap();
+        let blockchain = failover(vec![reverting, spare]);
+
+        assert!(blockchain.get_gas_price().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failover_errors_once_every_endpoint_fails() {
+        let blockchain = failover(vec![MockProvider::new(), MockProvider::new()]);
+        assert!(blockchain.get_gas_price().await.is_err());
+
+        assert!(Failover::<MockProvider>::with_endpoints(Vec::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn native_balance_is_read_in_matic() {
+        // 0.002 MATIC - not enough to cover many orders' gas
+        let (blockchain, mock) = mocked(vec![ok(U256::from(2_000_000_000_000_000u64))]);
+
+        let balance = blockchain.get_native_balance().await.unwrap();
+        assert!((balance - 0.002).abs() < 1e-12, "{}", balance);
+        mock.assert_request("eth_getBalance", json!([blockchain.address().unwrap(), "latest"])).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+// Durable storage for positions, so a restart recovers what's open
+
+use crate::position_tracker::Position;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Backing store the `PositionTracker` mirrors its writes into
+#[async_trait]
+pub trait PositionStore: Send + Sync {
+    /// Save a newly opened position
+    async fn insert(&self, position: &Position) -> Result<()>;
+
+    /// Persist changes to a stored position: its size after a partial close,
+    /// settlement (status, payout, profit, settled_at) or stale flag
+    async fn update(&self, position: &Position) -> Result<()>;
+
+    /// Positions still waiting for settlement
+    async fn load_open(&self) -> Result<Vec<Position>>;
+
+    /// Every stored position
+    async fn all(&self) -> Result<Vec<Position>>;
+}
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqlitePositionStore;
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::PositionStore;
+    use crate::position_tracker::{Position, PositionStatus};
+    use anyhow::{Context, Result};
+    use async_trait::async_trait;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqliteRow};
+    use sqlx::Row;
+    use std::str::FromStr;
+    use tracing::info;
+
+    const COLUMNS: &str = "id, arb_id, platform, event_id, event_title, outcome, amount, cost, \
+        price, fees_paid, order_id, status, created_at, settled_at, payout, profit, stale";
+
+    /// `PositionStore` backed by a SQLite database (feature "sqlite")
+    #[derive(Clone)]
+    pub struct SqlitePositionStore {
+        pool: SqlitePool,
+    }
+
+    impl SqlitePositionStore {
+        /// Open (creating if needed) the database at `url`, e.g.
+        /// `sqlite://positions.db`, and apply pending migrations
+        pub async fn connect(url: &str) -> Result<Self> {
+            let options = SqliteConnectOptions::from_str(url)
+                .with_context(|| format!("Invalid database URL {}", url))?
+                .create_if_missing(true);
+            let pool = SqlitePool::connect_with(options)
+                .await
+                .with_context(|| format!("Failed to open {}", url))?;
+
+            sqlx::migrate!("./migrations")
+                .run(&pool)
+                .await
+                .context("Failed to run database migrations")?;
+
+            info!("🗄️ Position store ready at {}", url);
+            Ok(Self { pool })
+        }
+
+        async fn query_positions(&self, filter: &str) -> Result<Vec<Position>> {
+            let sql = format!("SELECT {} FROM positions {} ORDER BY created_at", COLUMNS, filter);
+            let rows = sqlx::query(&sql)
+                .fetch_all(&self.pool)
+                .await
+                .context("Failed to load positions")?;
+            rows.iter().map(position_from_row).collect()
+        }
+    }
+
+    /// Status as stored, matching its JSON form ("Open", "Won", ...)
+    fn status_name(status: &PositionStatus) -> Result<String> {
+        Ok(serde_json::to_value(status)?
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    fn position_from_row(row: &SqliteRow) -> Result<Position> {
+        let platform: String = row.try_get("platform")?;
+        let status: String = row.try_get("status")?;
+
+        Ok(Position {
+            id: row.try_get("id")?,
+            arb_id: row.try_get("arb_id")?,
+            platform: platform.parse()?,
+            event_id: row.try_get("event_id")?,
+            event_title: row.try_get("event_title")?,
+            outcome: row.try_get("outcome")?,
+            amount: row.try_get("amount")?,
+            cost: row.try_get("cost")?,
+            price: row.try_get("price")?,
+            fees_paid: row.try_get("fees_paid")?,
+            order_id: row.try_get("order_id")?,
+            status: serde_json::from_value(serde_json::Value::String(status))
+                .context("Unknown position status")?,
+            created_at: row.try_get("created_at")?,
+            settled_at: row.try_get("settled_at")?,
+            payout: row.try_get("payout")?,
+            profit: row.try_get("profit")?,
+            stale: row.try_get("stale")?,
+        })
+    }
+
+    #[async_trait]
+    impl PositionStore for SqlitePositionStore {
+        async fn insert(&self, position: &Position) -> Result<()> {
+            let sql = format!(
+                "INSERT INTO positions ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                COLUMNS
+            );
+            sqlx::query(&sql)
+                .bind(&position.id)
+                .bind(&position.arb_id)
+                .bind(position.platform.as_str())
+                .bind(&position.event_id)
+                .bind(&position.event_title)
+                .bind(&position.outcome)
+                .bind(position.amount)
+                .bind(position.cost)
+                .bind(position.price)
+                .bind(position.fees_paid)
+                .bind(&position.order_id)
+                .bind(status_name(&position.status)?)
+                .bind(position.created_at)
+                .bind(position.settled_at)
+                .bind(position.payout)
+                .bind(position.profit)
+                .bind(position.stale)
+                .execute(&self.pool)
+                .await
+                .with_context(|| format!("Failed to insert position {}", position.id))?;
+            Ok(())
+        }
+
+        async fn update(&self, position: &Position) -> Result<()> {
+            sqlx::query(
+                "UPDATE positions SET amount = ?, cost = ?, price = ?, fees_paid = ?, status = ?, \
+                 settled_at = ?, payout = ?, profit = ?, stale = ? WHERE id = ?",
+            )
+            .bind(position.amount)
+            .bind(position.cost)
+            .bind(position.price)
+            .bind(position.fees_paid)
+            .bind(status_name(&position.status)?)
+            .bind(position.settled_at)
+            .bind(position.payout)
+            .bind(position.profit)
+            .bind(position.stale)
+            .bind(&position.id)
+            .execute(&self.pool)
+            .await
+            .with_context(|| format!("Failed to update position {}", position.id))?;
+            Ok(())
+        }
+
+        async fn load_open(&self) -> Result<Vec<Position>> {
+            self.query_positions("WHERE status = 'Open'").await
+        }
+
+        async fn all(&self) -> Result<Vec<Position>> {
+            self.query_positions("").await
+        }
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+    use crate::event::{Event, Platform};
+    use crate::position_tracker::{PositionStatus, PositionTracker};
+    use std::sync::Arc;
+
+    fn event() -> Event {
+        Event {
+            platform: Platform::Kalshi,
+            event_id: "KXBTC-24JUN02-T70000".to_string(),
+            title: "Will Bitcoin be above $70,000 on June 2?".to_string(),
+            description: String::new(),
+            resolution_date: None,
+            category: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn positions_survive_insert_settle_and_reload() {
+        let path = std::env::temp_dir().join(format!("positions-{}.db", uuid::Uuid::new_v4()));
+        let url = format!("sqlite://{}", path.display());
+
+        let store = Arc::new(SqlitePositionStore::connect(&url).await.unwrap());
+        let mut tracker = PositionTracker::new().with_store(store);
+        let won = Position::new(Platform::Kalshi, &event(), "YES".to_string(), 10.0, 4.5, 0.45, None);
+        let open = Position::new(Platform::Kalshi, &event(), "NO".to_string(), 5.0, 2.5, 0.5, None);
+        let (won_id, open_id) = (won.id.clone(), open.id.clone());
+        tracker.add_position(won);
+        tracker.add_position(open);
+        tracker.update_position_settlement(&won_id, true, Some(10.0));
+        tracker.mark_stale(&open_id);
+        tracker.finish_store_writes().unwrap().await.unwrap();
+
+        let store = Arc::new(SqlitePositionStore::connect(&url).await.unwrap());
+        let reloaded = PositionTracker::load_from_store(store.clone()).await.unwrap();
+        let won = reloaded.get_position(&won_id).unwrap();
+        assert_eq!(won.status, PositionStatus::Won);
+        assert_eq!(won.payout, Some(10.0));
+        assert!((won.profit.unwrap() - 5.5).abs() < 1e-9);
+
+        let open = reloaded.get_position(&open_id).unwrap();
+        assert_eq!(open.status, PositionStatus::Open);
+        assert!(open.stale);
+        assert_eq!(store.load_open().await.unwrap().len(), 1);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -1,38 +1,76 @@
-use crate::event::Event;
-use chrono::{DateTime, Utc};
+use crate::event::{normalize_outcome, Event, MarketPrices, Platform};
+use crate::exchange::Fill;
+use crate::position_store::PositionStore;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tracing::info;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum PositionStatus {
     Open,      // Trade executed, waiting for settlement
-    Settled,   // Event resolved
+    Settled,   // Event resolved void - stake refunded, no winner
     Won,       // Position won (payout received)
     Lost,      // Position lost (no payout)
+    Closed,    // Sold before the event resolved
+}
+
+impl fmt::Display for PositionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PositionStatus::Open => "Open",
+            PositionStatus::Settled => "Settled",
+            PositionStatus::Won => "Won",
+            PositionStatus::Lost => "Lost",
+            PositionStatus::Closed => "Closed",
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
     pub id: String,
-    pub platform: String,        // "polymarket" or "kalshi"
+    #[serde(default)]
+    pub arb_id: Option<String>,  // Shared by both legs of one arbitrage trade
+    pub platform: Platform,
     pub event_id: String,
     pub event_title: String,
     pub outcome: String,         // "YES" or "NO"
     pub amount: f64,            // Number of tokens/shares
     pub cost: f64,               // Total cost
     pub price: f64,              // Price per token/share
+    #[serde(default)]
+    pub fees_paid: f64,          // Venue fees charged on the fills
     pub order_id: Option<String>,
     pub status: PositionStatus,
     pub created_at: DateTime<Utc>,
     pub settled_at: Option<DateTime<Utc>>,
     pub payout: Option<f64>,     // Payout amount if won
     pub profit: Option<f64>,     // Profit/loss
+    #[serde(default)]
+    pub stale: bool,             // Open past max_open_age, flagged for manual review
+}
+
+/// e.g. "[kalshi] BTC above 100k - YES 10.0000 @ $0.4500 (Open)"
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} - {} {:.4} @ ${:.4} ({})",
+            self.platform, self.event_title, self.outcome, self.amount, self.price, self.status
+        )
+    }
 }
 
 impl Position {
     pub fn new(
-        platform: String,
+        platform: Platform,
         event: &Event,
         outcome: String,
         amount: f64,
@@ -42,53 +80,294 @@ impl Position {
     ) -> Self {
         Self {
             id: format!("{}_{}", platform, &uuid::Uuid::new_v4().to_string()[..8]),
+            arb_id: None,
             platform,
             event_id: event.event_id.clone(),
             event_title: event.title.clone(),
-            outcome,
+            outcome: normalize_outcome(&outcome),
             amount,
             cost,
             price,
+            fees_paid: 0.0,
             order_id,
             status: PositionStatus::Open,
             created_at: Utc::now(),
             settled_at: None,
             payout: None,
             profit: None,
+            stale: false,
+        }
+    }
+
+    /// Link this position to the arbitrage trade it was opened by
+    pub fn with_arb_id(mut self, arb_id: impl Into<String>) -> Self {
+        self.arb_id = Some(arb_id.into());
+        self
+    }
+
+    /// Replace the estimated size and price with what actually filled.
+    /// Leaves the position untouched if there are no fills.
+    pub fn apply_fills(&mut self, fills: &[Fill]) {
+        let shares: f64 = fills.iter().map(|f| f.shares).sum();
+        if shares <= 0.0 {
+            return;
+        }
+
+        self.amount = shares;
+        self.cost = fills.iter().map(Fill::cost).sum();
+        self.price = self.cost / shares; // Volume-weighted average
+        self.fees_paid = fills.iter().map(|f| f.fee).sum();
+    }
+
+    /// Shrink (or grow) the position to `shares`, scaling cost and fees to match
+    pub fn resize(&mut self, shares: f64) {
+        if self.amount > 0.0 {
+            let ratio = shares / self.amount;
+            self.cost *= ratio;
+            self.fees_paid *= ratio;
         }
+        self.amount = shares;
     }
 
     pub fn calculate_profit_if_won(&self) -> f64 {
         // If position wins, payout is amount * $1.00
         let payout = self.amount * 1.0;
-        payout - self.cost
+        payout - self.cost - self.fees_paid
     }
 
     pub fn calculate_profit_if_lost(&self) -> f64 {
         // If position loses, payout is $0.00
-        -self.cost
+        -self.cost - self.fees_paid
     }
 }
 
+/// Realized equity (cumulative settled profit) after a settlement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    pub timestamp: DateTime<Utc>,
+    pub equity: f64,
+}
+
+/// On-disk tracker state
+#[derive(Serialize, Deserialize)]
+struct TrackerState {
+    positions: Vec<Position>,
+    #[serde(default)]
+    equity_curve: Vec<EquityPoint>,
+}
+
+/// Accepts both the current state format and the older bare list of positions
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredState {
+    State(TrackerState),
+    Positions(Vec<Position>),
+}
+
+/// Share counts closer than this are treated as equal
+const SHARE_EPSILON: f64 = 1e-6;
+
+/// A write mirrored into the `PositionStore`
+enum StoreWrite {
+    Insert(Position),
+    Update(Position),
+}
+
 pub struct PositionTracker {
     positions: HashMap<String, Position>,
+    equity_curve: Vec<EquityPoint>,
+    store: Option<mpsc::UnboundedSender<StoreWrite>>, // Feeds the store writer task
+    store_writer: Option<JoinHandle<()>>,
+    max_open_age: Option<Duration>, // Open positions older than this are stale
+}
+
+impl Default for PositionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PositionTracker {
     pub fn new() -> Self {
         Self {
             positions: HashMap::new(),
+            equity_curve: Vec::new(),
+            store: None,
+            store_writer: None,
+            max_open_age: None,
         }
     }
 
+    /// Treat positions still open after `max_open_age` as stale (markets that
+    /// never resolve or were delisted)
+    pub fn with_max_open_age(mut self, max_open_age: Duration) -> Self {
+        self.max_open_age = Some(max_open_age);
+        self
+    }
+
+    /// Mirror new positions and settlements into `store`. Writes are applied
+    /// in order by a background task, so tracker methods stay synchronous;
+    /// call `finish_store_writes` before exiting so none are lost.
+    pub fn with_store(mut self, store: Arc<dyn PositionStore>) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let writer = tokio::spawn(async move {
+            while let Some(write) = rx.recv().await {
+                let result = match &write {
+                    StoreWrite::Insert(position) => store.insert(position).await,
+                    StoreWrite::Update(position) => store.update(position).await,
+                };
+                if let Err(e) = result {
+                    error!("Failed to persist position: {:#}", e);
+                }
+            }
+        });
+        self.store = Some(tx);
+        self.store_writer = Some(writer);
+        self
+    }
+
+    /// Stop mirroring writes into the store. The returned writer task exits
+    /// once everything queued so far has been written, so await it on shutdown.
+    pub fn finish_store_writes(&mut self) -> Option<JoinHandle<()>> {
+        self.store = None; // Closing the channel lets the writer drain and exit
+        self.store_writer.take()
+    }
+
+    /// Restore every position from `store` (rebuilding the equity curve from
+    /// settlements) and keep mirroring writes into it
+    pub async fn load_from_store(store: Arc<dyn PositionStore>) -> Result<Self> {
+        let positions = store.all().await.context("Failed to load positions from store")?;
+
+        let mut settled: Vec<&Position> = positions.iter().filter(|p| p.profit.is_some()).collect();
+        settled.sort_by_key(|p| p.settled_at);
+        let mut equity = 0.0;
+        let equity_curve = settled
+            .iter()
+            .map(|p| {
+                equity += p.profit.unwrap_or(0.0);
+                EquityPoint {
+                    timestamp: p.settled_at.unwrap_or(p.created_at),
+                    equity,
+                }
+            })
+            .collect();
+
+        info!("📂 Loaded {} positions from the position store", positions.len());
+
+        let tracker = Self {
+            positions: positions.into_iter().map(|p| (p.id.clone(), p)).collect(),
+            equity_curve,
+            store: None,
+            store_writer: None,
+            max_open_age: None,
+        };
+        Ok(tracker.with_store(store))
+    }
+
+    fn persist(&self, write: StoreWrite) {
+        if let Some(store) = &self.store {
+            // Only fails if the writer task is gone (runtime shutting down)
+            let _ = store.send(write);
+        }
+    }
+
+    /// Load positions previously written by `save_to_file`.
+    /// A missing file yields an empty tracker.
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read positions from {}", path.display()))?;
+        let state = match serde_json::from_str(&data)
+            .with_context(|| format!("Failed to parse positions in {}", path.display()))?
+        {
+            StoredState::State(state) => state,
+            StoredState::Positions(positions) => TrackerState {
+                positions,
+                equity_curve: Vec::new(),
+            },
+        };
+
+        info!("📂 Loaded {} positions from {}", state.positions.len(), path.display());
+
+        Ok(Self {
+            positions: state.positions.into_iter().map(|p| (p.id.clone(), p)).collect(),
+            equity_curve: state.equity_curve,
+            store: None,
+            store_writer: None,
+            max_open_age: None,
+        })
+    }
+
+    /// Write all positions to `path` as JSON (via a temp file, so a crash
+    /// mid-write never leaves a truncated file behind)
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let state = TrackerState {
+            positions: self.positions.values().cloned().collect(),
+            equity_curve: self.equity_curve.clone(),
+        };
+        let data = serde_json::to_string_pretty(&state)?;
+
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, data)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to move positions into {}", path.display()))?;
+
+        info!("💾 Saved {} positions to {}", state.positions.len(), path.display());
+        Ok(())
+    }
+
+    /// Write every position to `path` as CSV, oldest first
+    pub fn export_csv(&self, path: &Path) -> Result<()> {
+        let mut writer = csv::Writer::from_path(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+
+        writer.write_record([
+            "id", "arb_id", "platform", "event_id", "event_title", "outcome", "amount",
+            "cost", "price", "fees_paid", "status", "created_at", "settled_at", "payout", "profit",
+        ])?;
+
+        let mut positions: Vec<&Position> = self.positions.values().collect();
+        positions.sort_by_key(|p| p.created_at);
+
+        let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+        for p in &positions {
+            writer.write_record([
+                p.id.clone(),
+                p.arb_id.clone().unwrap_or_default(),
+                p.platform.to_string(),
+                p.event_id.clone(),
+                p.event_title.clone(),
+                p.outcome.clone(),
+                p.amount.to_string(),
+                p.cost.to_string(),
+                p.price.to_string(),
+                p.fees_paid.to_string(),
+                format!("{:?}", p.status),
+                p.created_at.to_rfc3339(),
+                p.settled_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                optional(p.payout),
+                optional(p.profit),
+            ])?;
+        }
+
+        writer.flush()
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        info!("📄 Exported {} positions to {}", positions.len(), path.display());
+        Ok(())
+    }
+
     /// Add a new position after trade execution
     pub fn add_position(&mut self, position: Position) {
-        info!("📝 Tracking new position: {} - {} {} @ ${:.4}", 
-            position.event_title, 
-            position.outcome,
-            position.amount,
-            position.price
-        );
+        info!("📝 Tracking new position: {}", position);
+        self.persist(StoreWrite::Insert(position.clone()));
         self.positions.insert(position.id.clone(), position);
     }
 
@@ -100,13 +379,43 @@ impl PositionTracker {
             .collect()
     }
 
+    /// Open positions created more than `max_open_age` before `now` (none
+    /// without a `max_open_age`)
+    pub fn find_stale_positions(&self, now: DateTime<Utc>) -> Vec<&Position> {
+        let Some(max_open_age) = self.max_open_age else {
+            return Vec::new();
+        };
+        self.positions
+            .values()
+            .filter(|p| p.status == PositionStatus::Open && now - p.created_at > max_open_age)
+            .collect()
+    }
+
+    /// Flag a position stale. It stays open, so it can still settle or be
+    /// closed. Returns false if the ID is unknown or it was already flagged.
+    pub fn mark_stale(&mut self, position_id: &str) -> bool {
+        match self.positions.get_mut(position_id) {
+            Some(position) if !position.stale => {
+                position.stale = true;
+                let position = position.clone();
+                self.persist(StoreWrite::Update(position));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn get_position(&self, position_id: &str) -> Option<&Position> {
+        self.positions.get(position_id)
+    }
+
     /// Get all positions
     pub fn get_all_positions(&self) -> Vec<&Position> {
         self.positions.values().collect()
     }
 
     /// Get positions by platform
-    pub fn get_positions_by_platform(&self, platform: &str) -> Vec<&Position> {
+    pub fn get_positions_by_platform(&self, platform: Platform) -> Vec<&Position> {
         self.positions
             .values()
             .filter(|p| p.platform == platform)
@@ -137,12 +446,15 @@ impl PositionTracker {
             };
             position.profit = Some(profit);
 
-            info!(
-                "💰 Position settled: {} - {} - Profit: ${:.2}",
-                position.event_title,
-                if won { "WON" } else { "LOST" },
-                profit
-            );
+            let equity = self.current_equity() + profit;
+            self.equity_curve.push(EquityPoint {
+                timestamp: Utc::now(),
+                equity,
+            });
+
+            let position = &self.positions[position_id];
+            self.persist(StoreWrite::Update(position.clone()));
+            info!("💰 Position settled: {} - Profit: ${:.2}", position, profit);
 
             Some(profit)
         } else {
@@ -150,6 +462,128 @@ impl PositionTracker {
         }
     }
 
+    /// Correct a settled position's payout to what the venue actually paid
+    /// (after fees and rounding), recomputing its profit and the equity curve.
+    /// Returns the corrected profit, or `None` if the position isn't settled.
+    pub fn reconcile_payout(&mut self, position_id: &str, actual_payout: f64) -> Option<f64> {
+        let position = self.positions.get_mut(position_id)?;
+        let (Some(computed_payout), Some(old_profit)) = (position.payout, position.profit) else {
+            return None;
+        };
+
+        let discrepancy = actual_payout - computed_payout;
+        if discrepancy.abs() < 0.005 {
+            return Some(old_profit);
+        }
+
+        position.payout = Some(actual_payout);
+        let profit = old_profit + discrepancy;
+        position.profit = Some(profit);
+
+        let equity = self.current_equity() + discrepancy;
+        self.equity_curve.push(EquityPoint {
+            timestamp: Utc::now(),
+            equity,
+        });
+
+        let position = &self.positions[position_id];
+        self.persist(StoreWrite::Update(position.clone()));
+        warn!(
+            "⚖️ Payout for {} was ${:.2}, not the computed ${:.2} - profit corrected to ${:.2}",
+            position, actual_payout, computed_payout, profit
+        );
+
+        Some(profit)
+    }
+
+    /// Mark a position closed after selling it for `proceeds` (USD) before
+    /// settlement. Returns the realized profit, or `None` if the ID is unknown.
+    pub fn close_position(&mut self, position_id: &str, proceeds: f64) -> Option<f64> {
+        let profit = self.realize(position_id, PositionStatus::Closed, proceeds)?;
+        info!("🚪 Position closed: {} - Profit: ${:.2}", self.positions[position_id], profit);
+        Some(profit)
+    }
+
+    /// Settle a position whose market resolved void, refunding `payout` (USD)
+    /// rather than paying $1 or nothing. Returns the realized profit, or
+    /// `None` if the ID is unknown.
+    pub fn void_position(&mut self, position_id: &str, payout: f64) -> Option<f64> {
+        let profit = self.realize(position_id, PositionStatus::Settled, payout)?;
+        info!("↩️ Position voided: {} - Profit: ${:.2}", self.positions[position_id], profit);
+        Some(profit)
+    }
+
+    /// Move a position to a final `status`, paid `payout`, and record its profit
+    fn realize(&mut self, position_id: &str, status: PositionStatus, payout: f64) -> Option<f64> {
+        let position = self.positions.get_mut(position_id)?;
+        position.status = status;
+        position.settled_at = Some(Utc::now());
+        position.payout = Some(payout);
+
+        let profit = payout - position.cost - position.fees_paid;
+        position.profit = Some(profit);
+
+        let equity = self.current_equity() + profit;
+        self.equity_curve.push(EquityPoint {
+            timestamp: Utc::now(),
+            equity,
+        });
+
+        self.persist(StoreWrite::Update(self.positions[position_id].clone()));
+        Some(profit)
+    }
+
+    /// Record selling `shares` of a position for `proceeds` (net of sell fees).
+    /// Selling all of it closes the position; a partial sale splits the sold
+    /// shares off into their own closed position and leaves the rest open.
+    pub fn close_shares(&mut self, position_id: &str, shares: f64, proceeds: f64) -> Option<f64> {
+        let position = self.positions.get_mut(position_id)?;
+        if shares >= position.amount - SHARE_EPSILON {
+            return self.close_position(position_id, proceeds);
+        }
+
+        let mut sold = position.clone();
+        sold.id = format!("{}_{}", sold.platform, &uuid::Uuid::new_v4().to_string()[..8]);
+        sold.resize(shares);
+        let remaining = position.amount - shares;
+        position.resize(remaining);
+        let position = position.clone();
+        self.persist(StoreWrite::Update(position));
+
+        let sold_id = sold.id.clone();
+        self.positions.insert(sold_id.clone(), sold.clone());
+        self.persist(StoreWrite::Insert(sold));
+        self.close_position(&sold_id, proceeds)
+    }
+
+    /// Total cost of all open positions (capital currently at risk)
+    pub fn total_open_cost(&self) -> f64 {
+        self.positions
+            .values()
+            .filter(|p| p.status == PositionStatus::Open)
+            .map(|p| p.cost)
+            .sum()
+    }
+
+    /// Mark-to-market P&L of open positions, valuing each held outcome at its
+    /// current best price. `prices` is keyed by (platform, event_id); positions
+    /// without a price are left out.
+    pub fn unrealized_pnl(&self, prices: &HashMap<(String, String), MarketPrices>) -> f64 {
+        self.positions
+            .values()
+            .filter(|p| p.status == PositionStatus::Open)
+            .filter_map(|p| {
+                let current = prices.get(&(p.platform.to_string(), p.event_id.clone()))?;
+                let mark = if normalize_outcome(&p.outcome) == "YES" {
+                    current.yes
+                } else {
+                    current.no
+                };
+                Some((mark - p.price) * p.amount)
+            })
+            .sum()
+    }
+
     /// Get total profit/loss
     pub fn get_total_profit(&self) -> f64 {
         self.positions
@@ -158,8 +592,29 @@ impl PositionTracker {
             .sum()
     }
 
+    /// Realized equity after the latest settlement (0 before any)
+    pub fn current_equity(&self) -> f64 {
+        self.equity_curve.last().map(|p| p.equity).unwrap_or(0.0)
+    }
+
+    /// Realized equity after each settlement, oldest first
+    pub fn equity_curve(&self) -> &[EquityPoint] {
+        &self.equity_curve
+    }
+
+    /// Largest peak-to-trough drop in realized equity, in USD (starting equity is 0)
+    pub fn max_drawdown(&self) -> f64 {
+        let mut peak = 0.0_f64;
+        let mut max_drawdown = 0.0_f64;
+        for point in &self.equity_curve {
+            peak = peak.max(point.equity);
+            max_drawdown = max_drawdown.max(peak - point.equity);
+        }
+        max_drawdown
+    }
+
     /// Get profit by platform
-    pub fn get_profit_by_platform(&self, platform: &str) -> f64 {
+    pub fn get_profit_by_platform(&self, platform: Platform) -> f64 {
         self.positions
             .values()
             .filter(|p| p.platform == platform)
@@ -167,30 +622,87 @@ impl PositionTracker {
             .sum()
     }
 
-    /// Get statistics
+    /// Get statistics (`unrealized_profit` is `None` - see `get_statistics_with_prices`)
     pub fn get_statistics(&self) -> PositionStatistics {
         let total = self.positions.len();
         let open = self.positions.values().filter(|p| p.status == PositionStatus::Open).count();
+        let stale = self
+            .positions
+            .values()
+            .filter(|p| p.status == PositionStatus::Open && p.stale)
+            .count();
         let won = self.positions.values().filter(|p| p.status == PositionStatus::Won).count();
         let lost = self.positions.values().filter(|p| p.status == PositionStatus::Lost).count();
         let total_profit = self.get_total_profit();
+        let open_exposure = self.total_open_cost();
+        let total_invested: f64 = self.positions.values().map(|p| p.cost).sum();
+
+        // Profit is only realized on settled or closed positions, so per-position
+        // and ROI figures divide by those alone - open capital would dilute them
+        let realized: Vec<&Position> = self.positions.values().filter(|p| p.profit.is_some()).collect();
+        let realized_cost: f64 = realized.iter().map(|p| p.cost).sum();
+
+        // Derived ratios are 0 until there's something to divide by
+        let settled = won + lost;
+        let win_rate = if settled > 0 {
+            won as f64 / settled as f64
+        } else {
+            0.0
+        };
+        let average_profit_per_position = if realized.is_empty() {
+            0.0
+        } else {
+            total_profit / realized.len() as f64
+        };
+        let overall_roi_percent = if realized_cost > 0.0 {
+            total_profit / realized_cost * 100.0
+        } else {
+            0.0
+        };
 
         PositionStatistics {
             total_positions: total,
             open_positions: open,
+            stale_positions: stale,
             won_positions: won,
             lost_positions: lost,
             total_profit,
+            open_exposure,
+            unrealized_profit: None,
+            win_rate,
+            average_profit_per_position,
+            total_invested,
+            overall_roi_percent,
+            max_drawdown: self.max_drawdown(),
+        }
+    }
+
+    /// Get statistics, marking open positions to market with `prices`
+    pub fn get_statistics_with_prices(
+        &self,
+        prices: &HashMap<(String, String), MarketPrices>,
+    ) -> PositionStatistics {
+        PositionStatistics {
+            unrealized_profit: Some(self.unrealized_pnl(prices)),
+            ..self.get_statistics()
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionStatistics {
     pub total_positions: usize,
     pub open_positions: usize,
+    pub stale_positions: usize, // Open positions flagged stale (included in open_positions)
     pub won_positions: usize,
     pub lost_positions: usize,
     pub total_profit: f64,
+    pub open_exposure: f64, // Cost of open positions
+    pub unrealized_profit: Option<f64>, // Mark-to-market P&L of open positions, when prices were supplied
+    pub win_rate: f64, // Won / settled (0-1)
+    pub average_profit_per_position: f64, // Realized profit per settled or closed position
+    pub total_invested: f64, // Cost of every position ever opened
+    pub overall_roi_percent: f64, // Realized profit / cost of settled and closed positions
+    pub max_drawdown: f64, // Worst peak-to-trough drop in realized equity
 }
 
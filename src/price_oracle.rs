@@ -0,0 +1,147 @@
+// MATIC/USD price sources, for converting Polygon gas into dollars
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+use tracing::warn;
+
+/// Source of the MATIC/USD rate used in gas cost estimates
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+    /// Current USD price of one MATIC
+    async fn matic_usd(&self) -> Result<f64>;
+}
+
+/// A fixed rate, e.g. from `MATIC_USD_PRICE`
+#[derive(Debug, Clone, Copy)]
+pub struct StaticOracle(pub f64);
+
+#[async_trait]
+impl PriceOracle for StaticOracle {
+    async fn matic_usd(&self) -> Result<f64> {
+        Ok(self.0)
+    }
+}
+
+const COINGECKO_URL: &str = "https://api.coingecko.com/api/v3";
+
+/// How long a fetched rate is reused before asking CoinGecko again
+const DEFAULT_ORACLE_TTL: Duration = Duration::from_secs(60);
+
+/// Which price source to use for MATIC/USD
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MaticPriceSource {
+    /// `matic_usd_price` from the config (default)
+    #[default]
+    Static,
+    /// Live rate from CoinGecko's simple price API
+    Coingecko,
+}
+
+/// MATIC/USD from CoinGecko, cached for a short TTL (shared across clones)
+#[derive(Debug, Clone)]
+pub struct CoingeckoOracle {
+    http_client: Client,
+    base_url: String,
+    coin_id: String,
+    ttl: Duration,
+    cached: Arc<Mutex<Option<(Instant, f64)>>>, // (fetched at, rate)
+}
+
+impl Default for CoingeckoOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CoingeckoOracle {
+    pub fn new() -> Self {
+        Self {
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            base_url: COINGECKO_URL.to_string(),
+            coin_id: "matic-network".to_string(),
+            ttl: DEFAULT_ORACLE_TTL,
+            cached: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Point at a different API root (e.g. CoinGecko Pro or a mock server)
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// CoinGecko coin ID to price (default "matic-network")
+    pub fn with_coin_id(mut self, coin_id: String) -> Self {
+        self.coin_id = coin_id;
+        self
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    async fn fetch_rate(&self) -> Result<f64> {
+        let url = format!("{}/simple/price", self.base_url);
+        let data: serde_json::Value = self
+            .http_client
+            .get(&url)
+            .query(&[("ids", self.coin_id.as_str()), ("vs_currencies", "usd")])
+            .send()
+            .await
+            .context("Failed to fetch MATIC price")?
+            .error_for_status()
+            .context("CoinGecko returned an error")?
+            .json()
+            .await
+            .context("Failed to parse CoinGecko response")?;
+
+        parse_coingecko_price(&data, &self.coin_id)
+            .with_context(|| format!("No USD price for {} in CoinGecko response", self.coin_id))
+    }
+}
+
+/// USD price from a simple price payload: `{"<coin_id>": {"usd": 0.52}}`
+fn parse_coingecko_price(data: &serde_json::Value, coin_id: &str) -> Option<f64> {
+    data.get(coin_id)?
+        .get("usd")?
+        .as_f64()
+        .filter(|price| *price > 0.0)
+}
+
+#[async_trait]
+impl PriceOracle for CoingeckoOracle {
+    async fn matic_usd(&self) -> Result<f64> {
+        let mut cached = self.cached.lock().await;
+        if let Some((fetched_at, rate)) = *cached {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(rate);
+            }
+        }
+
+        match self.fetch_rate().await {
+            Ok(rate) => {
+                *cached = Some((Instant::now(), rate));
+                Ok(rate)
+            }
+            // A slightly old rate beats no gas estimate at all
+            Err(e) => match *cached {
+                Some((_, rate)) => {
+                    warn!("⚠️ {} - reusing last MATIC price ${:.4}", e, rate);
+                    Ok(rate)
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
@@ -0,0 +1,53 @@
+// Suggestions for moving collateral between venues as balances drift apart
+
+use crate::event::Platform;
+use serde::Serialize;
+use std::fmt;
+
+/// How far (as a fraction of total capital) Polymarket's share may drift from
+/// the target before a rebalance is suggested
+pub const REBALANCE_TOLERANCE: f64 = 0.10;
+
+/// A suggested transfer of `amount` USD from one venue to the other.
+/// Transfers are manual - the bot only logs and notifies.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RebalanceAction {
+    pub from: Platform,
+    pub to: Platform,
+    pub amount: f64,
+}
+
+/// e.g. "move $250.00 from polymarket to kalshi"
+impl fmt::Display for RebalanceAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move ${:.2} from {} to {}", self.amount, self.from, self.to)
+    }
+}
+
+/// Transfer that brings Polymarket's share of the combined balance back to
+/// `target_ratio` (0.5 = split evenly), or `None` while it's within
+/// `REBALANCE_TOLERANCE` of the target (or there's nothing to move)
+#[allow(clippy::neg_cmp_op_on_partial_ord)] // Negated comparisons reject NaN too
+pub fn rebalance_plan(pm_balance: f64, kalshi_balance: f64, target_ratio: f64) -> Option<RebalanceAction> {
+    let total = pm_balance + kalshi_balance;
+    if !(total > 0.0) {
+        return None;
+    }
+
+    let target_pm = total * target_ratio.clamp(0.0, 1.0);
+    let excess = pm_balance - target_pm; // Positive when Polymarket holds too much
+    if excess.abs() <= total * REBALANCE_TOLERANCE {
+        return None;
+    }
+
+    let (from, to) = if excess > 0.0 {
+        (Platform::Polymarket, Platform::Kalshi)
+    } else {
+        (Platform::Kalshi, Platform::Polymarket)
+    };
+    Some(RebalanceAction {
+        from,
+        to,
+        amount: excess.abs(),
+    })
+}
@@ -0,0 +1,126 @@
+// Serve recorded venue API responses through the live client parsers
+
+use crate::clients::{
+    parse_clob_book, parse_kalshi_book, parse_kalshi_events, parse_polymarket_markets, DEFAULT_DEPTH_RANGE,
+};
+use crate::error::BotResult;
+use crate::event::{dedupe_events, Event, MarketPrices, Platform};
+use crate::exchange::{Exchange, Fill, Resolution, TimeInForce};
+use crate::order_book::OrderBook;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+
+/// Read-only `Exchange` backed by response bodies captured from a venue, for
+/// deterministic offline runs of the parsing, matching and detection code.
+///
+/// A fixture directory holds `events.json` (one page of the venue's events
+/// response, as returned by the API) and `books/<event_id>.json` (the order
+/// book response for each market). See `data/fixtures` for examples.
+#[derive(Debug, Clone)]
+pub struct ReplayClient {
+    platform: Platform,
+    dir: PathBuf,
+    depth_range: f64,
+}
+
+impl ReplayClient {
+    /// Replay Polymarket (Gamma GraphQL + CLOB) or Kalshi responses from `dir`
+    pub fn new(platform: Platform, dir: impl AsRef<Path>) -> Result<Self> {
+        if platform == Platform::Manifold {
+            bail!("No replay support for Manifold responses");
+        }
+        Ok(Self {
+            platform,
+            dir: dir.as_ref().to_path_buf(),
+            depth_range: DEFAULT_DEPTH_RANGE,
+        })
+    }
+
+    /// Count liquidity resting within `cents` of the best price
+    pub fn with_depth_range(mut self, cents: f64) -> Self {
+        self.depth_range = cents / 100.0;
+        self
+    }
+
+    fn read_json(&self, path: &Path) -> Result<serde_json::Value> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+        serde_json::from_str(&contents).with_context(|| format!("Invalid JSON in fixture {}", path.display()))
+    }
+
+    /// The recorded order book for `event_id`
+    pub fn fetch_order_book(&self, event_id: &str) -> Result<OrderBook> {
+        let data = self.read_json(&self.dir.join("books").join(format!("{}.json", event_id)))?;
+        Ok(match self.platform {
+            Platform::Kalshi => parse_kalshi_book(&data),
+            _ => parse_clob_book(&data),
+        })
+    }
+
+    fn read_only(&self) -> BotResult<Option<String>> {
+        Err(anyhow!("ReplayClient is read-only - orders can't be placed against recorded responses").into())
+    }
+}
+
+#[async_trait]
+impl Exchange for ReplayClient {
+    fn platform(&self) -> Platform {
+        self.platform
+    }
+
+    async fn fetch_events(&self) -> BotResult<Vec<Event>> {
+        let data = self.read_json(&self.dir.join("events.json"))?;
+        let events = match self.platform {
+            Platform::Kalshi => parse_kalshi_events(&data),
+            _ => parse_polymarket_markets(data["data"]["markets"].as_array().map(Vec::as_slice).unwrap_or_default()),
+        };
+        Ok(dedupe_events(events))
+    }
+
+    async fn fetch_prices(&self, event_id: &str) -> BotResult<MarketPrices> {
+        Ok(self.fetch_order_book(event_id)?.to_market_prices(self.depth_range))
+    }
+
+    async fn place_order(
+        &self,
+        _event_id: String,
+        _outcome: String,
+        _amount: f64,
+        _price: f64,
+        _time_in_force: TimeInForce,
+    ) -> BotResult<Option<String>> {
+        self.read_only()
+    }
+
+    async fn sell_order(
+        &self,
+        _event_id: String,
+        _outcome: String,
+        _shares: f64,
+        _min_price: f64,
+    ) -> BotResult<Option<String>> {
+        self.read_only()
+    }
+
+    async fn cancel_order(&self, _order_id: &str) -> BotResult<()> {
+        self.read_only().map(|_| ())
+    }
+
+    async fn get_order_status(&self, _order_id: &str) -> BotResult<String> {
+        Err(anyhow!("ReplayClient has no orders").into())
+    }
+
+    async fn get_fills(&self, _order_id: &str) -> BotResult<Vec<Fill>> {
+        Ok(Vec::new())
+    }
+
+    /// Recorded events never settle
+    async fn check_settlement(&self, _event_id: &str) -> BotResult<Option<Resolution>> {
+        Ok(None)
+    }
+
+    async fn get_balance(&self) -> BotResult<f64> {
+        Ok(0.0)
+    }
+}
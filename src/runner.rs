@@ -0,0 +1,828 @@
+// The bot's main loop: scans and settlement checks on their own intervals until cancelled
+
+use crate::arbitrage_detector::ArbitrageOpportunity;
+use crate::bot::{allocate, is_executable, MarketFilters, ScannedOpportunity, ShortTermArbitrageBot, TradeCooldown};
+use crate::circuit_breaker::CircuitBreaker;
+use crate::clients::PolymarketClient;
+use crate::clock::JitteredInterval;
+use crate::error::BotError;
+use crate::event::{Event, MarketPrices, Platform};
+use crate::exchange::Exchange;
+use crate::notifier::Notifiers;
+use crate::opportunity_log::{OpportunityLogger, OpportunityRecord};
+use crate::position_tracker::PositionTracker;
+use crate::price_oracle::PriceOracle;
+use crate::settlement_checker::SettlementChecker;
+use crate::trade_executor::TradeExecutor;
+use anyhow::Result;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+type PriceFuture = Pin<Box<dyn Future<Output = Result<MarketPrices>> + Send>>;
+
+/// Refreshes the Polymarket gas cost estimate before each scan
+struct GasPricing {
+    polymarket_client: Arc<PolymarketClient>,
+    price_oracle: Arc<dyn PriceOracle>,
+    fallback_matic_usd: f64, // Used when the oracle fails
+}
+
+/// Owns everything the scan and settlement loops need, so the whole runtime
+/// can be started, stopped and embedded without going through `main`
+pub struct BotRunner {
+    bot: ShortTermArbitrageBot,
+    polymarket_client: Arc<dyn Exchange>,
+    kalshi_client: Arc<dyn Exchange>,
+    manifold_client: Option<Arc<dyn Exchange>>,
+    settlement_checker: Arc<SettlementChecker>,
+    position_tracker: Arc<Mutex<PositionTracker>>,
+    execution: Execution,
+    gas_pricing: Option<GasPricing>,
+    scan_interval: JitteredInterval,
+    settlement_interval: JitteredInterval,
+    once: bool,      // Stop after the first scan pass
+    decoupled: bool, // Trade from a separate task fed over a channel
+    servers: Vec<JoinHandle<()>>,
+    shutdown: CancellationToken, // Stops the servers once the loop exits
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<crate::metrics::Metrics>>,
+    #[cfg(feature = "metrics")]
+    metrics_port: Option<u16>,
+    #[cfg(feature = "api")]
+    api_server: Option<(u16, Option<String>)>, // Port and bearer token
+}
+
+impl BotRunner {
+    pub fn new(
+        bot: ShortTermArbitrageBot,
+        polymarket_client: Arc<dyn Exchange>,
+        kalshi_client: Arc<dyn Exchange>,
+        trade_executor: Arc<TradeExecutor>,
+        settlement_checker: Arc<SettlementChecker>,
+        position_tracker: Arc<Mutex<PositionTracker>>,
+    ) -> Self {
+        let execution = Execution::new(trade_executor, position_tracker.clone(), &bot);
+        Self {
+            bot,
+            polymarket_client,
+            kalshi_client,
+            manifold_client: None,
+            settlement_checker,
+            position_tracker,
+            execution,
+            gas_pricing: None,
+            scan_interval: JitteredInterval::new(Duration::from_secs(60), 0.0),
+            settlement_interval: JitteredInterval::new(Duration::from_secs(300), 0.0),
+            once: false,
+            decoupled: false,
+            servers: Vec::new(),
+            shutdown: CancellationToken::new(),
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            #[cfg(feature = "metrics")]
+            metrics_port: None,
+            #[cfg(feature = "api")]
+            api_server: None,
+        }
+    }
+
+    /// Also fetch Manifold events (detection only - Manifold legs are never traded)
+    pub fn with_manifold_client(mut self, manifold_client: Arc<dyn Exchange>) -> Self {
+        self.manifold_client = Some(manifold_client);
+        self
+    }
+
+    /// Re-estimate Polymarket gas cost in USD before every scan, falling back
+    /// to `fallback_matic_usd` when the oracle can't be reached
+    pub fn with_gas_pricing(
+        mut self,
+        polymarket_client: Arc<PolymarketClient>,
+        price_oracle: Arc<dyn PriceOracle>,
+        fallback_matic_usd: f64,
+    ) -> Self {
+        self.gas_pricing = Some(GasPricing {
+            polymarket_client,
+            price_oracle,
+            fallback_matic_usd,
+        });
+        self
+    }
+
+    pub fn with_notifiers(mut self, notifiers: Notifiers) -> Self {
+        self.execution.notifiers = notifiers;
+        self
+    }
+
+    /// Append every detected opportunity to a JSONL log
+    pub fn with_opportunity_logger(mut self, logger: OpportunityLogger) -> Self {
+        self.execution.opportunity_logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Pause trading per `circuit_breaker` after repeated failures (default:
+    /// 15 minutes after 5 failed trades in a row)
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.execution.circuit_breaker = Arc::new(StdMutex::new(circuit_breaker));
+        self
+    }
+
+    /// Scan and settlement periods, each wait randomized by ± `jitter` (a fraction)
+    pub fn with_intervals(mut self, scan: Duration, settlement: Duration, jitter: f64) -> Self {
+        self.scan_interval = JitteredInterval::new(scan, jitter);
+        self.settlement_interval = JitteredInterval::new(settlement, jitter);
+        self
+    }
+
+    /// Find and log opportunities without placing orders
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.execution.dry_run = dry_run;
+        self
+    }
+
+    /// Return after the first scan pass instead of waiting for cancellation
+    pub fn with_single_scan(mut self, once: bool) -> Self {
+        self.once = once;
+        self
+    }
+
+    /// Hand opportunities to a separate executor task over a channel
+    /// instead of trading inline in the scan loop
+    pub fn with_decoupled_execution(mut self, decoupled: bool) -> Self {
+        self.decoupled = decoupled;
+        self
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: Arc<crate::metrics::Metrics>) -> Self {
+        self.execution.metrics = Some(metrics.clone());
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Serve `/metrics` on `port` while running (needs `with_metrics`)
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics_server(mut self, port: u16) -> Self {
+        self.metrics_port = Some(port);
+        self
+    }
+
+    /// Serve the REST API on localhost:`port` while running. Routes that
+    /// trade are disabled without `auth_token`.
+    #[cfg(feature = "api")]
+    pub fn with_api_server(mut self, port: u16, auth_token: Option<String>) -> Self {
+        self.api_server = Some((port, auth_token));
+        self
+    }
+
+    /// Scan and check settlements until `cancel` fires (or after one scan with
+    /// `with_single_scan`). Cancellation is only observed between ticks, so an
+    /// in-flight scan or settlement check always runs to completion; the
+    /// executor task finishes its current trade but discards anything still
+    /// queued. Returns once the executor, servers and position store writer
+    /// have all stopped.
+    pub async fn run(mut self, cancel: CancellationToken) {
+        self.start_servers();
+
+        // With decoupled execution, scans feed a channel drained by a separate executor task
+        let (opportunity_tx, executor_task): (_, Option<JoinHandle<()>>) = if self.decoupled {
+            let (tx, rx) = mpsc::channel(32);
+            let task = tokio::spawn(execute_from_channel(rx, self.execution.clone(), cancel.clone()));
+            info!("🔀 Decoupled execution - trades run in a separate task");
+            (Some(tx), Some(task))
+        } else {
+            (None, None)
+        };
+
+        let mut scanned = false;
+
+        loop {
+            if self.once && scanned {
+                info!("Single scan complete, exiting");
+                break;
+            }
+
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    info!("🛑 Shutdown requested, stopping...");
+                    break;
+                }
+                _ = self.scan_interval.tick() => {
+                    scanned = true;
+                    self.scan_and_trade(opportunity_tx.as_ref()).await;
+                }
+                _ = self.settlement_interval.tick() => {
+                    self.check_settlements().await;
+                }
+            }
+        }
+
+        drop(opportunity_tx);
+        if let Some(task) = executor_task {
+            if let Err(e) = task.await {
+                error!("Executor task failed: {}", e);
+            }
+        }
+
+        self.shutdown.cancel();
+        for server in self.servers.drain(..) {
+            if let Err(e) = server.await {
+                error!("Server task failed: {}", e);
+            }
+        }
+
+        // Wait for queued position store writes to land
+        let writer = self.position_tracker.lock().await.finish_store_writes();
+        if let Some(writer) = writer {
+            if let Err(e) = writer.await {
+                error!("Position store writer failed: {}", e);
+            }
+        }
+    }
+
+    /// Spawn the metrics and API servers, if configured
+    fn start_servers(&mut self) {
+        #[cfg(feature = "metrics")]
+        if let (Some(metrics), Some(port)) = (self.metrics.clone(), self.metrics_port) {
+            let shutdown = self.shutdown.clone();
+            self.servers.push(tokio::spawn(async move {
+                if let Err(e) = crate::metrics::serve(metrics, port, shutdown).await {
+                    error!("Metrics server stopped: {}", e);
+                }
+            }));
+        }
+
+        #[cfg(feature = "api")]
+        if let Some((port, auth_token)) = self.api_server.clone() {
+            let serve = crate::api::serve(
+                self.position_tracker.clone(),
+                self.settlement_checker.clone(),
+                self.bot.approval_queue(),
+                self.execution.trade_executor.clone(),
+                auth_token,
+                port,
+                self.shutdown.clone(),
+            );
+            self.servers.push(tokio::spawn(async move {
+                if let Err(e) = serve.await {
+                    error!("API server stopped: {}", e);
+                }
+            }));
+        }
+    }
+
+    /// Prices for `event_id` from whichever venue lists it
+    fn price_fetcher(&self) -> impl Fn(&str, Platform) -> PriceFuture + Clone + Send + Sync {
+        let pm = self.polymarket_client.clone();
+        let kalshi = self.kalshi_client.clone();
+        let manifold = self.manifold_client.clone();
+        move |event_id: &str, platform: Platform| {
+            let event_id = event_id.to_string();
+            let client: Option<Arc<dyn Exchange>> = match platform {
+                Platform::Polymarket => Some(pm.clone()),
+                Platform::Kalshi => Some(kalshi.clone()),
+                Platform::Manifold => manifold.clone(),
+            };
+            let prices: PriceFuture = Box::pin(async move {
+                match client {
+                    Some(client) => Ok(client.fetch_prices(&event_id).await?),
+                    None => Err(anyhow::anyhow!("MANIFOLD_API_KEY not set")),
+                }
+            });
+            prices
+        }
+    }
+
+    /// One scan pass: refresh gas cost, find opportunities and either queue
+    /// them for the executor task or trade them here
+    async fn scan_and_trade(&mut self, opportunity_tx: Option<&mpsc::Sender<ScannedOpportunity>>) {
+        // Refresh gas cost for Polymarket orders
+        if let Some(gas) = &self.gas_pricing {
+            let matic_usd = gas.price_oracle.matic_usd().await.unwrap_or_else(|e| {
+                warn!("Failed to fetch MATIC price ({}), using ${:.2}", e, gas.fallback_matic_usd);
+                gas.fallback_matic_usd
+            });
+            match gas.polymarket_client.estimate_gas_cost_usd(matic_usd).await {
+                Ok(gas_cost) => self.bot.set_gas_cost(Some(gas_cost)),
+                Err(e) => warn!("Failed to estimate gas cost: {}", e),
+            }
+        }
+
+        // Fetch events and scan for opportunities
+        let (pm_events, kalshi_events, manifold_events) = tokio::join!(
+            self.polymarket_client.fetch_events(),
+            self.kalshi_client.fetch_events(),
+            async {
+                match &self.manifold_client {
+                    Some(manifold) => manifold.fetch_events().await,
+                    None => Ok(Vec::new()),
+                }
+            }
+        );
+        let venues = [
+            pm_events.unwrap_or_default(),
+            kalshi_events.unwrap_or_default(),
+            manifold_events.unwrap_or_default(),
+        ];
+        let venues: Vec<&[Event]> = venues.iter().map(Vec::as_slice).collect();
+        let mut opportunities = self.bot.scan_venues(&venues, self.price_fetcher()).await;
+
+        // Opportunities a human approved since the last scan
+        for approved in self.bot.approval_queue().take_approved().await {
+            opportunities.push((
+                approved.pm_event,
+                approved.kalshi_event,
+                approved.opportunity,
+                approved.trade_size,
+            ));
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.scans.inc();
+            metrics.opportunities.inc_by(opportunities.len() as u64);
+        }
+
+        if opportunities.is_empty() {
+            return;
+        }
+
+        info!("Found {} arbitrage opportunities", opportunities.len());
+
+        if let Some(tx) = opportunity_tx {
+            // Size against the headroom here; the executor task still checks
+            // the exposure cap before each trade
+            let allocations = self.allocate(&opportunities).await;
+            let mut sent = vec![false; opportunities.len()];
+            for (i, trade_amount) in allocations {
+                let (pm_event, kalshi_event, opp, _) = opportunities[i].clone();
+                // Waits while the executor is behind (backpressure)
+                if tx.send((pm_event, kalshi_event, opp, trade_amount)).await.is_err() {
+                    error!("Executor task stopped, dropping opportunities");
+                    break;
+                }
+                sent[i] = true;
+            }
+            // The executor records what it was sent
+            for (opportunity, sent) in opportunities.iter().zip(sent) {
+                if !sent {
+                    self.execution.record(opportunity, false);
+                }
+            }
+            return;
+        }
+
+        let mut traded = vec![false; opportunities.len()];
+
+        // Keep scanning while the breaker is tripped, but don't trade
+        if let Some(remaining) = self.execution.paused() {
+            warn!(
+                "⏸️ Trading paused by circuit breaker ({}s remaining)",
+                remaining.as_secs()
+            );
+            self.record_opportunities(&opportunities, &traded);
+            return;
+        }
+
+        let allocations = self.allocate(&opportunities).await;
+
+        // Execute trades for found opportunities
+        for (i, trade_amount) in allocations {
+            let (pm_event, kalshi_event, opp, _) = &opportunities[i];
+            traded[i] = self.execution.execute(pm_event, kalshi_event, opp, trade_amount).await;
+
+            if self.execution.paused().is_some() {
+                break;
+            }
+        }
+
+        self.record_opportunities(&opportunities, &traded);
+    }
+
+    /// Spend the remaining exposure headroom on the best returns first,
+    /// gas included (see `allocate`). Returns (index, per-leg USD) pairs.
+    async fn allocate(&self, opportunities: &[ScannedOpportunity]) -> Vec<(usize, f64)> {
+        let max_total_exposure = self.bot.filters().max_total_exposure;
+        let open_cost = self.position_tracker.lock().await.total_open_cost();
+        let gas_cost = self.bot.arbitrage_detector().gas_cost();
+        let allocations = allocate(opportunities, max_total_exposure - open_cost, gas_cost);
+        if allocations.len() < opportunities.len() {
+            info!(
+                "💼 Capital allocated to {} of {} opportunities (${:.2} available)",
+                allocations.len(),
+                opportunities.len(),
+                max_total_exposure - open_cost
+            );
+        }
+        allocations
+    }
+
+    /// Append what was seen (and whether it traded) to the opportunity log
+    fn record_opportunities(&self, opportunities: &[ScannedOpportunity], traded: &[bool]) {
+        for (opportunity, traded) in opportunities.iter().zip(traded) {
+            self.execution.record(opportunity, *traded);
+        }
+    }
+
+    async fn check_settlements(&self) {
+        info!("Checking for settled positions...");
+        match self.settlement_checker.check_settlements().await {
+            Ok(count) => {
+                if count > 0 {
+                    info!("✅ {} positions settled!", count);
+
+                    // Show statistics
+                    let stats = self.settlement_checker.get_statistics().await;
+                    info!(
+                        "📊 Statistics - Total: {}, Open: {} ({} stale), Won: {}, Lost: {}, Total Profit: ${:.2}, Exposure: ${:.2}, Win Rate: {:.1}%, ROI: {:.2}%, Max Drawdown: ${:.2}",
+                        stats.total_positions,
+                        stats.open_positions,
+                        stats.stale_positions,
+                        stats.won_positions,
+                        stats.lost_positions,
+                        stats.total_profit,
+                        stats.open_exposure,
+                        stats.win_rate * 100.0,
+                        stats.overall_roi_percent,
+                        stats.max_drawdown
+                    );
+                } else {
+                    info!("No new settlements");
+                }
+            }
+            Err(e) => {
+                error!("Error checking settlements: {}", e);
+            }
+        }
+
+        // After settling, so the gauges reflect this cycle's resolutions
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.update_positions(&self.settlement_checker.get_statistics().await);
+        }
+
+        // Check balances every cycle, so low-balance alerts fire before trades start failing
+        if let Err(e) = self.settlement_checker.check_balances().await {
+            warn!("Failed to check balances: {}", e);
+        }
+    }
+}
+
+/// What trading one opportunity needs, shared by the inline scan loop and
+/// the decoupled executor task so both apply the same checks, circuit
+/// breaker, notifications, metrics and opportunity log
+#[derive(Clone)]
+struct Execution {
+    trade_executor: Arc<TradeExecutor>,
+    position_tracker: Arc<Mutex<PositionTracker>>,
+    filters: MarketFilters, // For the exposure cap
+    cooldown: TradeCooldown,
+    circuit_breaker: Arc<StdMutex<CircuitBreaker>>,
+    notifiers: Notifiers,
+    opportunity_logger: Option<Arc<OpportunityLogger>>,
+    dry_run: bool,
+    #[cfg(feature = "metrics")]
+    metrics: Option<Arc<crate::metrics::Metrics>>,
+}
+
+impl Execution {
+    fn new(
+        trade_executor: Arc<TradeExecutor>,
+        position_tracker: Arc<Mutex<PositionTracker>>,
+        bot: &ShortTermArbitrageBot,
+    ) -> Self {
+        Self {
+            trade_executor,
+            position_tracker,
+            filters: bot.filters().clone(),
+            cooldown: bot.trade_cooldown(),
+            // Overridden from config via `BotRunner::with_circuit_breaker`
+            circuit_breaker: Arc::new(StdMutex::new(CircuitBreaker::default())),
+            notifiers: Notifiers::default(),
+            opportunity_logger: None,
+            dry_run: false,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+        }
+    }
+
+    fn circuit_breaker(&self) -> std::sync::MutexGuard<'_, CircuitBreaker> {
+        self.circuit_breaker.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Time left before trading resumes, while the circuit breaker is tripped
+    fn paused(&self) -> Option<Duration> {
+        self.circuit_breaker().remaining_cooldown()
+    }
+
+    /// Trade one opportunity if it is executable, out of cooldown and within
+    /// the exposure cap (earlier trades may have landed since the scan).
+    /// Returns whether both legs were placed.
+    async fn execute(&self, pm_event: &Event, kalshi_event: &Event, opp: &ArbitrageOpportunity, trade_amount: f64) -> bool {
+        info!(
+            pm_event_id = %pm_event.event_id,
+            kalshi_event_id = %kalshi_event.event_id,
+            net_profit = opp.net_profit,
+            roi_percent = opp.roi_percent,
+            trade_size = trade_amount,
+            "🚨 Arbitrage Opportunity: {} - {}",
+            pm_event.title,
+            opp
+        );
+        self.notifiers.opportunity(pm_event, kalshi_event, opp, trade_amount);
+
+        if !is_executable(pm_event, kalshi_event) {
+            info!("👀 Not trading {} - Manifold legs are detection only", pm_event.title);
+            return false;
+        }
+
+        if self.cooldown.is_active(&pm_event.event_id, pm_event.platform)
+            || self.cooldown.is_active(&kalshi_event.event_id, kalshi_event.platform)
+        {
+            info!("⏭️ Skipping {} - traded recently", pm_event.title);
+            return false;
+        }
+
+        // Skip if this trade (both legs) would push exposure over the cap
+        let open_cost = self.position_tracker.lock().await.total_open_cost();
+        if !self.filters.within_exposure_limit(open_cost, trade_amount * 2.0) {
+            warn!(
+                "⚠️ Skipping trade - exposure ${:.2} + ${:.2} would exceed the cap",
+                open_cost,
+                trade_amount * 2.0
+            );
+            return false;
+        }
+
+        if self.dry_run {
+            info!("🧪 Dry run - would execute: {}", opp);
+            return false;
+        }
+
+        let result = self
+            .trade_executor
+            .execute_arbitrage(opp, pm_event, kalshi_event, trade_amount)
+            .await;
+        if let Ok(result) = &result {
+            if !result.skipped {
+                self.notifiers.trade(result);
+            }
+        }
+
+        let traded = match result {
+            Ok(result) if result.success => {
+                self.circuit_breaker().record_success();
+                self.cooldown.record(pm_event, kalshi_event);
+                #[cfg(feature = "metrics")]
+                if let Some(metrics) = &self.metrics {
+                    metrics.trades_executed.inc();
+                }
+                info!(
+                    "✅ Trade executed successfully! PM Order: {:?}, Kalshi Order: {:?}",
+                    result.polymarket_order_id, result.kalshi_order_id
+                );
+                true
+            }
+            Ok(result) if result.skipped => {
+                info!("⏭️ Trade skipped: {}", result.error.unwrap_or_default());
+                false
+            }
+            Ok(result) => {
+                self.record_failure();
+                info!(
+                    "⚠️ Trade execution failed: {}",
+                    result.error.unwrap_or_default()
+                );
+                false
+            }
+            // Running short of funds isn't a venue failure - don't trip the breaker
+            Err(e @ BotError::InsufficientBalance(_)) => {
+                warn!("⏭️ Trade skipped: {}", e);
+                false
+            }
+            Err(e) => {
+                self.record_failure();
+                error!("Error executing trade: {}", e);
+                false
+            }
+        };
+
+        if self.circuit_breaker().is_tripped() {
+            error!("🛑 Circuit breaker tripped after repeated failures - pausing trading");
+        }
+        traded
+    }
+
+    fn record_failure(&self) {
+        self.circuit_breaker().record_failure();
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.metrics {
+            metrics.trades_failed.inc();
+        }
+    }
+
+    /// Append what was seen (and whether it traded) to the opportunity log
+    fn record(&self, (pm_event, kalshi_event, opp, trade_amount): &ScannedOpportunity, traded: bool) {
+        let Some(logger) = &self.opportunity_logger else {
+            return;
+        };
+        let record = OpportunityRecord::new(pm_event, kalshi_event, opp, *trade_amount, traded);
+        if let Err(e) = logger.log(&record) {
+            warn!("⚠️ {}", e);
+        }
+    }
+}
+
+/// Consumer for decoupled execution: executes opportunities in the order
+/// they were found, through the same checks as inline trading. Once `cancel`
+/// fires, whatever is still queued is logged as untraded and dropped.
+async fn execute_from_channel(
+    mut rx: mpsc::Receiver<ScannedOpportunity>,
+    execution: Execution,
+    cancel: CancellationToken,
+) {
+    loop {
+        let opportunity = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => break,
+            opportunity = rx.recv() => match opportunity {
+                Some(opportunity) => opportunity,
+                None => return,
+            },
+        };
+        let traded = match execution.paused() {
+            Some(remaining) => {
+                warn!(
+                    "⏸️ Trading paused by circuit breaker ({}s remaining), dropping {}",
+                    remaining.as_secs(),
+                    opportunity.0.title
+                );
+                false
+            }
+            None => {
+                let (pm_event, kalshi_event, opp, trade_amount) = &opportunity;
+                execution.execute(pm_event, kalshi_event, opp, *trade_amount).await
+            }
+        };
+        execution.record(&opportunity, traded);
+    }
+
+    rx.close();
+    let mut discarded = 0;
+    while let Some(opportunity) = rx.recv().await {
+        execution.record(&opportunity, false);
+        discarded += 1;
+    }
+    if discarded > 0 {
+        info!("🛑 Discarded {} queued opportunities on shutdown", discarded);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage_detector::ArbitrageDetector;
+    use crate::mock::MockExchange;
+
+    fn opportunity(i: usize) -> ScannedOpportunity {
+        let pm_event = Event::new(Platform::Polymarket, format!("pm-{}", i), format!("Event {}", i), String::new());
+        let kalshi_event = Event::new(Platform::Kalshi, format!("K-{}", i), format!("Event {}", i), String::new());
+        let opp = ArbitrageDetector::new(0.0)
+            .check_arbitrage(&MarketPrices::new(0.40, 0.60, 1000.0), &MarketPrices::new(0.55, 0.45, 1000.0))
+            .unwrap();
+        (pm_event, kalshi_event, opp, 10.0)
+    }
+
+    #[tokio::test]
+    async fn channel_executes_opportunities_in_order() {
+        let pm = Arc::new(MockExchange::new(Platform::Polymarket));
+        let kalshi = Arc::new(MockExchange::new(Platform::Kalshi));
+        let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+        let executor = Arc::new(TradeExecutor::new(pm.clone(), kalshi.clone()).with_position_tracker(tracker.clone()));
+        let bot = ShortTermArbitrageBot::new(MarketFilters::default(), 0.5, 0.0);
+        let execution = Execution::new(executor, tracker.clone(), &bot);
+
+        let (tx, rx) = mpsc::channel(8);
+        for i in 0..5 {
+            tx.send(opportunity(i)).await.unwrap();
+        }
+        drop(tx);
+        execute_from_channel(rx, execution, CancellationToken::new()).await;
+
+        let traded: Vec<String> = pm.orders().into_iter().map(|order| order.event_id).collect();
+        assert_eq!(traded, ["pm-0", "pm-1", "pm-2", "pm-3", "pm-4"]);
+        assert_eq!(tracker.lock().await.get_open_positions().len(), 10);
+    }
+
+    #[tokio::test]
+    async fn channel_respects_the_circuit_breaker() {
+        let pm = Arc::new(MockExchange::new(Platform::Polymarket).with_rejected_orders());
+        let kalshi = Arc::new(MockExchange::new(Platform::Kalshi));
+        let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+        let executor = Arc::new(TradeExecutor::new(pm.clone(), kalshi.clone()));
+        let bot = ShortTermArbitrageBot::new(MarketFilters::default(), 0.5, 0.0);
+        let mut execution = Execution::new(executor, tracker, &bot);
+        execution.circuit_breaker = Arc::new(StdMutex::new(CircuitBreaker::new(2, Duration::from_secs(60))));
+
+        let (tx, rx) = mpsc::channel(8);
+        for i in 0..5 {
+            tx.send(opportunity(i)).await.unwrap();
+        }
+        drop(tx);
+        execute_from_channel(rx, execution.clone(), CancellationToken::new()).await;
+
+        // Two failures trip the breaker; the rest are dropped without ordering
+        let buys = kalshi.orders().into_iter().filter(|order| !order.sell).count();
+        assert_eq!(buys, 2);
+        assert!(execution.paused().is_some());
+    }
+
+    #[tokio::test]
+    async fn cancelled_channel_discards_queued_opportunities() {
+        let pm = Arc::new(MockExchange::new(Platform::Polymarket));
+        let kalshi = Arc::new(MockExchange::new(Platform::Kalshi));
+        let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+        let executor = Arc::new(TradeExecutor::new(pm.clone(), kalshi.clone()));
+        let bot = ShortTermArbitrageBot::new(MarketFilters::default(), 0.5, 0.0);
+        let execution = Execution::new(executor, tracker, &bot);
+
+        let (tx, rx) = mpsc::channel(8);
+        for i in 0..5 {
+            tx.send(opportunity(i)).await.unwrap();
+        }
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        execute_from_channel(rx, execution, cancel).await;
+
+        assert!(pm.orders().is_empty());
+        assert!(tx.is_closed());
+    }
+
+    #[tokio::test]
+    async fn decoupled_scan_sends_allocated_sizes() {
+        let resolves = chrono::Utc::now() + chrono::Duration::hours(2);
+        let event = |platform, id: &str| {
+            Event::new(platform, id.to_string(), "Will BTC be above $70,000?".to_string(), String::new())
+                .with_category("Crypto".to_string())
+                .with_resolution_date(resolves)
+        };
+        let pm: Arc<dyn Exchange> = Arc::new(
+            MockExchange::new(Platform::Polymarket)
+                .with_events(vec![event(Platform::Polymarket, "pm-btc")])
+                .with_prices("pm-btc", MarketPrices::new(0.40, 0.60, 10_000.0)),
+        );
+        let kalshi: Arc<dyn Exchange> = Arc::new(
+            MockExchange::new(Platform::Kalshi)
+                .with_events(vec![event(Platform::Kalshi, "KXBTC")])
+                .with_prices("KXBTC", MarketPrices::new(0.55, 0.45, 10_000.0)),
+        );
+        let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+        let executor = Arc::new(TradeExecutor::new(pm.clone(), kalshi.clone()));
+        let checker = Arc::new(SettlementChecker::new(pm.clone(), kalshi.clone(), tracker.clone()));
+        // $120 of headroom covers 60 per leg of a $100 opportunity
+        let filters = MarketFilters {
+            max_position_size: 100.0,
+            max_total_exposure: 120.0,
+            ..MarketFilters::default()
+        };
+        let bot = ShortTermArbitrageBot::new(filters, 0.5, 0.0);
+        let mut runner = BotRunner::new(bot, pm, kalshi, executor, checker, tracker).with_decoupled_execution(true);
+
+        let (tx, mut rx) = mpsc::channel(8);
+        runner.scan_and_trade(Some(&tx)).await;
+        drop(tx);
+
+        let (pm_event, _, _, trade_amount) = rx.recv().await.expect("opportunity sent");
+        assert_eq!(pm_event.event_id, "pm-btc");
+        assert!((trade_amount - 60.0).abs() < 1e-9, "sent ${}", trade_amount);
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn run_returns_after_cancel() {
+        let pm: Arc<dyn Exchange> = Arc::new(MockExchange::new(Platform::Polymarket));
+        let kalshi: Arc<dyn Exchange> = Arc::new(MockExchange::new(Platform::Kalshi));
+        let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+        let executor = Arc::new(TradeExecutor::new(pm.clone(), kalshi.clone()));
+        let checker = Arc::new(SettlementChecker::new(pm.clone(), kalshi.clone(), tracker.clone()));
+        let bot = ShortTermArbitrageBot::new(MarketFilters::default(), 0.5, 0.0);
+        let runner = BotRunner::new(bot, pm, kalshi, executor, checker, tracker)
+            .with_intervals(Duration::from_millis(10), Duration::from_millis(15), 0.0)
+            .with_decoupled_execution(true);
+
+        let cancel = CancellationToken::new();
+        let task = tokio::spawn(runner.run(cancel.clone()));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        cancel.cancel();
+
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .expect("run returns once cancelled")
+            .unwrap();
+    }
+}
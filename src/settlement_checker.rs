@@ -1,57 +1,129 @@
-use crate::clients::{KalshiClient, PolymarketClient};
-use crate::position_tracker::{Position, PositionStatus, PositionTracker};
-use anyhow::Result;
-use std::sync::Arc;
+use crate::error::BotResult;
+use crate::event::Platform;
+use crate::exchange::{Exchange, Resolution};
+use crate::notifier::Notifiers;
+use crate::rebalance::{rebalance_plan, RebalanceAction};
+use crate::position_tracker::{Position, PositionTracker};
+use anyhow::anyhow;
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 use tracing::{info, warn};
 
+/// Settlement cycles to keep asking a venue for a settled position's actual
+/// payout before keeping the computed one
+const MAX_PAYOUT_CHECKS: u32 = 10;
+
 pub struct SettlementChecker {
-    polymarket_client: Arc<PolymarketClient>,
-    kalshi_client: Arc<KalshiClient>,
+    polymarket_client: Arc<dyn Exchange>,
+    kalshi_client: Arc<dyn Exchange>,
+    manifold_client: Option<Arc<dyn Exchange>>,
     position_tracker: Arc<tokio::sync::Mutex<PositionTracker>>,
+    max_concurrent_checks: usize,
+    min_balance_alert: Option<f64>,
+    notifiers: Notifiers,
+    low_balance_alerted: Mutex<HashSet<Platform>>, // Venues alerted since their balance last recovered
+    rebalance_target: Option<f64>, // Target Polymarket share of combined balance
+    last_rebalance: Mutex<Option<RebalanceAction>>, // Last plan notified, until balances are back in range
+    pending_payouts: Mutex<Vec<(Position, u32)>>, // Settled positions whose payout isn't reported yet, with checks so far
 }
 
 impl SettlementChecker {
     pub fn new(
-        polymarket_client: Arc<PolymarketClient>,
-        kalshi_client: Arc<KalshiClient>,
+        polymarket_client: Arc<dyn Exchange>,
+        kalshi_client: Arc<dyn Exchange>,
         position_tracker: Arc<tokio::sync::Mutex<PositionTracker>>,
     ) -> Self {
         Self {
             polymarket_client,
             kalshi_client,
+            manifold_client: None,
             position_tracker,
+            max_concurrent_checks: 8,
+            min_balance_alert: None,
+            notifiers: Notifiers::default(),
+            low_balance_alerted: Mutex::new(HashSet::new()),
+            rebalance_target: None,
+            last_rebalance: Mutex::new(None),
+            pending_payouts: Mutex::new(Vec::new()),
         }
     }
 
-    /// Check all open positions for settlement
-    pub async fn check_settlements(&self) -> Result<usize> {
-        let mut settled_count = 0;
-        let tracker = self.position_tracker.lock().await;
-        let open_positions = tracker.get_open_positions();
-        drop(tracker); // Release lock before async operations
-
-        for position in open_positions {
-            let position_id = position.id.clone();
-            let event_id = position.event_id.clone();
-            let outcome = position.outcome.clone();
-            let platform = position.platform.clone();
-
-            // Check settlement based on platform
-            let settlement_result = match platform.as_str() {
-                "polymarket" => {
-                    self.polymarket_client.check_settlement(&event_id).await
-                }
-                "kalshi" => {
-                    self.kalshi_client.check_settlement(&event_id).await
-                }
-                _ => Ok(None),
-            };
+    /// Also check positions held on Manifold
+    pub fn with_manifold_client(mut self, manifold_client: Arc<dyn Exchange>) -> Self {
+        self.manifold_client = Some(manifold_client);
+        self
+    }
+
+    /// Limit how many settlement checks run at once (default 8)
+    pub fn with_max_concurrent_checks(mut self, max_concurrent_checks: usize) -> Self {
+        self.max_concurrent_checks = max_concurrent_checks.max(1);
+        self
+    }
+
+    /// Warn (and notify) from `check_balances` when a venue's balance drops
+    /// below `threshold`. Each venue alerts once until its balance recovers.
+    pub fn with_low_balance_alert(mut self, threshold: f64, notifiers: Notifiers) -> Self {
+        self.min_balance_alert = Some(threshold);
+        self.notifiers = notifiers;
+        self
+    }
+
+    /// Suggest moving collateral from `check_balances` once Polymarket's share
+    /// of the combined balance drifts from `target_ratio`. Notifications go to
+    /// the notifiers set with `with_notifiers` or `with_low_balance_alert`.
+    pub fn with_rebalance_target(mut self, target_ratio: f64) -> Self {
+        self.rebalance_target = Some(target_ratio);
+        self
+    }
+
+    pub fn with_notifiers(mut self, notifiers: Notifiers) -> Self {
+        self.notifiers = notifiers;
+        self
+    }
 
+    /// Check all open positions for settlement, up to `max_concurrent_checks` at a time
+    pub async fn check_settlements(&self) -> BotResult<usize> {
+        // Snapshot open positions so the lock isn't held across API calls
+        let open_positions: Vec<Position> = {
+            let tracker = self.position_tracker.lock().await;
+            tracker.get_open_positions().into_iter().cloned().collect()
+        };
+
+        let results: Vec<(Position, BotResult<Option<Resolution>>)> = stream::iter(open_positions)
+            .map(|position| async move {
+                // Check settlement based on platform
+                let result = match self.client(position.platform) {
+                    Ok(client) => client.check_settlement(&position.event_id).await,
+                    Err(e) => Err(e),
+                };
+                (position, result)
+            })
+            .buffer_unordered(self.max_concurrent_checks)
+            .collect()
+            .await;
+
+        // Apply all settlements under a single lock
+        let mut settled = Vec::new();
+        let mut tracker = self.position_tracker.lock().await;
+
+        for (position, settlement_result) in results {
             match settlement_result {
-                Ok(Some(resolved_yes)) => {
+                Ok(Some(Resolution::Void)) => {
+                    // No winner - assume the stake comes back until the venue
+                    // reports what it actually paid
+                    if let Some(profit) = tracker.void_position(&position.id, position.cost) {
+                        settled.push(position.clone());
+                        info!(
+                            "↩️ Position settled: {} - VOID - Profit: ${:.2}",
+                            position.event_title, profit
+                        );
+                    }
+                }
+                Ok(Some(resolution)) => {
                     // Event is settled!
-                    let won = (resolved_yes && outcome == "YES") 
-                        || (!resolved_yes && outcome == "NO");
+                    let won = resolution.won(&position.outcome) == Some(true);
 
                     let payout = if won {
                         Some(position.amount * 1.0) // $1.00 per token/share
@@ -60,13 +132,12 @@ impl SettlementChecker {
                     };
 
                     // Update position
-                    let mut tracker = self.position_tracker.lock().await;
                     if let Some(profit) = tracker.update_position_settlement(
-                        &position_id,
+                        &position.id,
                         won,
                         payout,
                     ) {
-                        settled_count += 1;
+                        settled.push(position.clone());
                         info!(
                             "✅ Position settled: {} - {} - Profit: ${:.2}",
                             position.event_title,
@@ -79,21 +150,110 @@ impl SettlementChecker {
                     // Event not yet settled, continue waiting
                 }
                 Err(e) => {
-                    warn!("Error checking settlement for {}: {}", event_id, e);
+                    warn!("Error checking settlement for {}: {}", position.event_id, e);
                 }
             }
         }
 
+        // Flag positions that have outlived max_open_age for manual review
+        let stale: Vec<Position> = tracker
+            .find_stale_positions(Utc::now())
+            .into_iter()
+            .filter(|p| !p.stale)
+            .cloned()
+            .collect();
+        for position in stale {
+            if tracker.mark_stale(&position.id) {
+                warn!(
+                    "⏳ Position {} has been open since {} - flagged stale, review or close it",
+                    position,
+                    position.created_at.format("%Y-%m-%d %H:%M UTC")
+                );
+            }
+        }
+        drop(tracker);
+
+        let settled_count = settled.len();
+        self.reconcile_payouts(settled).await;
         Ok(settled_count)
     }
 
+    /// Replace the assumed payout of settled positions with what the venue
+    /// actually paid. Positions the venue hasn't reported on yet are retried on
+    /// later cycles, up to `MAX_PAYOUT_CHECKS` times.
+    async fn reconcile_payouts(&self, settled: Vec<Position>) {
+        let mut to_check: Vec<(Position, u32)> = std::mem::take(&mut *self.pending_payouts.lock().unwrap());
+        to_check.extend(settled.into_iter().map(|position| (position, 0)));
+
+        let payouts: Vec<(Position, u32, BotResult<Option<f64>>)> = stream::iter(to_check)
+            .map(|(position, checks)| async move {
+                let result = match self.client(position.platform) {
+                    Ok(client) => {
+                        client
+                            .settlement_payout_per_share(&position.event_id, &position.outcome)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                (position, checks + 1, result)
+            })
+            .buffer_unordered(self.max_concurrent_checks)
+            .collect()
+            .await;
+
+        let mut retry = Vec::new();
+        let mut tracker = self.position_tracker.lock().await;
+        for (position, checks, result) in payouts {
+            match result {
+                Ok(Some(per_share)) => {
+                    tracker.reconcile_payout(&position.id, per_share * position.amount);
+                    continue;
+                }
+                Ok(None) => {} // Not reported (yet)
+                Err(e) => warn!("Could not fetch actual payout for {}: {}", position.event_id, e),
+            }
+            if checks < MAX_PAYOUT_CHECKS {
+                retry.push((position, checks));
+            } else {
+                info!("No payout reported for {} - keeping the computed one", position);
+            }
+        }
+        drop(tracker);
+        self.pending_payouts.lock().unwrap().extend(retry);
+    }
+
+    fn client(&self, platform: Platform) -> BotResult<&dyn Exchange> {
+        match platform {
+            Platform::Polymarket => Ok(self.polymarket_client.as_ref()),
+            Platform::Kalshi => Ok(self.kalshi_client.as_ref()),
+            Platform::Manifold => self
+                .manifold_client
+                .as_deref()
+                .ok_or_else(|| anyhow!("No Manifold client configured").into()),
+        }
+    }
+
     /// Check balances on both platforms
-    pub async fn check_balances(&self) -> Result<(f64, f64)> {
-        let (pm_balance, kalshi_balance) = tokio::join!(
+    pub async fn check_balances(&self) -> BotResult<(f64, f64)> {
+        let (pm_balance, kalshi_balance, gas_balance) = tokio::join!(
             self.polymarket_client.get_balance(),
-            self.kalshi_client.get_balance()
+            self.kalshi_client.get_balance(),
+            self.polymarket_client.get_gas_balance()
         );
 
+        // Only balances that were actually fetched can trigger (or re-arm) an alert
+        if let Ok(balance) = &pm_balance {
+            self.check_low_balance(Platform::Polymarket, *balance);
+        }
+        if let Ok(balance) = &kalshi_balance {
+            self.check_low_balance(Platform::Kalshi, *balance);
+        }
+
+        // A failed fetch would look like an empty venue - only plan on real balances
+        if let (Ok(pm), Ok(kalshi)) = (&pm_balance, &kalshi_balance) {
+            self.check_rebalance(*pm, *kalshi);
+        }
+
         let pm_balance = pm_balance.unwrap_or(0.0);
         let kalshi_balance = kalshi_balance.unwrap_or(0.0);
 
@@ -104,9 +264,51 @@ impl SettlementChecker {
             pm_balance + kalshi_balance
         );
 
+        match gas_balance {
+            Ok(Some(matic)) => info!("⛽ Gas balance - Polymarket wallet: {:.4} MATIC", matic),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to check MATIC balance: {}", e),
+        }
+
         Ok((pm_balance, kalshi_balance))
     }
 
+    /// Alert the first time `platform`'s balance is seen below the threshold,
+    /// and re-arm once it's back at or above it
+    fn check_low_balance(&self, platform: Platform, balance: f64) {
+        let Some(threshold) = self.min_balance_alert else {
+            return;
+        };
+
+        let mut alerted = self.low_balance_alerted.lock().unwrap_or_else(|e| e.into_inner());
+        if balance < threshold {
+            if alerted.insert(platform) {
+                warn!("🪫 {} balance ${:.2} is below the ${:.2} alert threshold", platform, balance, threshold);
+                self.notifiers.low_balance(platform, balance, threshold);
+            }
+        } else if alerted.remove(&platform) {
+            info!("🔋 {} balance recovered to ${:.2}", platform, balance);
+        }
+    }
+
+    /// Log a rebalance plan every check while balances are out of range, but
+    /// only notify when a plan first appears or switches direction
+    fn check_rebalance(&self, pm_balance: f64, kalshi_balance: f64) {
+        let Some(target_ratio) = self.rebalance_target else {
+            return;
+        };
+
+        let plan = rebalance_plan(pm_balance, kalshi_balance, target_ratio);
+        let mut last = self.last_rebalance.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(action) = &plan {
+            warn!("⚖️ Balances drifted from target - {}", action);
+            if last.is_none_or(|last| last.from != action.from) {
+                self.notifiers.rebalance(action);
+            }
+        }
+        *last = plan;
+    }
+
     /// Get position statistics
     pub async fn get_statistics(&self) -> crate::position_tracker::PositionStatistics {
         let tracker = self.position_tracker.lock().await;
@@ -1,64 +1,189 @@
-use crate::arbitrage_detector::ArbitrageOpportunity;
-use crate::clients::{KalshiClient, PolymarketClient};
-use crate::event::Event;
+use crate::arbitrage_detector::{ArbitrageDetector, ArbitrageOpportunity, OrderAction};
+use crate::error::{BotError, BotResult};
+use crate::exchange::{Exchange, Fill, TimeInForce};
+use crate::event::{normalize_outcome, Event, Platform};
 use crate::position_tracker::{Position, PositionTracker};
-use anyhow::Result;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 
-#[derive(Debug, Clone)]
+/// Share counts closer than this are treated as equal
+const SHARE_EPSILON: f64 = 1e-6;
+
+/// How far below the entry price we'll sell excess shares to rebalance
+const REBALANCE_SLIPPAGE: f64 = 0.05;
+
+/// Order statuses (lowercased, across venues) meaning nothing has executed
+const UNFILLED_STATUSES: &[&str] = &["resting", "live", "open", "canceled", "cancelled"];
+
+/// Order statuses meaning the order is still working and may fill more
+const OPEN_STATUSES: &[&str] = &["resting", "live", "open", "pending"];
+
+/// How often to re-check an order that's still resting
+const ORDER_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One placed leg of an arbitrage and how much of it filled
+struct FilledLeg<'a> {
+    client: &'a dyn Exchange,
+    position: &'a mut Position,
+    fills: Vec<Fill>, // Venue-reported executions so far
+    limit_price: f64, // Price the leg was ordered at
+    expected: f64,    // Shares ordered
+    filled: f64,      // Shares filled
+}
+
+impl FilledLeg<'_> {
+    fn is_partial(&self) -> bool {
+        self.filled < self.expected - SHARE_EPSILON
+    }
+}
+
+/// Shares filled on a leg. Without fill data, an order that ended unfilled
+/// (per its final `status`) counts as zero; otherwise `expected` is assumed.
+fn filled_shares(fills: &[Fill], status: Option<&str>, expected: f64) -> f64 {
+    if !fills.is_empty() {
+        return fills.iter().map(|f| f.shares).sum();
+    }
+    match status {
+        Some(status) if UNFILLED_STATUSES.contains(&status) => 0.0,
+        _ => expected,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeResult {
     pub success: bool,
+    pub skipped: bool, // No orders placed because the opportunity went away
     pub polymarket_order_id: Option<String>,
     pub kalshi_order_id: Option<String>,
     pub error: Option<String>,
 }
 
 pub struct TradeExecutor {
-    polymarket_client: PolymarketClient,
-    kalshi_client: KalshiClient,
+    polymarket_client: Arc<dyn Exchange>,
+    kalshi_client: Arc<dyn Exchange>,
+    manifold_client: Option<Arc<dyn Exchange>>,
     position_tracker: Option<Arc<Mutex<PositionTracker>>>,
+    arbitrage_detector: Option<ArbitrageDetector>,
+    min_gas_balance: f64, // Native token (MATIC) needed to pay for an order
+    time_in_force: TimeInForce,
+    fill_timeout: Duration, // How long a resting order may keep filling before the rest is cancelled
+    dry_run: bool, // Log closes instead of selling
 }
 
 impl TradeExecutor {
-    pub fn new(polymarket_client: PolymarketClient, kalshi_client: KalshiClient) -> Self {
+    pub fn new(polymarket_client: Arc<dyn Exchange>, kalshi_client: Arc<dyn Exchange>) -> Self {
         Self {
             polymarket_client,
             kalshi_client,
+            manifold_client: None,
             position_tracker: None,
+            arbitrage_detector: None,
+            min_gas_balance: 0.0,
+            time_in_force: TimeInForce::Ioc,
+            fill_timeout: Duration::from_secs(10),
+            dry_run: false,
         }
     }
 
+    /// Only log the sales `close_all_open` would make
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Client for positions and orders on Manifold
+    pub fn with_manifold_client(mut self, manifold_client: Arc<dyn Exchange>) -> Self {
+        self.manifold_client = Some(manifold_client);
+        self
+    }
+
+    /// Time in force for both legs (default IOC, so unfilled size never rests)
+    pub fn with_time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+
+    /// How long to wait for a resting leg to fill before cancelling the
+    /// remainder and rebalancing (default 10s; IOC orders finish at once)
+    pub fn with_fill_timeout(mut self, fill_timeout: Duration) -> Self {
+        self.fill_timeout = fill_timeout;
+        self
+    }
+
+    /// Refuse to trade while the Polymarket wallet holds less than `min_matic` for gas
+    pub fn with_min_gas_balance(mut self, min_matic: f64) -> Self {
+        self.min_gas_balance = min_matic;
+        self
+    }
+
+    /// Re-check opportunities with `detector` right before placing orders
+    pub fn with_arbitrage_detector(mut self, detector: ArbitrageDetector) -> Self {
+        self.arbitrage_detector = Some(detector);
+        self
+    }
+
     pub fn with_position_tracker(mut self, tracker: Arc<Mutex<PositionTracker>>) -> Self {
         self.position_tracker = Some(tracker);
         self
     }
 
-    /// Execute arbitrage trade on both platforms simultaneously
+    /// Execute arbitrage trade on both platforms simultaneously, buying the
+    /// same number of shares on each leg with at most `amount` spent on either
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            pm_event_id = %pm_event.event_id,
+            kalshi_event_id = %kalshi_event.event_id,
+            strategy = %opportunity.strategy,
+            net_profit = opportunity.net_profit,
+            amount = amount,
+        )
+    )]
     pub async fn execute_arbitrage(
         &self,
         opportunity: &ArbitrageOpportunity,
         pm_event: &Event,
         kalshi_event: &Event,
         amount: f64,
-    ) -> Result<TradeResult> {
-        info!(
-            "Executing arbitrage: {} - Expected profit: ${:.4} ({:.2}% ROI)",
-            opportunity.strategy, opportunity.net_profit, opportunity.roi_percent
-        );
+    ) -> BotResult<TradeResult> {
+        info!(roi_percent = opportunity.roi_percent, "Executing arbitrage: {}", opportunity);
+
+        // Prices may have moved since the scan - skip if the edge is gone
+        if !self.revalidate(opportunity, pm_event, kalshi_event, amount).await? {
+            return Ok(TradeResult {
+                success: false,
+                skipped: true,
+                polymarket_order_id: None,
+                kalshi_order_id: None,
+                error: Some("Opportunity no longer profitable".to_string()),
+            });
+        }
+
+        // Same share count on both legs so either outcome pays the same;
+        // the dearer leg spends `amount`, the cheaper one less
+        let pm_price = opportunity.polymarket_action.price;
+        let kalshi_price = opportunity.kalshi_action.price;
+        let shares = amount / pm_price.max(kalshi_price);
+        let (pm_amount, kalshi_amount) = (shares * pm_price, shares * kalshi_price);
+
+        // Make sure both legs can be paid for before placing either order
+        self.check_affordability(pm_amount, kalshi_amount).await?;
 
         // Execute trades simultaneously on both platforms
         let (pm_result, kalshi_result) = tokio::join!(
             self.execute_polymarket_trade(
                 pm_event,
                 &opportunity.polymarket_action,
-                amount
+                pm_amount
             ),
             self.execute_kalshi_trade(
                 kalshi_event,
                 &opportunity.kalshi_action,
-                amount
+                kalshi_amount
             )
         );
 
@@ -76,48 +201,107 @@ impl TradeExecutor {
             let pm_order_id = pm_result.unwrap();
             let kalshi_order_id = kalshi_result.unwrap();
 
+            let arb_id = uuid::Uuid::new_v4().to_string();
+
+            // Polymarket position
+            let mut pm_position = Position::new(
+                Platform::Polymarket,
+                pm_event,
+                opportunity.polymarket_action.outcome.clone(),
+                shares,
+                pm_amount, // cost (USDC spent on this leg)
+                pm_price,
+                pm_order_id.clone(),
+            )
+            .with_arb_id(arb_id.clone());
+
+            // Kalshi position
+            let mut kalshi_position = Position::new(
+                Platform::Kalshi,
+                kalshi_event,
+                opportunity.kalshi_action.outcome.clone(),
+                shares,
+                kalshi_amount, // cost (USDC spent on this leg)
+                kalshi_price,
+                kalshi_order_id.clone(),
+            )
+            .with_arb_id(arb_id);
+
+            // Let resting orders finish (cancelling what's left at the timeout)
+            // so the fills read next are final
+            let (pm_status, kalshi_status) = tokio::join!(
+                self.await_terminal_status(self.polymarket_client.as_ref(), pm_order_id.as_deref()),
+                self.await_terminal_status(self.kalshi_client.as_ref(), kalshi_order_id.as_deref())
+            );
+
+            // Record what actually filled rather than the limit price
+            let (pm_fills, kalshi_fills) = tokio::join!(
+                self.fetch_fills(self.polymarket_client.as_ref(), pm_order_id.as_deref()),
+                self.fetch_fills(self.kalshi_client.as_ref(), kalshi_order_id.as_deref())
+            );
+            pm_position.apply_fills(&pm_fills);
+            kalshi_position.apply_fills(&kalshi_fills);
+
+            // A partially filled leg leaves the trade unhedged - rebalance the difference
+            let mut pm_leg = FilledLeg {
+                client: self.polymarket_client.as_ref(),
+                filled: filled_shares(&pm_fills, pm_status.as_deref(), shares),
+                position: &mut pm_position,
+                fills: pm_fills,
+                limit_price: pm_price,
+                expected: shares,
+            };
+            let mut kalshi_leg = FilledLeg {
+                client: self.kalshi_client.as_ref(),
+                filled: filled_shares(&kalshi_fills, kalshi_status.as_deref(), shares),
+                position: &mut kalshi_position,
+                fills: kalshi_fills,
+                limit_price: kalshi_price,
+                expected: shares,
+            };
+            let hedged = if pm_leg.is_partial() || kalshi_leg.is_partial() {
+                Some(self.rebalance_partial_fill(&mut pm_leg, &mut kalshi_leg).await)
+            } else {
+                None
+            };
+
+            if let Some(hedged) = hedged {
+                if hedged <= SHARE_EPSILON {
+                    warn!("⚠️ Neither leg filled enough to hedge - no position recorded");
+                    return Ok(TradeResult {
+                        success: false,
+                        skipped: false,
+                        polymarket_order_id: pm_order_id,
+                        kalshi_order_id,
+                        error: Some("Orders did not fill".to_string()),
+                    });
+                }
+                // Only the balanced portion is an arbitrage position
+                pm_position.resize(hedged);
+                kalshi_position.resize(hedged);
+            }
+
             // Track positions if tracker is available
             if let Some(tracker) = &self.position_tracker {
                 let mut tracker = tracker.lock().await;
-                
-                // Track Polymarket position
-                let pm_position = Position::new(
-                    "polymarket".to_string(),
-                    pm_event,
-                    opportunity.polymarket_action.1.clone(), // outcome
-                    amount / opportunity.polymarket_action.2, // amount / price
-                    amount * opportunity.polymarket_action.2, // cost
-                    opportunity.polymarket_action.2, // price
-                    pm_order_id.clone(),
-                );
                 tracker.add_position(pm_position);
-
-                // Track Kalshi position
-                let kalshi_position = Position::new(
-                    "kalshi".to_string(),
-                    kalshi_event,
-                    opportunity.kalshi_action.1.clone(), // outcome
-                    amount / opportunity.kalshi_action.2, // amount / price
-                    amount * opportunity.kalshi_action.2, // cost
-                    opportunity.kalshi_action.2, // price
-                    kalshi_order_id.clone(),
-                );
                 tracker.add_position(kalshi_position);
             }
 
             Ok(TradeResult {
                 success: true,
+                skipped: false,
                 polymarket_order_id: pm_order_id,
-                kalshi_order_id: kalshi_order_id,
+                kalshi_order_id,
                 error: None,
             })
         } else {
             // One or both trades failed
             let mut errors = Vec::new();
-            if let Err(e) = pm_result {
+            if let Err(e) = &pm_result {
                 errors.push(format!("Polymarket: {}", e));
             }
-            if let Err(e) = kalshi_result {
+            if let Err(e) = &kalshi_result {
                 errors.push(format!("Kalshi: {}", e));
             }
 
@@ -125,16 +309,33 @@ impl TradeExecutor {
 
             warn!("⚠️ Arbitrage execution failed: {}", error_msg);
 
-            // If one succeeded, we need to cancel it (or handle partial execution)
-            if pm_success {
-                warn!("Polymarket trade succeeded but Kalshi failed - may need to cancel PM trade");
+            // A leg that went through on its own is unhedged - sell it back
+            if let Ok(order_id) = &pm_result {
+                warn!("Polymarket trade succeeded but Kalshi failed - unwinding the Polymarket leg");
+                self.unwind_leg(
+                    self.polymarket_client.as_ref(),
+                    pm_event,
+                    &opportunity.polymarket_action,
+                    order_id.as_deref(),
+                    shares,
+                )
+                .await;
             }
-            if kalshi_success {
-                warn!("Kalshi trade succeeded but Polymarket failed - may need to cancel Kalshi trade");
+            if let Ok(order_id) = &kalshi_result {
+                warn!("Kalshi trade succeeded but Polymarket failed - unwinding the Kalshi leg");
+                self.unwind_leg(
+                    self.kalshi_client.as_ref(),
+                    kalshi_event,
+                    &opportunity.kalshi_action,
+                    order_id.as_deref(),
+                    shares,
+                )
+                .await;
             }
 
             Ok(TradeResult {
                 success: false,
+                skipped: false,
                 polymarket_order_id: pm_result.ok().flatten(),
                 kalshi_order_id: kalshi_result.ok().flatten(),
                 error: Some(error_msg),
@@ -142,28 +343,291 @@ impl TradeExecutor {
         }
     }
 
+    /// Poll an order until it stops resting, cancelling whatever is still
+    /// open after `fill_timeout`. Returns the last status seen, if any.
+    async fn await_terminal_status(&self, client: &dyn Exchange, order_id: Option<&str>) -> Option<String> {
+        let order_id = order_id?;
+        let deadline = tokio::time::Instant::now() + self.fill_timeout;
+        loop {
+            let status = match client.get_order_status(order_id).await {
+                Ok(status) => status,
+                Err(e) => {
+                    warn!("Could not read {} order {} status: {}", client.platform(), order_id, e);
+                    return None;
+                }
+            };
+            if !OPEN_STATUSES.contains(&status.as_str()) {
+                return Some(status);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                info!("Cancelling unfilled remainder of {} order {}", client.platform(), order_id);
+                if let Err(e) = client.cancel_order(order_id).await {
+                    error!("Failed to cancel remainder of {} order {}: {}", client.platform(), order_id, e);
+                }
+                return Some(status);
+            }
+            tokio::time::sleep(ORDER_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Even out two legs after a partial fill: first try to buy the short
+    /// leg's shortfall at its original limit, then sell whatever the fuller leg
+    /// still holds beyond the other. Returns the hedged share count.
+    async fn rebalance_partial_fill<'a>(&self, pm_leg: &mut FilledLeg<'a>, kalshi_leg: &mut FilledLeg<'a>) -> f64 {
+        warn!(
+            "⚠️ Partial fill - Polymarket {:.2}/{:.2}, Kalshi {:.2}/{:.2} shares",
+            pm_leg.filled, pm_leg.expected, kalshi_leg.filled, kalshi_leg.expected
+        );
+
+        let (short, long) = if pm_leg.filled < kalshi_leg.filled {
+            (pm_leg, kalshi_leg)
+        } else {
+            (kalshi_leg, pm_leg)
+        };
+
+        let shortfall = long.filled - short.filled;
+        if shortfall > SHARE_EPSILON {
+            self.buy_up(short, shortfall).await;
+        }
+
+        let hedged = short.filled.min(long.filled);
+        let excess = long.filled - hedged;
+        if excess > SHARE_EPSILON {
+            let platform = long.client.platform();
+            let min_price = long.position.price * (1.0 - REBALANCE_SLIPPAGE);
+            match long
+                .client
+                .sell_order(
+                    long.position.event_id.clone(),
+                    long.position.outcome.clone(),
+                    excess,
+                    min_price,
+                )
+                .await
+            {
+                Ok(_) => info!("Sold {:.2} excess shares on {} to rebalance", excess, platform),
+                Err(e) => error!(
+                    "❌ Failed to sell {:.2} excess shares on {} - position is unhedged: {}",
+                    excess, platform, e
+                ),
+            }
+        }
+
+        hedged
+    }
+
+    /// Sell back whatever filled of a leg whose counterpart was never placed,
+    /// at its limit less `REBALANCE_SLIPPAGE`
+    async fn unwind_leg(
+        &self,
+        client: &dyn Exchange,
+        event: &Event,
+        action: &OrderAction,
+        order_id: Option<&str>,
+        expected: f64,
+    ) {
+        let platform = client.platform();
+        let status = self.await_terminal_status(client, order_id).await;
+        let fills = self.fetch_fills(client, order_id).await;
+        let filled = filled_shares(&fills, status.as_deref(), expected);
+        if filled <= SHARE_EPSILON {
+            return;
+        }
+
+        let min_price = action.price * (1.0 - REBALANCE_SLIPPAGE);
+        match client
+            .sell_order(event.event_id.clone(), action.outcome.clone(), filled, min_price)
+            .await
+        {
+            Ok(_) => info!("Sold back {:.2} unhedged shares on {}", filled, platform),
+            Err(e) => error!(
+                "❌ Failed to sell back {:.2} shares on {} - position is unhedged: {}",
+                filled, platform, e
+            ),
+        }
+    }
+
+    /// Buy up to `shares` more of a leg at its original limit (IOC), adding
+    /// whatever fills to the leg
+    async fn buy_up(&self, leg: &mut FilledLeg<'_>, shares: f64) {
+        let platform = leg.client.platform();
+        let order_id = match leg
+            .client
+            .place_order(
+                leg.position.event_id.clone(),
+                leg.position.outcome.clone(),
+                shares * leg.limit_price,
+                leg.limit_price,
+                TimeInForce::Ioc,
+            )
+            .await
+        {
+            Ok(order_id) => order_id,
+            Err(e) => {
+                warn!("Could not buy {:.2} shares on {} to rebalance: {}", shares, platform, e);
+                return;
+            }
+        };
+
+        let status = self.await_terminal_status(leg.client, order_id.as_deref()).await;
+        let fills = self.fetch_fills(leg.client, order_id.as_deref()).await;
+        let bought = filled_shares(&fills, status.as_deref(), shares);
+        if bought <= SHARE_EPSILON {
+            return;
+        }
+
+        info!("Bought {:.2} more shares on {} to rebalance", bought, platform);
+        if fills.is_empty() {
+            // No fill data - grow at the average price so far
+            leg.position.resize(leg.position.amount + bought);
+        } else {
+            leg.fills.extend(fills);
+            leg.position.apply_fills(&leg.fills);
+        }
+        leg.filled += bought;
+    }
+
+    /// Fills for `order_id`, or none (keeping the estimate) if they can't be fetched
+    async fn fetch_fills(&self, client: &dyn Exchange, order_id: Option<&str>) -> Vec<Fill> {
+        let Some(order_id) = order_id else {
+            return Vec::new();
+        };
+        match client.get_fills(order_id).await {
+            Ok(fills) => fills,
+            Err(e) => {
+                warn!(
+                    "Could not fetch {} fills for {}, recording limit price: {}",
+                    client.platform(),
+                    order_id,
+                    e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Re-fetch both markets and check the same legs are still profitable
+    /// when buying `amount` of each, at the fill price and with gas counted.
+    /// Always true when no detector is configured.
+    pub async fn revalidate(
+        &self,
+        opportunity: &ArbitrageOpportunity,
+        pm_event: &Event,
+        kalshi_event: &Event,
+        amount: f64,
+    ) -> BotResult<bool> {
+        let Some(detector) = &self.arbitrage_detector else {
+            return Ok(true);
+        };
+
+        let (pm_prices, kalshi_prices) = tokio::join!(
+            self.polymarket_client.fetch_prices(&pm_event.event_id),
+            self.kalshi_client.fetch_prices(&kalshi_event.event_id)
+        );
+        let pm_prices = pm_prices.context("Failed to refresh Polymarket prices")?;
+        let kalshi_prices = kalshi_prices.context("Failed to refresh Kalshi prices")?;
+
+        // Both legs on the same outcome means an inverted match - view Kalshi
+        // in Polymarket's polarity, as the scan did
+        let inverted = opportunity.polymarket_action.outcome == opportunity.kalshi_action.outcome;
+        let kalshi_prices = if inverted {
+            kalshi_prices.inverted()
+        } else {
+            kalshi_prices
+        };
+
+        // Price both legs at the average fill for this size (None if the book
+        // is now too thin to fill it)
+        let current = pm_prices
+            .at_size(amount)
+            .zip(kalshi_prices.at_size(amount))
+            .and_then(|(pm_fill, kalshi_fill)| detector.check_arbitrage_for_size(&pm_fill, &kalshi_fill, amount));
+
+        match current {
+            Some(current) if current.polymarket_action.outcome == opportunity.polymarket_action.outcome => {
+                info!(
+                    "Revalidated opportunity: profit ${:.4} -> ${:.4}",
+                    opportunity.net_profit, current.net_profit
+                );
+                Ok(true)
+            }
+            _ => {
+                warn!(
+                    "⚠️ Opportunity on {} no longer profitable, skipping trade",
+                    pm_event.title
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// Verify both accounts hold enough balance for their leg.
+    /// Errors (without placing anything) if either side is short or unreachable.
+    pub async fn check_affordability(&self, pm_cost: f64, kalshi_cost: f64) -> BotResult<()> {
+        let (pm_balance, kalshi_balance, gas_balance) = tokio::join!(
+            self.polymarket_client.get_balance(),
+            self.kalshi_client.get_balance(),
+            self.polymarket_client.get_gas_balance()
+        );
+
+        let pm_balance = pm_balance.context("Failed to check Polymarket balance")?;
+        let kalshi_balance = kalshi_balance.context("Failed to check Kalshi balance")?;
+        let gas_balance = gas_balance.context("Failed to check MATIC balance")?;
+
+        let mut shortfalls = Vec::new();
+        if let Some(gas_balance) = gas_balance {
+            if gas_balance < self.min_gas_balance {
+                shortfalls.push(format!(
+                    "MATIC balance {:.4} < required {:.4} for gas",
+                    gas_balance, self.min_gas_balance
+                ));
+            }
+        }
+        if pm_balance < pm_cost {
+            shortfalls.push(format!(
+                "Polymarket balance ${:.2} < required ${:.2}",
+                pm_balance, pm_cost
+            ));
+        }
+        if kalshi_balance < kalshi_cost {
+            shortfalls.push(format!(
+                "Kalshi balance ${:.2} < required ${:.2}",
+                kalshi_balance, kalshi_cost
+            ));
+        }
+
+        if !shortfalls.is_empty() {
+            let error_msg = shortfalls.join("; ");
+            warn!("⚠️ Insufficient balance, skipping trade: {}", error_msg);
+            return Err(BotError::InsufficientBalance(error_msg));
+        }
+
+        Ok(())
+    }
+
     /// Execute trade on Polymarket
     async fn execute_polymarket_trade(
         &self,
         event: &Event,
-        action: &(String, String, f64), // (action, outcome, price)
+        action: &OrderAction,
         amount: f64,
-    ) -> Result<Option<String>> {
-        let (action_type, outcome, max_price) = action;
+    ) -> BotResult<Option<String>> {
+        let OrderAction { side: action_type, outcome, price: max_price } = action;
 
         info!(
-            "Placing {} order on Polymarket: {} {} @ ${:.4} (amount: ${:.2})",
+            "Placing {} order on Polymarket: {} @ ${:.4} (amount: ${:.2})",
             action_type, outcome, max_price, amount
         );
 
         // Execute actual Polymarket trade
-        match self
+        let order_id = match self
             .polymarket_client
             .place_order(
                 event.event_id.clone(),
                 outcome.clone(),
                 amount,
                 *max_price,
+                self.time_in_force,
             )
             .await
         {
@@ -172,34 +636,35 @@ impl TradeExecutor {
                 error!("Polymarket order failed: {}", e);
                 return Err(e);
             }
-        }
-        
-        info!("✅ Polymarket order placed: {}", order_id);
-        Ok(Some(order_id))
+        };
+
+        info!("✅ Polymarket order placed: {:?}", order_id);
+        Ok(order_id)
     }
 
     /// Execute trade on Kalshi
     async fn execute_kalshi_trade(
         &self,
         event: &Event,
-        action: &(String, String, f64), // (action, outcome, price)
+        action: &OrderAction,
         amount: f64,
-    ) -> Result<Option<String>> {
-        let (action_type, outcome, price) = action;
+    ) -> BotResult<Option<String>> {
+        let OrderAction { side: action_type, outcome, price } = action;
 
         info!(
-            "Placing {} order on Kalshi: {} {} @ ${:.4} (amount: ${:.2})",
+            "Placing {} order on Kalshi: {} @ ${:.4} (amount: ${:.2})",
             action_type, outcome, price, amount
         );
 
         // Execute actual Kalshi trade
-        match self
+        let order_id = match self
             .kalshi_client
             .place_order(
                 event.event_id.clone(),
                 outcome.clone(),
                 amount,
                 *price,
+                self.time_in_force,
             )
             .await
         {
@@ -208,45 +673,141 @@ impl TradeExecutor {
                 error!("Kalshi order failed: {}", e);
                 return Err(e);
             }
-        }
-        
-        info!("✅ Kalshi order placed: {}", order_id);
-        Ok(Some(order_id))
+        };
+
+        info!("✅ Kalshi order placed: {:?}", order_id);
+        Ok(order_id)
     }
 
-    /// Cancel an order (if needed due to partial execution)
-    pub async fn cancel_order(&self, platform: &str, order_id: &str) -> Result<()> {
+    fn client(&self, platform: Platform) -> BotResult<&dyn Exchange> {
         match platform {
-            "polymarket" => {
-                // TODO: Implement Polymarket order cancellation
-                info!("Cancelling Polymarket order: {}", order_id);
-                Ok(())
-            }
-            "kalshi" => {
-                // TODO: Implement Kalshi order cancellation
-                info!("Cancelling Kalshi order: {}", order_id);
-                Ok(())
-            }
-            _ => {
-                error!("Unknown platform: {}", platform);
-                Err(anyhow::anyhow!("Unknown platform: {}", platform))
-            }
+            Platform::Polymarket => Ok(self.polymarket_client.as_ref()),
+            Platform::Kalshi => Ok(self.kalshi_client.as_ref()),
+            Platform::Manifold => self
+                .manifold_client
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("No Manifold client configured").into()),
         }
     }
 
-    /// Get order status
-    pub async fn get_order_status(&self, platform: &str, order_id: &str) -> Result<String> {
-        match platform {
-            "polymarket" => {
-                // TODO: Implement Polymarket order status check
-                Ok("filled".to_string())
+    /// Emergency exit: sell every open position in `tracker` back to its venue
+    /// and mark it closed. One result per position, in no particular order.
+    /// Positions on a venue without a configured client are skipped (left open).
+    pub async fn close_all_open(&self, tracker: &Mutex<PositionTracker>) -> Vec<BotResult<TradeResult>> {
+        // Snapshot so the lock isn't held across API calls
+        let open_positions: Vec<Position> = {
+            let tracker = tracker.lock().await;
+            tracker.get_open_positions().into_iter().cloned().collect()
+        };
+        if self.dry_run {
+            for position in &open_positions {
+                info!("🧪 Dry run - would close {}", position);
             }
-            "kalshi" => {
-                // TODO: Implement Kalshi order status check
-                Ok("filled".to_string())
+            return open_positions
+                .iter()
+                .map(|_| {
+                    Ok(TradeResult {
+                        success: false,
+                        skipped: true,
+                        polymarket_order_id: None,
+                        kalshi_order_id: None,
+                        error: Some("Dry run".to_string()),
+                    })
+                })
+                .collect();
+        }
+        warn!("🚨 Closing {} open positions", open_positions.len());
+
+        let mut results = Vec::with_capacity(open_positions.len());
+        for position in open_positions {
+            let result = self.close_position(tracker, &position).await;
+            if let Err(e) = &result {
+                error!("❌ Failed to close {}: {}", position, e);
             }
-            _ => Err(anyhow::anyhow!("Unknown platform: {}", platform)),
+            results.push(result);
         }
+        results
+    }
+
+    /// Sell one position at its current price (less `REBALANCE_SLIPPAGE`),
+    /// recording in `tracker` only the shares that filled, at their real proceeds
+    async fn close_position(&self, tracker: &Mutex<PositionTracker>, position: &Position) -> BotResult<TradeResult> {
+        let client = match self.client(position.platform) {
+            Ok(client) => client,
+            Err(e) => {
+                warn!("⏭️ Can't close {} - {}", position, e);
+                return Ok(TradeResult {
+                    success: false,
+                    skipped: true,
+                    polymarket_order_id: None,
+                    kalshi_order_id: None,
+                    error: Some(e.to_string()),
+                });
+            }
+        };
+
+        let price = self.exit_price(position).await?;
+        let order_id = client
+            .sell_order(
+                position.event_id.clone(),
+                position.outcome.clone(),
+                position.amount,
+                price * (1.0 - REBALANCE_SLIPPAGE),
+            )
+            .await
+            .with_context(|| format!("Failed to sell {} shares on {}", position.amount, position.platform))?;
+
+        let fills = match &order_id {
+            Some(order_id) => client
+                .get_fills(order_id)
+                .await
+                .with_context(|| format!("Failed to read fills for sell order {}", order_id))?,
+            None => Vec::new(),
+        };
+        let sold = fills.iter().map(|f| f.shares).sum::<f64>().min(position.amount);
+        let proceeds: f64 = fills.iter().map(|f| f.cost() - f.fee).sum();
+
+        let error = if sold <= 0.0 {
+            warn!("⚠️ Sell order for {} filled nothing - position stays open", position);
+            Some("Sell order filled nothing".to_string())
+        } else {
+            tracker.lock().await.close_shares(&position.id, sold, proceeds);
+            (sold < position.amount - 1e-6)
+                .then(|| format!("Only {:.4} of {:.4} shares sold", sold, position.amount))
+        };
+
+        Ok(TradeResult {
+            success: error.is_none(),
+            skipped: false,
+            polymarket_order_id: order_id.clone().filter(|_| position.platform == Platform::Polymarket),
+            kalshi_order_id: order_id.filter(|_| position.platform == Platform::Kalshi),
+            error,
+        })
+    }
+
+    /// Current price of the outcome `position` holds
+    async fn exit_price(&self, position: &Position) -> BotResult<f64> {
+        let prices = self
+            .client(position.platform)?
+            .fetch_prices(&position.event_id)
+            .await
+            .with_context(|| format!("Failed to fetch {} prices for {}", position.platform, position.event_id))?;
+        Ok(if normalize_outcome(&position.outcome) == "YES" {
+            prices.yes
+        } else {
+            prices.no
+        })
+    }
+
+    /// Cancel an order (if needed due to partial execution)
+    pub async fn cancel_order(&self, platform: Platform, order_id: &str) -> BotResult<()> {
+        info!("Cancelling {} order: {}", platform, order_id);
+        self.client(platform)?.cancel_order(order_id).await
+    }
+
+    /// Get order status
+    pub async fn get_order_status(&self, platform: Platform, order_id: &str) -> BotResult<String> {
+        self.client(platform)?.get_order_status(order_id).await
     }
 }
 
@@ -0,0 +1,94 @@
+// REST API served over a real socket
+
+#![cfg(feature = "api")]
+
+use polymarket_kalshi_arbitrage_bot::{
+    api, ApprovalQueue, Event, MockExchange, Platform, Position, PositionTracker, SettlementChecker, TradeExecutor,
+};
+use serde_json::Value;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Serve the API over a tracker holding one won and one open position
+async fn serve(auth_token: Option<&str>) -> String {
+    let event = Event::new(Platform::Kalshi, "KXBTC".into(), "BTC above $70k".into(), String::new());
+    let won = Position::new(Platform::Kalshi, &event, "YES".into(), 100.0, 40.0, 0.40, None);
+    let open = Position::new(Platform::Kalshi, &event, "NO".into(), 50.0, 25.0, 0.50, None);
+    let won_id = won.id.clone();
+    let mut tracker = PositionTracker::new();
+    tracker.add_position(won);
+    tracker.add_position(open);
+    tracker.update_position_settlement(&won_id, true, Some(100.0));
+
+    let pm: Arc<MockExchange> = Arc::new(MockExchange::new(Platform::Polymarket));
+    let kalshi: Arc<MockExchange> = Arc::new(MockExchange::new(Platform::Kalshi));
+    let tracker = Arc::new(Mutex::new(tracker));
+    let checker = Arc::new(SettlementChecker::new(pm.clone(), kalshi.clone(), tracker.clone()));
+    let executor = Arc::new(TradeExecutor::new(pm, kalshi));
+    let app = api::router(
+        tracker,
+        checker,
+        ApprovalQueue::default(),
+        executor,
+        auth_token.map(str::to_string),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    url
+}
+
+#[tokio::test]
+async fn statistics_reflect_the_tracker() {
+    let url = serve(None).await;
+
+    let stats: Value = reqwest::get(format!("{}/statistics", url))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(stats["total_positions"], 2);
+    assert_eq!(stats["open_positions"], 1);
+    assert_eq!(stats["won_positions"], 1);
+    assert_eq!(stats["lost_positions"], 0);
+    assert_eq!(stats["total_profit"], 60.0);
+    assert_eq!(stats["open_exposure"], 25.0);
+    assert_eq!(stats["win_rate"], 1.0);
+}
+
+#[tokio::test]
+async fn trading_routes_need_the_token() {
+    let url = serve(Some("secret")).await;
+    let client = reqwest::Client::new();
+    let close_all = format!("{}/positions/close-all", url);
+
+    let missing = client.post(&close_all).send().await.unwrap();
+    let wrong = client.post(&close_all).bearer_auth("guess").send().await.unwrap();
+    let approve = client
+        .post(format!("{}/approvals/unknown/approve", url))
+        .bearer_auth("secret")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(missing.status(), 401);
+    assert_eq!(wrong.status(), 401);
+    assert_eq!(approve.status(), 404); // Authorized, but nothing to approve
+}
+
+#[tokio::test]
+async fn trading_routes_are_disabled_without_a_token() {
+    let url = serve(None).await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/positions/close-all", url))
+        .bearer_auth("")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 403);
+}
@@ -0,0 +1,298 @@
+// Venue clients against a local mock server
+
+mod common;
+
+use common::MockServer;
+use polymarket_kalshi_arbitrage_bot::{
+    BotError, KalshiClient, ManifoldClient, Platform, PolymarketClient, RateLimiter, RetryPolicy, TimeInForce,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+const POLYMARKET_EVENTS: &str = include_str!("../data/fixtures/polymarket/events.json");
+const KALSHI_EVENTS: &str = include_str!("../data/fixtures/kalshi/events.json");
+
+#[tokio::test]
+async fn polymarket_fetch_events_from_base_url() {
+    let server = MockServer::start(&[("/graphql", POLYMARKET_EVENTS)]).await;
+    let client = PolymarketClient::new().with_base_url(server.url.clone());
+
+    let events = client.fetch_events().await.unwrap();
+
+    let ids: Vec<&str> = events.iter().map(|e| e.event_id.as_str()).collect();
+    assert_eq!(ids, ["pm-btc-70k", "pm-lakers-celtics"]);
+    assert!(events.iter().all(|e| e.platform == Platform::Polymarket));
+    assert_eq!(server.requests(), ["POST /graphql"]);
+}
+
+#[tokio::test]
+async fn kalshi_fetch_events_from_base_url() {
+    let server = MockServer::start(&[("/trade-api/v2/events", KALSHI_EVENTS)]).await;
+    let client = KalshiClient::new(String::new(), String::new()).with_base_url(server.url.clone());
+
+    let events = client.fetch_events().await.unwrap();
+
+    let ids: Vec<&str> = events.iter().map(|e| e.event_id.as_str()).collect();
+    assert_eq!(ids, ["KXBTC-24JUN02-T70000", "KXNBA-24JUN02-LAL"]);
+    assert_eq!(events[0].category.as_deref(), Some("Crypto"));
+    assert_eq!(server.requests().len(), 1);
+    assert!(server.requests()[0].starts_with("GET /trade-api/v2/events?"));
+}
+
+#[tokio::test]
+async fn kalshi_failures_map_to_error_variants() {
+    let server = MockServer::start(&[]).await;
+    let client = KalshiClient::new(String::new(), String::new()).with_base_url(server.url.clone());
+
+    let missing = client.fetch_events().await;
+    assert!(matches!(missing, Err(BotError::Api { status: 404, .. })));
+
+    let bad_price = client
+        .place_order("KXBTC".into(), "YES".into(), 10.0, 1.5, TimeInForce::Ioc)
+        .await;
+    assert!(matches!(bad_price, Err(BotError::InvalidPrice(_))));
+    assert_eq!(server.requests().len(), 1, "invalid orders are never sent");
+}
+
+#[tokio::test]
+async fn shared_request_limit_caps_in_flight_requests() {
+    let server = MockServer::with_delay(
+        &[
+            ("/graphql", r#"{"data": {"markets": []}}"#),
+            ("/trade-api/v2/events", r#"{"events": [], "cursor": ""}"#),
+        ],
+        Duration::from_millis(50),
+    )
+    .await;
+    let request_limit = Arc::new(Semaphore::new(3));
+    let polymarket = PolymarketClient::new()
+        .with_base_url(server.url.clone())
+        .with_request_limit(request_limit.clone());
+    let kalshi = KalshiClient::new(String::new(), String::new())
+        .with_base_url(server.url.clone())
+        .with_rate_limit(1000.0)
+        .with_request_limit(request_limit);
+
+    // Clones share the limit too
+    let requests = (0..10).flat_map(|_| {
+        let (polymarket, kalshi) = (polymarket.clone(), kalshi.clone());
+        [
+            tokio::spawn(async move { polymarket.force_refresh().await.map(|_| ()) }),
+            tokio::spawn(async move { kalshi.force_refresh().await.map(|_| ()) }),
+        ]
+    });
+    for result in futures::future::join_all(requests).await {
+        result.unwrap().unwrap();
+    }
+
+    assert_eq!(server.requests().len(), 20);
+    assert!(server.max_in_flight() <= 3, "{} requests in flight", server.max_in_flight());
+}
+
+#[tokio::test]
+async fn retries_rate_limits_and_server_errors() {
+    let server = MockServer::start(&[("/trade-api/v2/events", KALSHI_EVENTS)]).await;
+    server.fail_next(&[429, 503]);
+    let client = KalshiClient::new(String::new(), String::new())
+        .with_base_url(server.url.clone())
+        .with_retry_policy(RetryPolicy::new(3, Duration::from_millis(1)));
+
+    let events = client.fetch_events().await.unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_the_last_retry() {
+    let server = MockServer::start(&[("/trade-api/v2/events", KALSHI_EVENTS)]).await;
+    server.fail_next(&[500, 502, 500, 500]);
+    let client = KalshiClient::new(String::new(), String::new())
+        .with_base_url(server.url.clone())
+        .with_retry_policy(RetryPolicy::new(2, Duration::from_millis(1)));
+
+    let result = client.fetch_events().await;
+
+    assert!(matches!(result, Err(BotError::Api { status: 500, .. })), "{:?}", result);
+    assert_eq!(server.requests().len(), 3, "one attempt plus two retries");
+}
+
+#[tokio::test]
+async fn rate_limiter_spaces_requests_at_the_configured_rate() {
+    let limiter = Arc::new(RateLimiter::new(50.0));
+    let interval = Duration::from_millis(20);
+    let start = Instant::now();
+
+    let requests = (0..20).map(|_| {
+        let limiter = limiter.clone();
+        tokio::spawn(async move {
+            limiter.acquire().await;
+            start.elapsed()
+        })
+    });
+    let mut times: Vec<Duration> = futures::future::join_all(requests)
+        .await
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    times.sort();
+
+    // The nth request never goes out before its slot, n intervals in
+    for (n, elapsed) in times.iter().enumerate() {
+        assert!(*elapsed >= interval * n as u32, "request {} sent after {:?}", n, elapsed);
+    }
+    let total = times[19];
+    assert!(total < interval * 19 + Duration::from_millis(200), "20 requests took {:?}", total);
+}
+
+#[tokio::test]
+async fn kalshi_follows_the_cursor_until_it_runs_out() {
+    let first_page = r#"{"events": [{"event_ticker": "KXBTC-A", "title": "BTC above $70k"}], "cursor": "page-2"}"#;
+    let last_page = r#"{"events": [{"event_ticker": "KXETH-B", "title": "ETH above $4k"}], "cursor": ""}"#;
+    let server = MockServer::start(&[
+        ("/trade-api/v2/events", first_page),
+        ("/trade-api/v2/events", last_page),
+    ])
+    .await;
+    let client = KalshiClient::new(String::new(), String::new()).with_base_url(server.url.clone());
+
+    let events = client.fetch_events().await.unwrap();
+
+    let ids: Vec<&str> = events.iter().map(|e| e.event_id.as_str()).collect();
+    assert_eq!(ids, ["KXBTC-A", "KXETH-B"]);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2, "an empty cursor ends the loop");
+    assert!(!requests[0].contains("cursor="));
+    assert!(requests[1].contains("cursor=page-2"), "{}", requests[1]);
+}
+
+#[tokio::test]
+async fn event_cache_serves_repeat_fetches_until_the_ttl_expires() {
+    let page = r#"{"events": [{"event_ticker": "KXBTC-A", "title": "BTC above $70k"}], "cursor": ""}"#;
+    let server = MockServer::start(&[("/trade-api/v2/events", page)]).await;
+    let client = KalshiClient::new(String::new(), String::new())
+        .with_base_url(server.url.clone())
+        .with_event_cache_ttl(Duration::from_millis(200));
+
+    assert_eq!(client.fetch_events().await.unwrap().len(), 1);
+    assert_eq!(client.fetch_events().await.unwrap().len(), 1);
+    // Clones share the cache
+    assert_eq!(client.clone().fetch_events().await.unwrap().len(), 1);
+    assert_eq!(server.requests().len(), 1);
+
+    tokio::time::sleep(Duration::from_millis(250)).await;
+    assert_eq!(client.fetch_events().await.unwrap().len(), 1);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn proxied_client_sends_requests_through_the_proxy() {
+    let page = r#"{"events": [{"event_ticker": "KXBTC-A", "title": "BTC above $70k"}], "cursor": ""}"#;
+    let proxy = MockServer::start(&[("/trade-api/v2/events", page)]).await;
+    // Unresolvable, so the request can only succeed by going through the proxy
+    let client = KalshiClient::new(String::new(), String::new())
+        .with_base_url("http://kalshi.invalid".to_string())
+        .with_proxy(proxy.url.clone())
+        .unwrap();
+
+    assert_eq!(client.fetch_events().await.unwrap().len(), 1);
+    let requests = proxy.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("GET http://kalshi.invalid/trade-api/v2/events"), "{}", requests[0]);
+
+    assert!(KalshiClient::new(String::new(), String::new()).with_proxy("ftp://127.0.0.1:21".to_string()).is_err());
+    assert!(PolymarketClient::new().with_proxy("127.0.0.1:8080".to_string()).is_err());
+}
+
+/// A GraphQL page of `count` Polymarket markets, numbered from `first`
+fn polymarket_page(first: usize, count: usize) -> String {
+    let markets: Vec<_> = (first..first + count)
+        .map(|i| serde_json::json!({"id": format!("pm-{}", i), "question": format!("Market {}?", i), "outcomes": []}))
+        .collect();
+    serde_json::json!({"data": {"markets": markets}}).to_string()
+}
+
+#[tokio::test]
+async fn polymarket_pages_until_a_short_page() {
+    let (full_page, short_page) = (polymarket_page(0, 1000), polymarket_page(1000, 2));
+    let server = MockServer::start(&[("/graphql", &full_page), ("/graphql", &short_page)]).await;
+    let client = PolymarketClient::new().with_base_url(server.url.clone());
+
+    let events = client.fetch_events().await.unwrap();
+
+    assert_eq!(events.len(), 1002);
+    assert_eq!(events[0].event_id, "pm-0");
+    assert_eq!(events[1001].event_id, "pm-1001");
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn polymarket_pagination_stops_at_the_page_cap() {
+    // Every page is full, so only the cap ends the loop
+    let full_page = polymarket_page(0, 1000);
+    let server = MockServer::start(&[("/graphql", &full_page)]).await;
+    let client = PolymarketClient::new().with_base_url(server.url.clone());
+
+    client.fetch_events().await.unwrap();
+
+    assert_eq!(server.requests().len(), 50);
+}
+
+#[tokio::test]
+async fn price_stream_resubscribes_after_a_disconnect() {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::Message;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+    let subscriptions = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    // Each connection gets one book update, then the server hangs up
+    let server_subscriptions = subscriptions.clone();
+    tokio::spawn(async move {
+        for bid in ["0.40", "0.45"] {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            if let Some(Ok(Message::Text(subscribe))) = ws.next().await {
+                server_subscriptions.lock().unwrap().push(subscribe);
+            }
+            let update = serde_json::json!({
+                "market": "pm-btc",
+                "bids": [{"price": bid, "size": "100"}],
+                "asks": [{"price": "0.55", "size": "100"}],
+            });
+            ws.send(Message::Text(update.to_string())).await.unwrap();
+            ws.close(None).await.unwrap();
+        }
+    });
+
+    let client = PolymarketClient::new()
+        .with_ws_url(ws_url)
+        .with_retry_policy(RetryPolicy::new(3, Duration::from_millis(1)));
+    let updates: Vec<_> = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.subscribe_prices(vec!["pm-btc".to_string()]).take(2).collect(),
+    )
+    .await
+    .expect("both updates arrive");
+
+    let yes: Vec<f64> = updates.iter().map(|(_, prices)| prices.yes).collect();
+    assert_eq!(yes, [0.40, 0.45]);
+    assert!(updates.iter().all(|(market, _)| market == "pm-btc"));
+
+    let subscriptions = subscriptions.lock().unwrap();
+    assert_eq!(subscriptions.len(), 2, "reconnected and subscribed again");
+    assert!(subscriptions.iter().all(|s| s.contains("pm-btc")));
+}
+
+#[tokio::test]
+async fn manifold_refuses_to_sell_below_the_minimum_price() {
+    let server = MockServer::start(&[("/market/mf-btc", r#"{"id": "mf-btc", "probability": 0.30}"#)]).await;
+    let client = ManifoldClient::new(String::new()).with_base_url(server.url.clone());
+
+    let result = client.sell_order("mf-btc".into(), "YES".into(), 10.0, 0.35).await;
+
+    assert!(matches!(result, Err(BotError::InvalidPrice(_))), "{:?}", result);
+    assert_eq!(server.requests(), ["GET /market/mf-btc"], "no sale is posted");
+}
@@ -0,0 +1,162 @@
+// Minimal HTTP server answering canned JSON, for pointing venue clients at
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Serves one JSON body per path (matched on the path without its query),
+/// 404s anything else, and records what it was asked for. Also works as an
+/// HTTP proxy, serving absolute-form requests from the same routes. A path listed more
+/// than once answers with each of its bodies in turn, then repeats the last.
+pub struct MockServer {
+    pub url: String,
+    state: Arc<ServerState>,
+}
+
+struct ServerState {
+    routes: Vec<(String, String)>,
+    delay: Duration, // Before answering each request
+    requests: Mutex<Vec<String>>, // "METHOD /path", in arrival order
+    bodies: Mutex<Vec<String>>,   // Request bodies, in the same order
+    failures: Mutex<Vec<u16>>,     // Statuses to answer with before serving routes again
+    hits: Mutex<HashMap<String, usize>>, // Requests answered per route path
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+impl MockServer {
+    /// Serve `routes` of (path, JSON body) on a free localhost port
+    pub async fn start(routes: &[(&str, &str)]) -> Self {
+        Self::with_delay(routes, Duration::ZERO).await
+    }
+
+    /// Like `start`, but hold every request for `delay` before answering
+    pub async fn with_delay(routes: &[(&str, &str)], delay: Duration) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(ServerState {
+            routes: routes.iter().map(|(path, body)| (path.to_string(), body.to_string())).collect(),
+            delay,
+            requests: Mutex::new(Vec::new()),
+            bodies: Mutex::new(Vec::new()),
+            failures: Mutex::new(Vec::new()),
+            hits: Mutex::new(HashMap::new()),
+            in_flight: AtomicUsize::new(0),
+            max_in_flight: AtomicUsize::new(0),
+        });
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(respond(stream, server_state.clone()));
+            }
+        });
+
+        Self { url, state }
+    }
+
+    /// Answer the next requests with these statuses (one each, in order),
+    /// whatever their path
+    pub fn fail_next(&self, statuses: &[u16]) {
+        self.state.failures.lock().unwrap().extend_from_slice(statuses);
+    }
+
+    /// Requests received so far, as "METHOD /path"
+    pub fn requests(&self) -> Vec<String> {
+        self.state.requests.lock().unwrap().clone()
+    }
+
+    /// Bodies of the requests received so far (empty for bodiless requests)
+    pub fn bodies(&self) -> Vec<String> {
+        self.state.bodies.lock().unwrap().clone()
+    }
+
+    /// Most requests ever being answered at once
+    pub fn max_in_flight(&self) -> usize {
+        self.state.max_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+async fn respond(mut stream: TcpStream, state: Arc<ServerState>) {
+    let Some((method, path, body)) = read_request(&mut stream).await else {
+        return;
+    };
+    state.requests.lock().unwrap().push(format!("{} {}", method, path));
+    state.bodies.lock().unwrap().push(body);
+
+    let in_flight = state.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+    state.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+    tokio::time::sleep(state.delay).await;
+
+    let failure = {
+        let mut failures = state.failures.lock().unwrap();
+        (!failures.is_empty()).then(|| failures.remove(0))
+    };
+    let route = path.split('?').next().unwrap_or_default();
+    // Proxied requests name the whole URL ("http://host/path"); route on the path
+    let route = match route.strip_prefix("http://") {
+        Some(target) => target.find('/').map_or("/", |start| &target[start..]),
+        None => route,
+    };
+    let bodies: Vec<&String> = state.routes.iter().filter(|(path, _)| path == route).map(|(_, body)| body).collect();
+    let body = (failure.is_none() && !bodies.is_empty()).then(|| {
+        let mut hits = state.hits.lock().unwrap();
+        let hit = hits.entry(route.to_string()).or_default();
+        *hit += 1;
+        bodies[(*hit - 1).min(bodies.len() - 1)]
+    });
+    let response = match (failure, body) {
+        (Some(status), _) => format!("HTTP/1.1 {} Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status),
+        (None, Some(body)) => format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ),
+        (None, None) => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+    state.in_flight.fetch_sub(1, Ordering::SeqCst);
+}
+
+/// Read one request's head and body, returning its method, path and body
+async fn read_request(stream: &mut TcpStream) -> Option<(String, String, String)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let content_length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length").then(|| value.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0);
+    while buffer.len() < head_end + content_length {
+        let read = stream.read(&mut chunk).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+
+    let body_end = buffer.len().min(head_end + content_length);
+    let body = String::from_utf8_lossy(&buffer[head_end..body_end]).to_string();
+    let mut request_line = head.lines().next()?.split_whitespace();
+    Some((request_line.next()?.to_string(), request_line.next()?.to_string(), body))
+}
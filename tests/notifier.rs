@@ -0,0 +1,56 @@
+// Notification payloads sent to a webhook
+
+mod common;
+
+use common::MockServer;
+use polymarket_kalshi_arbitrage_bot::{ArbitrageDetector, Event, MarketPrices, Notifier, Platform, WebhookNotifier};
+use serde_json::{json, Value};
+
+fn sent(server: &MockServer) -> Vec<Value> {
+    server.bodies().iter().map(|body| serde_json::from_str(body).unwrap()).collect()
+}
+
+#[tokio::test]
+async fn opportunity_payload_names_both_markets() {
+    let server = MockServer::start(&[("/hook", "{}")]).await;
+    let notifier = WebhookNotifier::new(format!("{}/hook", server.url));
+    let pm = Event::new(Platform::Polymarket, "pm-btc".into(), "BTC above $70k".into(), String::new());
+    let kalshi = Event::new(Platform::Kalshi, "KXBTC".into(), "Bitcoin above $70k".into(), String::new());
+    let opportunity = ArbitrageDetector::new(0.0)
+        .check_arbitrage(&MarketPrices::new(0.40, 0.60, 10_000.0), &MarketPrices::new(0.55, 0.45, 10_000.0))
+        .unwrap();
+
+    notifier.notify_opportunity(&pm, &kalshi, &opportunity, 40.0).await.unwrap();
+
+    assert_eq!(server.requests(), ["POST /hook"]);
+    let payload = &sent(&server)[0];
+    assert_eq!(payload["type"], "opportunity");
+    assert_eq!(payload["polymarket_event"], json!({ "id": "pm-btc", "title": "BTC above $70k" }));
+    assert_eq!(payload["kalshi_event"], json!({ "id": "KXBTC", "title": "Bitcoin above $70k" }));
+    assert_eq!(payload["strategy"], opportunity.strategy.as_str());
+    assert_eq!(payload["net_profit"], opportunity.net_profit);
+    assert_eq!(payload["trade_size"], 40.0);
+}
+
+#[tokio::test]
+async fn low_balance_payload() {
+    let server = MockServer::start(&[("/hook", "{}")]).await;
+    let notifier = WebhookNotifier::new(format!("{}/hook", server.url));
+
+    notifier.notify_low_balance(Platform::Kalshi, 12.5, 50.0).await.unwrap();
+
+    assert_eq!(
+        sent(&server),
+        [json!({ "type": "low_balance", "platform": "kalshi", "balance": 12.5, "threshold": 50.0 })]
+    );
+}
+
+#[tokio::test]
+async fn webhook_errors_surface() {
+    let server = MockServer::start(&[("/hook", "{}")]).await;
+    server.fail_next(&[500]);
+    let notifier = WebhookNotifier::new(format!("{}/hook", server.url));
+
+    let error = notifier.notify_low_balance(Platform::Kalshi, 12.5, 50.0).await.unwrap_err();
+    assert!(error.to_string().contains("500"), "{}", error);
+}
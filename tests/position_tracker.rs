@@ -0,0 +1,134 @@
+// Position bookkeeping and statistics
+
+use polymarket_kalshi_arbitrage_bot::{Event, MarketPrices, Platform, Position, PositionTracker};
+use std::collections::HashMap;
+
+fn position(tracker: &mut PositionTracker, cost: f64) -> String {
+    let event = Event::new(Platform::Kalshi, "KXBTC".into(), "BTC above $70k".into(), String::new());
+    let position = Position::new(Platform::Kalshi, &event, "YES".into(), cost * 2.0, cost, 0.5, None);
+    let id = position.id.clone();
+    tracker.add_position(position);
+    id
+}
+
+#[test]
+fn statistics_measure_returns_on_realized_positions_only() {
+    let mut tracker = PositionTracker::new();
+    let won = position(&mut tracker, 50.0);
+    let closed = position(&mut tracker, 50.0);
+    position(&mut tracker, 900.0); // Still open
+
+    tracker.update_position_settlement(&won, true, None); // +50
+    tracker.close_position(&closed, 40.0); // -10
+
+    let stats = tracker.get_statistics();
+    assert!((stats.total_profit - 40.0).abs() < 1e-9);
+    assert!((stats.total_invested - 1000.0).abs() < 1e-9);
+    // $40 on the $100 that's been realized, not on the $1000 ever invested
+    assert!((stats.overall_roi_percent - 40.0).abs() < 1e-9);
+    // Closed positions count alongside settled ones
+    assert!((stats.average_profit_per_position - 20.0).abs() < 1e-9);
+    assert!((stats.win_rate - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn unrealized_pnl_marks_open_positions_to_current_prices() {
+    let event = |platform, id: &str| Event::new(platform, id.into(), "BTC above $70k".into(), String::new());
+    let mut tracker = PositionTracker::new();
+    // 100 YES @ 0.40 and 50 NO @ 0.55, plus one with no current price
+    let pm = event(Platform::Polymarket, "pm-btc");
+    tracker.add_position(Position::new(Platform::Polymarket, &pm, "YES".into(), 100.0, 40.0, 0.40, None));
+    let kalshi = event(Platform::Kalshi, "KXBTC");
+    tracker.add_position(Position::new(Platform::Kalshi, &kalshi, "NO".into(), 50.0, 27.5, 0.55, None));
+    let unpriced = event(Platform::Kalshi, "KXETH");
+    tracker.add_position(Position::new(Platform::Kalshi, &unpriced, "YES".into(), 10.0, 5.0, 0.50, None));
+
+    let prices = HashMap::from([
+        (("polymarket".to_string(), "pm-btc".to_string()), MarketPrices::new(0.46, 0.54, 1_000.0)),
+        (("kalshi".to_string(), "KXBTC".to_string()), MarketPrices::new(0.50, 0.50, 1_000.0)),
+    ]);
+
+    // (0.46 - 0.40) * 100 + (0.50 - 0.55) * 50
+    let expected = 6.0 - 2.5;
+    assert!((tracker.unrealized_pnl(&prices) - expected).abs() < 1e-9);
+    let stats = tracker.get_statistics_with_prices(&prices);
+    assert!((stats.unrealized_profit.unwrap() - expected).abs() < 1e-9);
+    assert_eq!(tracker.get_statistics().unrealized_profit, None);
+}
+
+#[test]
+fn max_drawdown_measures_the_deepest_fall_from_a_peak() {
+    let mut tracker = PositionTracker::new();
+    assert_eq!(tracker.max_drawdown(), 0.0);
+
+    // Realized P&L of -20, +70, -30, +40, -45, +10 gives equity
+    // -20, 50, 20, 60, 15, 25: the fall from 60 to 15 is the deepest
+    for pnl in [-20.0, 70.0, -30.0, 40.0, -45.0, 10.0] {
+        let id = position(&mut tracker, 100.0);
+        tracker.close_position(&id, 100.0 + pnl);
+    }
+
+    let equity: Vec<f64> = tracker.equity_curve().iter().map(|point| point.equity).collect();
+    assert_eq!(equity, [-20.0, 50.0, 20.0, 60.0, 15.0, 25.0]);
+    assert!((tracker.max_drawdown() - 45.0).abs() < 1e-9);
+    assert!((tracker.current_equity() - 25.0).abs() < 1e-9);
+}
+
+#[test]
+fn csv_export_writes_one_row_per_position() {
+    let mut tracker = PositionTracker::new();
+    let won = position(&mut tracker, 50.0);
+    let open = position(&mut tracker, 20.0);
+    tracker.update_position_settlement(&won, true, None);
+
+    let path = std::env::temp_dir().join(format!("positions-{}.csv", uuid::Uuid::new_v4()));
+    tracker.export_csv(&path).unwrap();
+    let mut reader = csv::Reader::from_path(&path).unwrap();
+    let headers = reader.headers().unwrap().clone();
+    let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(headers.len(), 15);
+    assert_eq!(rows.len(), 2);
+    let column = |row: &csv::StringRecord, name: &str| {
+        row[headers.iter().position(|header| header == name).unwrap()].to_string()
+    };
+    let row = |id: &str| rows.iter().find(|row| column(row, "id") == id).unwrap();
+
+    let settled = row(&won);
+    assert_eq!(column(settled, "platform"), "kalshi");
+    assert_eq!(column(settled, "outcome"), "YES");
+    assert_eq!(column(settled, "amount").parse::<f64>().unwrap(), 100.0);
+    assert_eq!(column(settled, "cost").parse::<f64>().unwrap(), 50.0);
+    assert_eq!(column(settled, "status"), "Won");
+    assert_eq!(column(settled, "profit").parse::<f64>().unwrap(), 50.0);
+    assert!(!column(settled, "settled_at").is_empty());
+
+    // Unsettled fields stay blank rather than zero
+    let unsettled = row(&open);
+    assert_eq!(column(unsettled, "status"), "Open");
+    assert_eq!(column(unsettled, "payout"), "");
+    assert_eq!(column(unsettled, "profit"), "");
+    assert_eq!(column(unsettled, "settled_at"), "");
+}
+
+#[test]
+fn saved_positions_load_back_unchanged() {
+    let mut tracker = PositionTracker::new();
+    let won = position(&mut tracker, 50.0);
+    let open = position(&mut tracker, 20.0);
+    tracker.update_position_settlement(&won, true, None);
+
+    let path = std::env::temp_dir().join(format!("positions-{}.json", uuid::Uuid::new_v4()));
+    tracker.save_to_file(&path).unwrap();
+    let loaded = PositionTracker::load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    for id in [&won, &open] {
+        let (before, after) = (tracker.get_position(id).unwrap(), loaded.get_position(id).unwrap());
+        assert_eq!(serde_json::to_value(before).unwrap(), serde_json::to_value(after).unwrap());
+    }
+    assert_eq!(loaded.get_all_positions().len(), 2);
+    assert_eq!(loaded.equity_curve().len(), 1);
+    assert!((loaded.current_equity() - 50.0).abs() < 1e-9);
+}
@@ -0,0 +1,128 @@
+// Recorded venue responses parsed through the live client parsers
+
+use chrono::{TimeZone, Utc};
+use polymarket_kalshi_arbitrage_bot::{Event, Exchange, MarketPrices, Platform, ReplayClient};
+
+fn fixtures(path: &str) -> String {
+    format!("{}/data/fixtures/{}", env!("CARGO_MANIFEST_DIR"), path)
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "expected {}, got {}", expected, actual);
+}
+
+fn assert_prices(prices: &MarketPrices, yes: f64, no: f64, yes_bid: f64, yes_ask: f64, liquidity: f64) {
+    assert_close(prices.yes, yes);
+    assert_close(prices.no, no);
+    assert_close(prices.yes_bid.unwrap(), yes_bid);
+    assert_close(prices.yes_ask.unwrap(), yes_ask);
+    assert_close(prices.liquidity, liquidity);
+}
+
+fn find<'a>(events: &'a [Event], id: &str) -> &'a Event {
+    events.iter().find(|e| e.event_id == id).unwrap()
+}
+
+#[tokio::test]
+async fn polymarket_events_parse_every_field() {
+    let client = ReplayClient::new(Platform::Polymarket, fixtures("polymarket")).unwrap();
+
+    let events = client.fetch_events().await.unwrap();
+
+    assert_eq!(events.len(), 2);
+    let btc = find(&events, "pm-btc-70k");
+    assert_eq!(btc.platform, Platform::Polymarket);
+    assert_eq!(btc.title, "Will Bitcoin be above $70,000 on June 2?");
+    assert_eq!(btc.description, "Resolves YES if BTC trades above $70,000 at 12:00 UTC on June 2.");
+    assert_eq!(btc.resolution_date, Some(Utc.with_ymd_and_hms(2024, 6, 2, 12, 0, 0).unwrap()));
+    assert_eq!(btc.category.as_deref(), Some("crypto"));
+
+    // Null description and an offset timestamp
+    let lakers = find(&events, "pm-lakers-celtics");
+    assert_eq!(lakers.title, "Will the Lakers beat the Celtics?");
+    assert_eq!(lakers.description, "");
+    assert_eq!(lakers.resolution_date, Some(Utc.with_ymd_and_hms(2024, 6, 2, 3, 30, 0).unwrap()));
+    assert_eq!(lakers.category.as_deref(), Some("sports"));
+}
+
+#[tokio::test]
+async fn polymarket_book_parses_to_prices() {
+    let client = ReplayClient::new(Platform::Polymarket, fixtures("polymarket")).unwrap();
+
+    let prices = client.fetch_prices("pm-btc-70k").await.unwrap();
+
+    // Liquidity counts levels within 2 cents of the best bid (0.42) and ask (0.44)
+    let liquidity = 0.42 * 1500.0 + 0.41 * 900.0 + 0.44 * 1200.0 + 0.45 * 800.0;
+    assert_prices(&prices, 0.42, 0.56, 0.42, 0.44, liquidity);
+    assert!(prices.book.is_some());
+}
+
+#[tokio::test]
+async fn kalshi_events_parse_every_field() {
+    let client = ReplayClient::new(Platform::Kalshi, fixtures("kalshi")).unwrap();
+
+    let events = client.fetch_events().await.unwrap();
+
+    assert_eq!(events.len(), 2);
+    let btc = find(&events, "KXBTC-24JUN02-T70000");
+    assert_eq!(btc.platform, Platform::Kalshi);
+    assert_eq!(btc.title, "Will Bitcoin be above $70,000 on June 2?");
+    assert_eq!(btc.description, "Resolves YES if BTC trades above $70,000 at 12:00 UTC on June 2.");
+    assert_eq!(btc.resolution_date, Some(Utc.with_ymd_and_hms(2024, 6, 2, 12, 0, 0).unwrap()));
+    assert_eq!(btc.category.as_deref(), Some("Crypto"));
+
+    // No subtitle or expiration
+    let lakers = find(&events, "KXNBA-24JUN02-LAL");
+    assert_eq!(lakers.title, "Will the Lakers beat the Celtics?");
+    assert_eq!(lakers.description, "");
+    assert_eq!(lakers.resolution_date, None);
+    assert_eq!(lakers.category.as_deref(), Some("Sports"));
+}
+
+#[tokio::test]
+async fn kalshi_book_parses_to_prices() {
+    let client = ReplayClient::new(Platform::Kalshi, fixtures("kalshi")).unwrap();
+
+    let prices = client.fetch_prices("KXBTC-24JUN02-T70000").await.unwrap();
+
+    // Best YES bid 49c; the best NO bid (50c) is a YES ask at 50c
+    let liquidity = 0.49 * 500.0 + 0.50 * 400.0;
+    assert_prices(&prices, 0.49, 0.50, 0.49, 0.50, liquidity);
+}
+
+#[tokio::test]
+async fn malformed_kalshi_events_are_skipped_or_defaulted() {
+    let client = ReplayClient::new(Platform::Kalshi, fixtures("malformed/kalshi")).unwrap();
+
+    let events = client.fetch_events().await.unwrap();
+
+    // Missing and empty tickers are dropped; bad fields fall back to defaults
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_id, "KXETH-24JUN02-T4000");
+    assert_eq!(events[0].title, "");
+    assert_eq!(events[0].resolution_date, None);
+    assert_eq!(events[0].category, None);
+}
+
+#[tokio::test]
+async fn truncated_book_is_an_error() {
+    let client = ReplayClient::new(Platform::Kalshi, fixtures("malformed/kalshi")).unwrap();
+
+    let error = client.fetch_prices("KXETH-24JUN02-T4000").await.unwrap_err();
+
+    assert!(error.to_string().contains("Invalid JSON"), "{}", error);
+}
+
+#[tokio::test]
+async fn graphql_errors_yield_no_events() {
+    let client = ReplayClient::new(Platform::Polymarket, fixtures("malformed/polymarket")).unwrap();
+
+    assert!(client.fetch_events().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn missing_book_is_an_error() {
+    let client = ReplayClient::new(Platform::Polymarket, fixtures("polymarket")).unwrap();
+
+    assert!(client.fetch_prices("pm-unknown").await.is_err());
+}
@@ -0,0 +1,95 @@
+// JSON round trips for the types logged, served over the API and recorded
+
+use polymarket_kalshi_arbitrage_bot::{
+    ArbitrageDetector, Event, EventMatcher, MarketPrices, MatchConfidence, Platform, Position, PositionStatistics,
+    PositionTracker, TradeResult,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Serialize `value`, read it back and check nothing changed on the way
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> Value {
+    let json = serde_json::to_value(value).unwrap();
+    let decoded: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+    json
+}
+
+#[test]
+fn arbitrage_opportunity() {
+    let opportunity = ArbitrageDetector::new(0.0)
+        .check_arbitrage(&MarketPrices::new(0.40, 0.60, 10_000.0), &MarketPrices::new(0.55, 0.45, 10_000.0))
+        .unwrap();
+
+    let json = round_trip(&opportunity);
+    // Legs are self-describing objects rather than tuples
+    assert_eq!(json["polymarket_action"]["outcome"], "YES");
+    assert_eq!(json["kalshi_action"]["outcome"], "NO");
+    assert_eq!(json["kalshi_action"]["side"], opportunity.kalshi_action.side.as_str());
+    assert_eq!(json["net_profit"], opportunity.net_profit);
+}
+
+#[test]
+fn trade_result() {
+    let result = TradeResult {
+        success: false,
+        skipped: false,
+        polymarket_order_id: Some("0xabc".to_string()),
+        kalshi_order_id: None,
+        error: Some("Kalshi order rejected".to_string()),
+    };
+
+    let json = round_trip(&result);
+    assert_eq!(json["polymarket_order_id"], "0xabc");
+    assert_eq!(json["kalshi_order_id"], Value::Null);
+}
+
+#[test]
+fn market_prices() {
+    let prices = MarketPrices::new(0.42, 0.58, 5_000.0);
+
+    let json = round_trip(&prices);
+    assert_eq!(json["yes"], 0.42);
+    assert_eq!(json["no"], 0.58);
+
+    // Quotes recorded without the optional bid/ask fields still load
+    let mut minimal = json.clone();
+    for field in ["yes_bid", "yes_ask", "no_bid", "no_ask"] {
+        minimal.as_object_mut().unwrap().remove(field);
+    }
+    let minimal: MarketPrices = serde_json::from_value(minimal).unwrap();
+    assert_eq!(minimal.yes_bid, None);
+}
+
+#[test]
+fn match_confidence() {
+    let pm = Event::new(Platform::Polymarket, "pm-btc".into(), "Will Bitcoin be above $70,000?".into(), String::new());
+    let kalshi = Event::new(Platform::Kalshi, "KXBTC".into(), "Will Bitcoin be above $70,000?".into(), String::new());
+    let (_, _, confidence) = EventMatcher::new(0.5)
+        .find_matches_with_confidence(&[pm], &[kalshi])
+        .into_iter()
+        .next()
+        .unwrap();
+
+    let json = round_trip::<MatchConfidence>(&confidence);
+    assert_eq!(json["inverted"], false);
+    assert_eq!(json["overall_score"], confidence.overall_score);
+}
+
+#[test]
+fn position_statistics() {
+    let mut tracker = PositionTracker::new();
+    let event = Event::new(Platform::Kalshi, "KXBTC".into(), "BTC above $70k".into(), String::new());
+    let won = Position::new(Platform::Kalshi, &event, "YES".into(), 100.0, 50.0, 0.5, None);
+    let won_id = won.id.clone();
+    tracker.add_position(won);
+    tracker.add_position(Position::new(Platform::Kalshi, &event, "NO".into(), 20.0, 10.0, 0.5, None));
+    tracker.update_position_settlement(&won_id, true, None);
+    let prices = HashMap::from([(("kalshi".to_string(), "KXBTC".to_string()), MarketPrices::new(0.4, 0.6, 1_000.0))]);
+
+    let json = round_trip::<PositionStatistics>(&tracker.get_statistics_with_prices(&prices));
+    assert_eq!(json["won_positions"], 1);
+    assert_eq!(json["open_positions"], 1);
+    assert!(json["unrealized_profit"].is_number());
+}
@@ -0,0 +1,93 @@
+// Settlement checks against in-memory venues
+
+use polymarket_kalshi_arbitrage_bot::{
+    Event, MockExchange, Platform, Position, PositionStatus, PositionTracker, Resolution, SettlementChecker,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+#[tokio::test]
+async fn checks_every_position_within_the_concurrency_cap() {
+    let mut kalshi = MockExchange::new(Platform::Kalshi).with_latency(Duration::from_millis(20));
+    let mut tracker = PositionTracker::new();
+    for i in 0..12 {
+        let event_id = format!("KX-{}", i);
+        let event = Event::new(Platform::Kalshi, event_id.clone(), format!("Event {}", i), String::new());
+        tracker.add_position(Position::new(Platform::Kalshi, &event, "YES".into(), 10.0, 5.0, 0.5, None));
+        let resolution = if i % 2 == 0 { Resolution::Yes } else { Resolution::No };
+        kalshi = kalshi.with_settlement(&event_id, resolution);
+    }
+    let kalshi = Arc::new(kalshi);
+    let tracker = Arc::new(Mutex::new(tracker));
+    let checker = SettlementChecker::new(
+        Arc::new(MockExchange::new(Platform::Polymarket)),
+        kalshi.clone(),
+        tracker.clone(),
+    )
+    .with_max_concurrent_checks(3);
+
+    let settled = checker.check_settlements().await.unwrap();
+
+    assert_eq!(settled, 12);
+    assert_eq!(kalshi.calls(), 24, "one settlement check and one payout lookup each");
+    assert!(kalshi.max_in_flight() <= 3, "{} checks in flight", kalshi.max_in_flight());
+    assert!(kalshi.max_in_flight() > 1, "checks ran one at a time");
+
+    let tracker = tracker.lock().await;
+    let won = tracker
+        .get_all_positions()
+        .iter()
+        .filter(|p| p.status == PositionStatus::Won)
+        .count();
+    assert_eq!(won, 6);
+}
+
+#[tokio::test]
+async fn voided_market_settles_once_with_a_refund() {
+    let event = Event::new(Platform::Polymarket, "pm-void".into(), "Voided market".into(), String::new());
+    let mut tracker = PositionTracker::new();
+    tracker.add_position(Position::new(Platform::Polymarket, &event, "YES".into(), 10.0, 4.0, 0.4, None));
+    let tracker = Arc::new(Mutex::new(tracker));
+    let polymarket = Arc::new(MockExchange::new(Platform::Polymarket).with_settlement("pm-void", Resolution::Void));
+    let checker = SettlementChecker::new(
+        polymarket.clone(),
+        Arc::new(MockExchange::new(Platform::Kalshi)),
+        tracker.clone(),
+    );
+
+    assert_eq!(checker.check_settlements().await.unwrap(), 1);
+    assert_eq!(checker.check_settlements().await.unwrap(), 0, "void is terminal");
+
+    let tracker = tracker.lock().await;
+    let position = tracker.get_all_positions()[0];
+    assert_eq!(position.status, PositionStatus::Settled);
+    assert_eq!(position.payout, Some(4.0));
+    assert_eq!(position.profit, Some(0.0));
+}
+
+#[tokio::test]
+async fn payouts_not_yet_reported_are_reconciled_on_a_later_cycle() {
+    let event = Event::new(Platform::Kalshi, "KXBTC".into(), "BTC above $70k".into(), String::new());
+    let mut tracker = PositionTracker::new();
+    tracker.add_position(Position::new(Platform::Kalshi, &event, "YES".into(), 10.0, 4.0, 0.4, None));
+    let tracker = Arc::new(Mutex::new(tracker));
+    let kalshi = Arc::new(MockExchange::new(Platform::Kalshi).with_settlement("KXBTC", Resolution::Yes));
+    let checker = SettlementChecker::new(
+        Arc::new(MockExchange::new(Platform::Polymarket)),
+        kalshi.clone(),
+        tracker.clone(),
+    );
+
+    assert_eq!(checker.check_settlements().await.unwrap(), 1);
+    assert_eq!(tracker.lock().await.get_all_positions()[0].payout, Some(10.0));
+
+    // The venue reports fees taken from the payout a cycle later
+    kalshi.set_payout("KXBTC", 0.98);
+    assert_eq!(checker.check_settlements().await.unwrap(), 0);
+
+    let tracker = tracker.lock().await;
+    let position = tracker.get_all_positions()[0];
+    assert_eq!(position.payout, Some(9.8));
+    assert!((position.profit.unwrap() - 5.8).abs() < 1e-9);
+}
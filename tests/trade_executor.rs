@@ -0,0 +1,290 @@
+// Trade execution against in-memory venues
+
+use polymarket_kalshi_arbitrage_bot::{
+    ArbitrageDetector, ArbitrageOpportunity, BotError, Event, Exchange, MarketPrices, MockExchange, Platform, PositionTracker,
+    TradeExecutor,
+};
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+fn events() -> (Event, Event) {
+    let pm = Event::new(Platform::Polymarket, "pm-btc".into(), "BTC above $70k".into(), String::new());
+    let kalshi = Event::new(Platform::Kalshi, "KXBTC".into(), "BTC above $70k".into(), String::new());
+    (pm, kalshi)
+}
+
+/// Buy YES on Polymarket at 0.40 and NO on Kalshi at 0.45
+fn prices() -> (MarketPrices, MarketPrices) {
+    (MarketPrices::new(0.40, 0.60, 10_000.0), MarketPrices::new(0.55, 0.45, 10_000.0))
+}
+
+fn opportunity() -> ArbitrageOpportunity {
+    let (pm_prices, kalshi_prices) = prices();
+    ArbitrageDetector::new(0.0)
+        .check_arbitrage(&pm_prices, &kalshi_prices)
+        .expect("prices leave an arbitrage")
+}
+
+fn venues() -> (MockExchange, MockExchange) {
+    let (pm_prices, kalshi_prices) = prices();
+    (
+        MockExchange::new(Platform::Polymarket).with_prices("pm-btc", pm_prices),
+        MockExchange::new(Platform::Kalshi).with_prices("KXBTC", kalshi_prices),
+    )
+}
+
+#[tokio::test]
+async fn filled_arbitrage_records_both_legs() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm), Arc::new(kalshi));
+    let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_position_tracker(tracker.clone());
+    let (pm_event, kalshi_event) = events();
+
+    let result = executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert_eq!(pm.orders().len(), 1);
+    assert_eq!(kalshi.orders().len(), 1);
+    assert_eq!(pm.orders()[0].outcome, "YES");
+    assert_eq!(kalshi.orders()[0].outcome, "NO");
+
+    // Both legs hold the shares $40 buys on the dearer (Kalshi 0.45) leg
+    let shares = 40.0 / 0.45;
+    let tracker = tracker.lock().await;
+    let positions = tracker.get_open_positions();
+    assert_eq!(positions.len(), 2);
+    assert!(positions.iter().all(|p| (p.amount - shares).abs() < 1e-6));
+    let cost = |platform| positions.iter().find(|p| p.platform == platform).unwrap().cost;
+    assert!((cost(Platform::Polymarket) - shares * 0.40).abs() < 1e-6);
+    assert!((cost(Platform::Kalshi) - 40.0).abs() < 1e-6);
+}
+
+/// Log sink for a test-local subscriber
+#[derive(Clone, Default)]
+struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+impl Write for CapturedLogs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn execution_logs_carry_the_trade_amount() {
+    let logs = CapturedLogs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let (pm, kalshi) = venues();
+    let executor = TradeExecutor::new(Arc::new(pm), Arc::new(kalshi));
+    let (pm_event, kalshi_event) = events();
+    executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(logs.contains("Executing arbitrage"), "{}", logs);
+    assert!(logs.contains("amount=40.0"), "{}", logs);
+    assert!(logs.contains("pm_event_id=pm-btc"), "{}", logs);
+}
+
+#[tokio::test]
+async fn rejected_leg_unwinds_the_filled_one() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm), Arc::new(kalshi.with_rejected_orders()));
+    let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_position_tracker(tracker.clone());
+    let (pm_event, kalshi_event) = events();
+
+    let result = executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+
+    assert!(!result.success);
+    assert!(result.error.unwrap().contains("Kalshi"));
+    assert!(tracker.lock().await.get_open_positions().is_empty());
+
+    // The Polymarket leg filled alone, so it's sold straight back
+    let pm_orders = pm.orders();
+    assert_eq!(pm_orders.len(), 2);
+    assert!(pm_orders[1].sell);
+    assert_eq!(pm_orders[1].outcome, "YES");
+    assert!((pm_orders[1].shares - 40.0 / 0.45).abs() < 1e-6);
+}
+
+#[tokio::test]
+async fn partial_fill_buys_up_the_short_leg_and_sells_the_rest() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm), Arc::new(kalshi.with_fill_ratio(0.6)));
+    let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_position_tracker(tracker.clone());
+    let (pm_event, kalshi_event) = events();
+
+    let result = executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+    assert!(result.success);
+
+    // Both legs order 88.89 shares: Polymarket fills them all, Kalshi 60% (53.33)
+    let shares = 40.0 / 0.45;
+    let kalshi_orders = kalshi.orders();
+    assert_eq!(kalshi_orders.len(), 2);
+    let buy_up = &kalshi_orders[1];
+    assert!(!buy_up.sell);
+    assert_eq!(buy_up.outcome, "NO");
+    assert!((buy_up.shares - shares * 0.4).abs() < 1e-6);
+    assert!((buy_up.price - 0.45).abs() < 1e-9);
+
+    // The buy-up fills 60% too, leaving Polymarket's excess to sell
+    let kalshi_shares = shares * 0.6 + buy_up.shares * 0.6;
+    let pm_orders = pm.orders();
+    assert_eq!(pm_orders.len(), 2);
+    assert!(pm_orders[1].sell);
+    assert!((pm_orders[1].shares - (shares - kalshi_shares)).abs() < 1e-6);
+
+    let tracker = tracker.lock().await;
+    let positions = tracker.get_open_positions();
+    assert_eq!(positions.len(), 2);
+    assert!(positions.iter().all(|p| (p.amount - kalshi_shares).abs() < 1e-6));
+}
+
+#[tokio::test]
+async fn resting_leg_is_cancelled_at_the_fill_timeout() {
+    let (pm, kalshi) = venues();
+    let kalshi = kalshi.with_fill_ratio(0.0).with_order_status("resting");
+    let (pm, kalshi) = (Arc::new(pm), Arc::new(kalshi));
+    let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone())
+        .with_position_tracker(tracker.clone())
+        .with_fill_timeout(Duration::ZERO);
+    let (pm_event, kalshi_event) = events();
+
+    let result = executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+
+    // Nothing hedged: the filled Polymarket leg is sold back whole
+    assert!(!result.success);
+    let pm_orders = pm.orders();
+    assert_eq!(pm_orders.len(), 2);
+    assert!(pm_orders[1].sell);
+    assert!((pm_orders[1].shares - 40.0 / 0.45).abs() < 1e-6);
+    assert!(tracker.lock().await.get_open_positions().is_empty());
+}
+
+#[tokio::test]
+async fn short_balance_places_no_orders() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm.with_balance(10.0)), Arc::new(kalshi));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone());
+    let (pm_event, kalshi_event) = events();
+
+    let result = executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await;
+
+    assert!(matches!(result, Err(BotError::InsufficientBalance(_))));
+    assert!(pm.orders().is_empty());
+    assert!(kalshi.orders().is_empty());
+}
+
+#[tokio::test]
+async fn low_gas_balance_places_no_orders() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm.with_gas_balance(0.002)), Arc::new(kalshi));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_min_gas_balance(0.1);
+    let (pm_event, kalshi_event) = events();
+
+    let result = executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await;
+
+    match result {
+        Err(BotError::InsufficientBalance(message)) => assert!(message.contains("MATIC"), "{}", message),
+        other => panic!("expected a MATIC shortfall, got {:?}", other.map(|_| ())),
+    }
+    assert!(pm.orders().is_empty());
+    assert!(kalshi.orders().is_empty());
+}
+
+#[tokio::test]
+async fn close_all_sells_each_position_once() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm), Arc::new(kalshi));
+    let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_position_tracker(tracker.clone());
+    let (pm_event, kalshi_event) = events();
+    executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+
+    let results = executor.close_all_open(&tracker).await;
+
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| matches!(r, Ok(r) if r.success)));
+    for venue in [&pm, &kalshi] {
+        let sells: Vec<_> = venue.orders().into_iter().filter(|o| o.sell).collect();
+        assert_eq!(sells.len(), 1, "one offsetting order on {}", venue.platform());
+        assert!((sells[0].shares - venue.orders()[0].shares).abs() < 1e-9);
+    }
+    assert!(tracker.lock().await.get_open_positions().is_empty());
+}
+
+#[tokio::test]
+async fn close_all_in_dry_run_sells_nothing() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm), Arc::new(kalshi));
+    let tracker = Arc::new(Mutex::new(PositionTracker::new()));
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_position_tracker(tracker.clone());
+    let (pm_event, kalshi_event) = events();
+    executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_dry_run(true);
+    let results = executor.close_all_open(&tracker).await;
+
+    assert!(results.iter().all(|r| matches!(r, Ok(r) if r.skipped)));
+    assert!(pm.orders().iter().chain(kalshi.orders().iter()).all(|o| !o.sell));
+    assert_eq!(tracker.lock().await.get_open_positions().len(), 2);
+}
+
+#[tokio::test]
+async fn revalidation_counts_gas_set_on_a_shared_detector() {
+    let (pm, kalshi) = venues();
+    let (pm, kalshi) = (Arc::new(pm), Arc::new(kalshi));
+    let detector = ArbitrageDetector::new(0.0);
+    let executor = TradeExecutor::new(pm.clone(), kalshi.clone()).with_arbitrage_detector(detector.clone());
+    let (pm_event, kalshi_event) = events();
+
+    // 15c of edge per $0.85 pair; $20 of gas on a $40 trade eats all of it
+    detector.set_gas_cost(Some(20.0));
+    let result = executor
+        .execute_arbitrage(&opportunity(), &pm_event, &kalshi_event, 40.0)
+        .await
+        .unwrap();
+
+    assert!(result.skipped);
+    assert!(pm.orders().is_empty() && kalshi.orders().is_empty());
+}